@@ -1,7 +1,9 @@
 use crate::common::{configure_criterion, DomainError};
 use criterion::{criterion_group, BenchmarkId, Criterion};
+use error_rail::types::{SharedComposableError, ThinComposableError};
 use error_rail::{ComposableError, ErrorContext, ErrorOps};
 use std::hint::black_box;
+use std::mem::size_of;
 
 pub fn bench_composable_error_creation(c: &mut Criterion) {
     c.bench_function("core/error_creation", |b| {
@@ -58,6 +60,67 @@ pub fn bench_error_cloning_deep(c: &mut Criterion) {
     group.finish();
 }
 
+/// Returns a result through a few non-inlined frames, as `?` chains do.
+#[inline(never)]
+fn propagate<E>(depth: usize, make: &dyn Fn() -> E) -> Result<u64, E> {
+    if depth == 0 {
+        return Err(make());
+    }
+    let value = propagate(depth - 1, make)?;
+    Ok(value + 1)
+}
+
+pub fn bench_result_size(c: &mut Criterion) {
+    let mut group = c.benchmark_group("core/result_size");
+
+    let sizes = [
+        ("composable", size_of::<Result<u64, ComposableError<DomainError>>>()),
+        ("boxed", size_of::<Result<u64, Box<ComposableError<DomainError>>>>()),
+        ("thin", size_of::<Result<u64, ThinComposableError<DomainError>>>()),
+        ("shared", size_of::<Result<u64, SharedComposableError<DomainError>>>()),
+    ];
+    for (name, size) in sizes {
+        println!("core/result_size/{name}: {size} bytes");
+    }
+
+    let make = || {
+        ComposableError::new(DomainError::Database("Query failed".to_string()))
+            .with_context(ErrorContext::tag("database"))
+    };
+    #[allow(
+        clippy::result_large_err,
+        reason = "measures propagating the unboxed error against the thin one"
+    )]
+    group.bench_function("composable", |b| b.iter(|| black_box(propagate(black_box(8), &make))));
+    group.bench_function("thin", |b| {
+        let make = || make().into_thin();
+        b.iter(|| black_box(propagate(black_box(8), &make)))
+    });
+    group.finish();
+}
+
+pub fn bench_shared_clone(c: &mut Criterion) {
+    let mut group = c.benchmark_group("core/shared_clone");
+
+    let mut err = ComposableError::new(DomainError::Network("Service unavailable".to_string()));
+    for i in 0..10 {
+        err = err.with_context(ErrorContext::metadata("attempt", i));
+    }
+    let thin = err.clone().into_thin();
+    let shared = err.clone().into_shared();
+
+    group.bench_function("composable", |b| b.iter(|| black_box(err.clone())));
+    group.bench_function("thin", |b| b.iter(|| black_box(thin.clone())));
+    group.bench_function("shared", |b| b.iter(|| black_box(shared.clone())));
+    group.bench_function("shared_broadcast_16", |b| {
+        b.iter(|| {
+            let subscribers: Vec<_> = (0..16).map(|_| shared.clone()).collect();
+            black_box(subscribers)
+        })
+    });
+    group.finish();
+}
+
 pub fn bench_error_ops_recover(c: &mut Criterion) {
     c.bench_function("core/ops_recover", |b| {
         b.iter(|| black_box(black_box(Err::<i32, &str>("missing")).recover(|_| Ok(42))))
@@ -79,6 +142,8 @@ criterion_group! {
         bench_composable_error_creation,
         bench_error_cloning_and_arc,
         bench_error_cloning_deep,
+        bench_result_size,
+        bench_shared_clone,
         bench_error_ops_recover,
         bench_error_ops_bimap,
 }
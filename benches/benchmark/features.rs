@@ -9,17 +9,12 @@ use error_rail::ErrorPipeline;
 #[cfg(feature = "serde")]
 use std::hint::black_box;
 
-#[cfg(feature = "std")]
-use error_rail::backtrace;
-
 #[cfg(feature = "std")]
 pub fn bench_backtrace_lazy_success(c: &mut Criterion) {
     c.bench_function("std/backtrace_lazy_success", |b| {
         b.iter(|| {
             let result: Result<UserData, DomainError> = Ok(UserData::new(42));
-            let _ = ErrorPipeline::new(result)
-                .with_context(backtrace!())
-                .finish();
+            let _ = ErrorPipeline::new(result).with_backtrace().finish();
         })
     });
 }
@@ -30,9 +25,7 @@ pub fn bench_backtrace_lazy_error(c: &mut Criterion) {
         b.iter(|| {
             let result: Result<UserData, DomainError> =
                 Err(DomainError::Network("Connection refused".to_string()));
-            let _ = ErrorPipeline::new(result)
-                .with_context(backtrace!())
-                .finish();
+            let _ = ErrorPipeline::new(result).with_backtrace().finish();
         })
     });
 }
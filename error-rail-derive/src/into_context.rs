@@ -0,0 +1,143 @@
+//! Expansion of `#[derive(IntoErrorContext)]`.
+
+use proc_macro2::{Span, TokenStream};
+use quote::{quote, ToTokens};
+use syn::{Data, DeriveInput, Field, LitStr, PathArguments, Type};
+
+/// How a single field is mapped onto the group context.
+enum Role {
+    Metadata(LitStr),
+    Tag,
+    Message,
+    Skip,
+}
+
+pub(crate) fn expand(input: &DeriveInput) -> syn::Result<TokenStream> {
+    let fields = match &input.data {
+        Data::Struct(data) => &data.fields,
+        Data::Enum(data) => {
+            return Err(syn::Error::new_spanned(
+                data.enum_token,
+                "`IntoErrorContext` can only be derived for structs",
+            ))
+        },
+        Data::Union(data) => {
+            return Err(syn::Error::new_spanned(
+                data.union_token,
+                "`IntoErrorContext` can only be derived for structs",
+            ))
+        },
+    };
+
+    let mut has_message = false;
+    let mut steps = Vec::new();
+    for (index, field) in fields.iter().enumerate() {
+        let member = match &field.ident {
+            Some(ident) => ident.to_token_stream(),
+            None => syn::Index::from(index).to_token_stream(),
+        };
+        let default_key = match &field.ident {
+            Some(ident) => ident.to_string(),
+            None => index.to_string(),
+        };
+
+        let role = field_role(field, &default_key)?;
+        if let Role::Message = role {
+            if has_message {
+                return Err(syn::Error::new_spanned(
+                    field,
+                    "only one field can be marked `#[context(message)]`",
+                ));
+            }
+            has_message = true;
+        }
+
+        let call = match &role {
+            Role::Metadata(key) => quote! {
+                use ::error_rail::__private::{DisplayField as _, TypedField as _};
+                builder = builder.metadata(
+                    #key,
+                    (&::error_rail::__private::MetadataField(value)).metadata_value(),
+                );
+            },
+            Role::Tag => quote!(builder = builder.tag(::error_rail::__private::to_string(value));),
+            Role::Message => {
+                quote!(builder = builder.message(::error_rail::__private::to_string(value));)
+            },
+            Role::Skip => continue,
+        };
+
+        steps.push(if is_option(&field.ty) {
+            quote! {
+                if let ::core::option::Option::Some(value) = &self.#member {
+                    #call
+                }
+            }
+        } else {
+            quote! {
+                {
+                    let value = &self.#member;
+                    #call
+                }
+            }
+        });
+    }
+
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let mutability = if steps.is_empty() { quote!() } else { quote!(mut) };
+
+    Ok(quote! {
+        impl #impl_generics ::error_rail::traits::IntoErrorContext for #name #ty_generics #where_clause {
+            fn into_error_context(self) -> ::error_rail::ErrorContext {
+                let #mutability builder = ::error_rail::ErrorContext::builder();
+                #(#steps)*
+                builder.build()
+            }
+        }
+    })
+}
+
+fn field_role(field: &Field, default_key: &str) -> syn::Result<Role> {
+    let mut role = None;
+    for attr in field
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("context"))
+    {
+        attr.parse_nested_meta(|meta| {
+            let parsed = if meta.path.is_ident("tag") {
+                Role::Tag
+            } else if meta.path.is_ident("message") {
+                Role::Message
+            } else if meta.path.is_ident("skip") {
+                Role::Skip
+            } else if meta.path.is_ident("metadata") {
+                if meta.input.peek(syn::Token![=]) {
+                    Role::Metadata(meta.value()?.parse()?)
+                } else {
+                    Role::Metadata(LitStr::new(default_key, Span::call_site()))
+                }
+            } else {
+                return Err(
+                    meta.error("expected one of `tag`, `message`, `skip` or `metadata = \"key\"`")
+                );
+            };
+            if role.replace(parsed).is_some() {
+                return Err(meta.error("a field can only have one `#[context(...)]` role"));
+            }
+            Ok(())
+        })?;
+    }
+    Ok(role.unwrap_or_else(|| Role::Metadata(LitStr::new(default_key, Span::call_site()))))
+}
+
+/// Returns `true` for fields written as `Option<T>` (or a path ending in `Option<T>`).
+fn is_option(ty: &Type) -> bool {
+    let Type::Path(path) = ty else {
+        return false;
+    };
+    path.path.segments.last().is_some_and(|segment| {
+        segment.ident == "Option" && matches!(segment.arguments, PathArguments::AngleBracketed(_))
+    })
+}
@@ -0,0 +1,79 @@
+//! Derive and attribute macros for [error-rail](https://docs.rs/error-rail).
+//!
+//! These macros are re-exported by `error-rail` when its `derive` feature is
+//! enabled; depend on that feature rather than on this crate directly.
+//!
+//! ```toml
+//! [dependencies]
+//! error-rail = { version = "0.11", features = ["derive"] }
+//! ```
+
+use proc_macro::TokenStream;
+use syn::{parse_macro_input, DeriveInput, ItemFn};
+
+mod into_context;
+mod rail_context;
+mod transient;
+
+/// Derives `IntoErrorContext`, turning a struct into a structured group context.
+///
+/// Every field becomes a metadata entry keyed by its name (or index, for tuple
+/// structs). Fields whose type converts into a `MetadataValue` (numbers, `bool`,
+/// `Duration`, strings) keep their type; any other field is rendered with
+/// `Display`. Field attributes change the mapping:
+///
+/// - `#[context(tag)]` adds the field as a tag
+/// - `#[context(metadata = "key")]` uses `key` instead of the field name
+/// - `#[context(message)]` uses the field as the context message (at most one field)
+/// - `#[context(skip)]` leaves the field out
+///
+/// `Option` fields only contribute when they are `Some`. See the
+/// `error_rail::IntoErrorContext` documentation for an example.
+#[proc_macro_derive(IntoErrorContext, attributes(context))]
+pub fn derive_into_error_context(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    into_context::expand(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Derives `TransientError` from declarative attributes on enum variants.
+///
+/// - `#[transient]` marks a variant as retryable; `#[permanent]` (the default) does not
+/// - `#[retry_after(ms = 500)]` or `#[retry_after(secs = 2)]` sets `retry_after_hint`
+/// - `#[max_retries(3)]` sets `max_retries_hint`
+/// - `#[transient(delegate)]` forwards every method to the variant's single field,
+///   which must implement `TransientError` itself
+///
+/// Variants with retry hints are transient unless marked otherwise. Putting
+/// `#[transient]` on the enum makes transient the default for unmarked
+/// variants. On structs, the attributes go on the struct itself.
+#[proc_macro_derive(TransientError, attributes(transient, permanent, retry_after, max_retries))]
+pub fn derive_transient_error(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    transient::expand(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Attaches a lazily formatted context to every error leaving a function.
+///
+/// The optional arguments are `format_args!` arguments and may name the
+/// function's parameters, e.g. `#[rail_context("loading user {id}")]`. The
+/// context also records the function name as `fn` metadata and the location of
+/// the function. It is only formatted when the function returns `Err`, so
+/// parameters used in the message must not be moved by the body.
+///
+/// - `Result<T, E>` becomes `BoxedResult<T, E>`, with `E` wrapped on the error path
+/// - `BoxedResult<T, E>`, `RailResult<T>` and other results whose error already
+///   carries a context stack keep their type; the context is pushed onto it
+///
+/// On `async fn`s wrapping a plain `Result<T, E>` goes through
+/// `FutureResultExt::with_ctx`, so it needs error-rail's `async` feature.
+#[proc_macro_attribute]
+pub fn rail_context(args: TokenStream, item: TokenStream) -> TokenStream {
+    let item = parse_macro_input!(item as ItemFn);
+    rail_context::expand(args.into(), item)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
@@ -0,0 +1,156 @@
+//! Expansion of `#[rail_context]`.
+
+use proc_macro2::TokenStream;
+use quote::{quote, quote_spanned};
+use syn::{GenericArgument, ItemFn, PathArguments, ReturnType, Type};
+
+/// How the error leaving the function receives its context.
+enum Target {
+    /// `Result<T, E>`: the error is wrapped and the signature becomes `BoxedResult<T, E>`.
+    Wrap { ok: Box<Type>, err: Box<Type> },
+    /// The error already carries a context stack, which the context is pushed onto.
+    InPlace,
+}
+
+/// Result aliases whose error already carries a context stack.
+const CONTEXT_RESULTS: &[&str] =
+    &["BoxedResult", "BoxedComposableResult", "ComposableResult", "RailResult"];
+
+/// Error types that already carry a context stack.
+const CONTEXT_ERRORS: &[&str] = &["ComposableError", "BoxedComposableError", "RailError"];
+
+pub(crate) fn expand(args: TokenStream, mut item: ItemFn) -> syn::Result<TokenStream> {
+    let ReturnType::Type(_, ret) = &item.sig.output else {
+        return Err(syn::Error::new_spanned(
+            &item.sig,
+            "`#[rail_context]` requires a function returning `Result<T, E>` or `BoxedResult<T, E>`",
+        ));
+    };
+    let ret = (**ret).clone();
+    let target = classify(&ret)?;
+
+    let ident = &item.sig.ident;
+    let name = ident.to_string();
+    let message = if args.is_empty() {
+        quote!(::core::option::Option::None)
+    } else {
+        quote!(::core::option::Option::Some(::error_rail::__private::format(
+            ::core::format_args!(#args)
+        )))
+    };
+    let context = quote_spanned! {ident.span()=>
+        || {
+            ::error_rail::__private::fn_context(
+                #message,
+                #name,
+                ::error_rail::types::Location::new(::core::file!(), ::core::line!())
+                    .with_column(::core::column!())
+                    .with_module_path(::core::module_path!()),
+            )
+        }
+    };
+
+    let block = &item.block;
+    let body = if item.sig.asyncness.is_some() {
+        let future = quote! {
+            async {
+                let __rail_result: #ret = #block;
+                __rail_result
+            }
+        };
+        match &target {
+            Target::Wrap { .. } => quote! {
+                ::core::result::Result::map_err(
+                    ::error_rail::async_ext::FutureResultExt::with_ctx(#future, #context).await,
+                    ::error_rail::__private::Box::new,
+                )
+            },
+            Target::InPlace => quote! {
+                ::error_rail::__private::attach(#future.await, #context)
+            },
+        }
+    } else {
+        let result = quote! {
+            #[allow(clippy::redundant_closure_call)]
+            let __rail_result = (|| -> #ret #block)();
+        };
+        match &target {
+            Target::Wrap { .. } => quote! {
+                #result
+                ::error_rail::__private::attach_boxed(__rail_result, #context)
+            },
+            Target::InPlace => quote! {
+                #result
+                ::error_rail::__private::attach(__rail_result, #context)
+            },
+        }
+    };
+
+    if let Target::Wrap { ok, err } = target {
+        item.sig.output = syn::parse_quote!(-> ::error_rail::BoxedResult<#ok, #err>);
+    }
+    item.block = syn::parse_quote!({ #body });
+    Ok(quote!(#item))
+}
+
+fn classify(ret: &Type) -> syn::Result<Target> {
+    let unsupported = || {
+        syn::Error::new_spanned(
+            ret,
+            "`#[rail_context]` requires a function returning `Result<T, E>` or `BoxedResult<T, E>`",
+        )
+    };
+    let segment = last_segment(ret).ok_or_else(unsupported)?;
+    if CONTEXT_RESULTS.iter().any(|alias| segment.ident == alias) {
+        return Ok(Target::InPlace);
+    }
+    if segment.ident != "Result" {
+        return Err(unsupported());
+    }
+
+    let PathArguments::AngleBracketed(args) = &segment.arguments else {
+        return Err(unsupported());
+    };
+    let mut types = args.args.iter().filter_map(|arg| match arg {
+        GenericArgument::Type(ty) => Some(ty),
+        _ => None,
+    });
+    let (Some(ok), Some(err), None) = (types.next(), types.next(), types.next()) else {
+        return Err(unsupported());
+    };
+
+    if carries_context(err) {
+        Ok(Target::InPlace)
+    } else {
+        Ok(Target::Wrap { ok: Box::new(ok.clone()), err: Box::new(err.clone()) })
+    }
+}
+
+/// Returns `true` for `ComposableError<E>`, `RailError` and their boxed forms.
+fn carries_context(ty: &Type) -> bool {
+    let Some(segment) = last_segment(ty) else {
+        return false;
+    };
+    if CONTEXT_ERRORS.iter().any(|name| segment.ident == name) {
+        return true;
+    }
+    if segment.ident != "Box" {
+        return false;
+    }
+    match &segment.arguments {
+        PathArguments::AngleBracketed(args) => args.args.iter().any(|arg| match arg {
+            GenericArgument::Type(inner) => carries_context(inner),
+            _ => false,
+        }),
+        _ => false,
+    }
+}
+
+fn last_segment(ty: &Type) -> Option<&syn::PathSegment> {
+    match ty {
+        Type::Path(path) if path.qself.is_none() => path.path.segments.last(),
+        Type::Group(group) => last_segment(&group.elem),
+        Type::Paren(paren) => last_segment(&paren.elem),
+        _ => None,
+    }
+}
@@ -0,0 +1,239 @@
+//! Expansion of `#[derive(TransientError)]`.
+
+use proc_macro2::TokenStream;
+use quote::{quote, quote_spanned};
+use syn::spanned::Spanned;
+use syn::{Attribute, Data, DeriveInput, Fields, LitInt};
+
+/// Whether a variant (or struct) is retried, and how.
+#[derive(Clone, Copy, PartialEq)]
+enum Kind {
+    Transient,
+    Permanent,
+    Delegate,
+}
+
+/// Classification parsed from the attributes of one variant (or of the container).
+#[derive(Default)]
+struct Classification {
+    kind: Option<Kind>,
+    retry_after: Option<TokenStream>,
+    max_retries: Option<LitInt>,
+}
+
+/// Generated bodies of one `match` arm for each trait method.
+struct Arm {
+    pattern: TokenStream,
+    is_transient: TokenStream,
+    is_permanent: TokenStream,
+    retry_after: TokenStream,
+    max_retries: TokenStream,
+}
+
+pub(crate) fn expand(input: &DeriveInput) -> syn::Result<TokenStream> {
+    let container = parse_attrs(&input.attrs)?;
+
+    let arms = match &input.data {
+        Data::Enum(data) => {
+            if container.retry_after.is_some() || container.max_retries.is_some() {
+                return Err(syn::Error::new_spanned(
+                    &input.ident,
+                    "`#[retry_after]` and `#[max_retries]` go on variants, not on the enum",
+                ));
+            }
+            if container.kind == Some(Kind::Delegate) {
+                return Err(syn::Error::new_spanned(
+                    &input.ident,
+                    "`#[transient(delegate)]` goes on variants, not on the enum",
+                ));
+            }
+            let default_kind = container.kind.unwrap_or(Kind::Permanent);
+            data.variants
+                .iter()
+                .map(|variant| {
+                    let classification = parse_attrs(&variant.attrs)?;
+                    let ident = &variant.ident;
+                    let path = quote!(Self::#ident);
+                    arm(path, &variant.fields, classification, default_kind, variant.span())
+                })
+                .collect::<syn::Result<Vec<_>>>()?
+        },
+        Data::Struct(data) => {
+            vec![arm(quote!(Self), &data.fields, container, Kind::Permanent, input.ident.span())?]
+        },
+        Data::Union(data) => {
+            return Err(syn::Error::new_spanned(
+                data.union_token,
+                "`TransientError` can only be derived for enums and structs",
+            ))
+        },
+    };
+
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    if arms.is_empty() {
+        return Ok(quote! {
+            impl #impl_generics ::error_rail::traits::TransientError for #name #ty_generics #where_clause {
+                fn is_transient(&self) -> bool {
+                    match *self {}
+                }
+            }
+        });
+    }
+
+    let patterns: Vec<_> = arms.iter().map(|arm| &arm.pattern).collect();
+    let is_transient = arms.iter().map(|arm| &arm.is_transient);
+    let is_permanent = arms.iter().map(|arm| &arm.is_permanent);
+    let retry_after = arms.iter().map(|arm| &arm.retry_after);
+    let max_retries = arms.iter().map(|arm| &arm.max_retries);
+
+    Ok(quote! {
+        impl #impl_generics ::error_rail::traits::TransientError for #name #ty_generics #where_clause {
+            fn is_transient(&self) -> bool {
+                match self {
+                    #(#patterns => #is_transient,)*
+                }
+            }
+
+            fn is_permanent(&self) -> bool {
+                match self {
+                    #(#patterns => #is_permanent,)*
+                }
+            }
+
+            fn retry_after_hint(&self) -> ::core::option::Option<::core::time::Duration> {
+                match self {
+                    #(#patterns => #retry_after,)*
+                }
+            }
+
+            fn max_retries_hint(&self) -> ::core::option::Option<u32> {
+                match self {
+                    #(#patterns => #max_retries,)*
+                }
+            }
+        }
+    })
+}
+
+/// Builds the match arm for one variant; unclassified variants with retry
+/// hints are transient, others fall back to `default_kind`.
+fn arm(
+    path: TokenStream,
+    fields: &Fields,
+    classification: Classification,
+    default_kind: Kind,
+    span: proc_macro2::Span,
+) -> syn::Result<Arm> {
+    let Classification { kind, retry_after, max_retries } = classification;
+    let has_hints = retry_after.is_some() || max_retries.is_some();
+    let kind = kind.unwrap_or(if has_hints { Kind::Transient } else { default_kind });
+
+    if kind == Kind::Delegate {
+        if has_hints {
+            return Err(syn::Error::new(
+                span,
+                "delegated variants take their hints from the inner error",
+            ));
+        }
+        if fields.len() != 1 {
+            return Err(syn::Error::new(
+                span,
+                "`#[transient(delegate)]` requires exactly one field holding the inner error",
+            ));
+        }
+        let pattern = match fields {
+            Fields::Named(named) => {
+                let field = &named.named[0].ident;
+                quote!(#path { #field: inner })
+            },
+            _ => quote!(#path(inner)),
+        };
+        let delegate = |method: TokenStream| quote_spanned!(span=> ::error_rail::traits::TransientError::#method(inner));
+        return Ok(Arm {
+            pattern,
+            is_transient: delegate(quote!(is_transient)),
+            is_permanent: delegate(quote!(is_permanent)),
+            retry_after: delegate(quote!(retry_after_hint)),
+            max_retries: delegate(quote!(max_retries_hint)),
+        });
+    }
+
+    if kind == Kind::Permanent && has_hints {
+        return Err(syn::Error::new(span, "permanent errors cannot carry retry hints"));
+    }
+
+    let pattern = match fields {
+        Fields::Named(_) => quote!(#path { .. }),
+        Fields::Unnamed(_) => quote!(#path(..)),
+        Fields::Unit => path,
+    };
+    let transient = kind == Kind::Transient;
+    let permanent = !transient;
+    Ok(Arm {
+        pattern,
+        is_transient: quote!(#transient),
+        is_permanent: quote!(#permanent),
+        retry_after: match retry_after {
+            Some(duration) => quote!(::core::option::Option::Some(#duration)),
+            None => quote!(::core::option::Option::None),
+        },
+        max_retries: match max_retries {
+            Some(count) => quote!(::core::option::Option::Some(#count)),
+            None => quote!(::core::option::Option::None),
+        },
+    })
+}
+
+fn parse_attrs(attrs: &[Attribute]) -> syn::Result<Classification> {
+    let mut parsed = Classification::default();
+    for attr in attrs {
+        let path = attr.path();
+        if path.is_ident("transient") || path.is_ident("permanent") {
+            let kind = if path.is_ident("permanent") {
+                attr.meta.require_path_only()?;
+                Kind::Permanent
+            } else if matches!(attr.meta, syn::Meta::Path(_)) {
+                Kind::Transient
+            } else {
+                attr.parse_nested_meta(|meta| {
+                    if meta.path.is_ident("delegate") {
+                        Ok(())
+                    } else {
+                        Err(meta.error("expected `#[transient]` or `#[transient(delegate)]`"))
+                    }
+                })?;
+                Kind::Delegate
+            };
+            if parsed.kind.replace(kind).is_some() {
+                return Err(syn::Error::new_spanned(
+                    attr,
+                    "only one of `#[transient]`, `#[permanent]` or `#[transient(delegate)]` is allowed",
+                ));
+            }
+        } else if path.is_ident("retry_after") {
+            let mut duration = None;
+            attr.parse_nested_meta(|meta| {
+                let value: LitInt = meta.value()?.parse()?;
+                let constructor = if meta.path.is_ident("ms") {
+                    quote!(from_millis)
+                } else if meta.path.is_ident("secs") {
+                    quote!(from_secs)
+                } else {
+                    return Err(meta.error("expected `ms = ...` or `secs = ...`"));
+                };
+                if duration.is_some() {
+                    return Err(meta.error("`retry_after` takes a single duration"));
+                }
+                duration = Some(quote!(::core::time::Duration::#constructor(#value)));
+                Ok(())
+            })?;
+            parsed.retry_after = Some(duration.ok_or_else(|| {
+                syn::Error::new_spanned(attr, "expected `#[retry_after(ms = ...)]`")
+            })?);
+        } else if path.is_ident("max_retries") {
+            parsed.max_retries = Some(attr.parse_args()?);
+        }
+    }
+    Ok(parsed)
+}
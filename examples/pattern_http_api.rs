@@ -3,7 +3,8 @@
 //! Converting internal errors to structured HTTP responses with appropriate
 //! status codes.
 
-use error_rail::{context, ComposableError, ErrorPipeline};
+use error_rail::types::MetadataValue;
+use error_rail::{context, ComposableError, ErrorContext, ErrorPipeline};
 
 #[derive(Debug)]
 enum ApiError {
@@ -28,8 +29,13 @@ impl std::fmt::Display for ApiError {
 
 impl std::error::Error for ApiError {}
 
-// Map error to HTTP status code
+// Map error to HTTP status code, honouring a status set by the handler
 fn error_to_status_code(err: &ComposableError<ApiError>) -> u16 {
+    if let Some(&MetadataValue::U64(status)) = err.metadata_any("http_status") {
+        if let Ok(status) = u16::try_from(status) {
+            return status;
+        }
+    }
     match err.core_error() {
         ApiError::NotFound => 404,
         ApiError::Unauthorized => 401,
@@ -46,6 +52,13 @@ fn get_resource(resource_id: &str) -> Result<String, Box<ComposableError<ApiErro
             .finish_boxed();
     }
 
+    if resource_id == "legacy" {
+        // Retired resources answer 410 Gone instead of 404
+        return ErrorPipeline::new(Err(ApiError::NotFound))
+            .with_context(ErrorContext::metadata("http_status", 410u16))
+            .finish_boxed();
+    }
+
     // Simulate resource fetch
     ErrorPipeline::new(Err(ApiError::NotFound))
         .with_context(context!("fetching resource: {}", resource_id))
@@ -74,4 +87,8 @@ fn main() {
     let (status, body) = handle_request("");
     println!("Status: {}, Body: {}", status, body);
     // Output: Status: 400, Body: bad request: resource_id cannot be empty
+
+    let (status, body) = handle_request("legacy");
+    println!("Status: {}, Body: {}", status, body);
+    // Output: Status: 410, Body: resource not found
 }
@@ -13,7 +13,8 @@
 //! - Easily swap retry implementations without changing error handling code
 
 use error_rail::{
-    context, group, traits::TransientError, ComposableError, ErrorContext, ErrorPipeline,
+    context, group, traits::TransientError, types::MetadataValue, ComposableError, ErrorContext,
+    ErrorPipeline,
 };
 use std::time::Duration;
 
@@ -97,6 +98,7 @@ fn call_api(attempt: u32) -> Result<String, ApiError> {
 }
 
 /// Example: Manual retry loop using ErrorPipeline
+#[allow(clippy::result_large_err)]
 fn manual_retry_example() -> Result<String, ComposableError<ApiError>> {
     let max_attempts = 5;
 
@@ -104,15 +106,17 @@ fn manual_retry_example() -> Result<String, ComposableError<ApiError>> {
         let result = call_api(attempt);
 
         let pipeline = ErrorPipeline::new(result)
-            .with_context(group!(tag("api"), metadata("attempt", format!("{}", attempt))))
+            .with_context(group!(tag("api"), metadata("attempt", attempt)))
             .with_retry_context(attempt);
 
         // Use is_transient() to check without consuming pipeline
         if pipeline.is_transient() {
-            if let Some(wait_time) = pipeline.retry_after_hint() {
+            if let (Some(MetadataValue::U64(failed)), Some(wait_time)) =
+                (pipeline.metadata_any("attempt"), pipeline.retry_after_hint())
+            {
                 println!(
                     "Attempt {} failed (transient), waiting {:?} before retry",
-                    attempt, wait_time
+                    failed, wait_time
                 );
                 // In real code: std::thread::sleep(wait_time);
             }
@@ -129,6 +133,7 @@ fn manual_retry_example() -> Result<String, ComposableError<ApiError>> {
 }
 
 /// Example: Using recover_transient for single retry attempt
+#[allow(clippy::result_large_err)]
 fn recover_transient_example() -> Result<String, ComposableError<ApiError>> {
     let result = call_api(1); // Will fail with Timeout
 
@@ -0,0 +1,231 @@
+//! Firmware-style code built on `error_rail::heapless`.
+//!
+//! The crate is `#![no_std]`, does not link `alloc` and depends on error-rail
+//! with `default-features = false`, so it builds for targets without a global
+//! allocator. Its host tests install a counting allocator and check that none
+//! of this code allocates.
+
+#![no_std]
+// Errors are stored inline on purpose; there is no heap to box them on.
+#![allow(clippy::result_large_err)]
+
+use core::fmt::{self, Display, Formatter, Write};
+use error_rail::heapless::{
+    BoundedValidation, ChainFormat, HeaplessContext, HeaplessError, InlineStr,
+};
+use error_rail::Severity;
+
+/// Failure reported by the sensor driver.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SensorError {
+    Nack,
+    OutOfRange(i16),
+}
+
+impl Display for SensorError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Nack => f.write_str("bus nack"),
+            Self::OutOfRange(raw) => write!(f, "reading {} out of range", raw),
+        }
+    }
+}
+
+impl core::error::Error for SensorError {}
+
+/// Error type of the firmware: three contexts, two metadata pairs, 24-byte strings.
+pub type FwError = HeaplessError<SensorError, 3, 2, 24>;
+
+/// Driver-level read of a raw temperature sample.
+pub fn read_raw(addr: u8, sample: Option<i16>) -> Result<i16, FwError> {
+    let raw = sample.ok_or(SensorError::Nack)?;
+    if !(-400..=1250).contains(&raw) {
+        return Err(FwError::new(SensorError::OutOfRange(raw))
+            .with_context(HeaplessContext::tag("i2c").with_metadata("addr", addr))
+            .set_severity(Severity::Warning));
+    }
+    Ok(raw)
+}
+
+/// Reads a temperature in tenths of a degree, adding context on failure.
+pub fn read_temperature(addr: u8, sample: Option<i16>) -> Result<i16, FwError> {
+    read_raw(addr, sample)
+        .map_err(|e| e.with_context(HeaplessContext::format(format_args!("sensor {}", addr))))
+}
+
+/// Sample rate and channel accepted by the device.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Config {
+    pub rate_hz: u32,
+    pub channel: u8,
+}
+
+/// Reason a configuration field was rejected.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConfigError {
+    RateTooLow,
+    RateTooHigh,
+    BadChannel(u8),
+    ChannelReserved,
+}
+
+fn check_rate(rate_hz: u32) -> BoundedValidation<ConfigError, u32, 2> {
+    match rate_hz {
+        0 => BoundedValidation::invalid(ConfigError::RateTooLow),
+        r if r > 1_000 => BoundedValidation::invalid(ConfigError::RateTooHigh),
+        r => BoundedValidation::valid(r),
+    }
+}
+
+fn check_channel(channel: u8) -> BoundedValidation<ConfigError, u8, 2> {
+    let range = if channel < 8 {
+        BoundedValidation::valid(channel)
+    } else {
+        BoundedValidation::invalid(ConfigError::BadChannel(channel))
+    };
+    let reserved = if channel == 0 || channel >= 8 {
+        BoundedValidation::invalid(ConfigError::ChannelReserved)
+    } else {
+        BoundedValidation::valid(())
+    };
+    range.zip(reserved).map(|(channel, ())| channel)
+}
+
+/// Validates every field, keeping at most two errors.
+pub fn validate_config(rate_hz: u32, channel: u8) -> BoundedValidation<ConfigError, Config, 2> {
+    check_rate(rate_hz)
+        .zip(check_channel(channel))
+        .map(|(rate_hz, channel)| Config { rate_hz, channel })
+}
+
+/// Fixed-size log sink standing in for a UART.
+pub type LogLine = InlineStr<96>;
+
+/// Writes one log line for `err` into `sink`.
+pub fn log_error<W: Write>(sink: &mut W, err: &FwError) -> fmt::Result {
+    err.write_chain_with(sink, &ChainFormat::new().separator(" <- "))?;
+    sink.write_str("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use super::*;
+    use core::cell::Cell;
+    use error_rail::heapless::{Text, Value};
+    use std::alloc::{GlobalAlloc, Layout, System};
+
+    /// Forwards to the system allocator and counts allocations per thread.
+    struct CountingAllocator;
+
+    std::thread_local! {
+        static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+    }
+
+    unsafe impl GlobalAlloc for CountingAllocator {
+        unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+            let _ = ALLOCATIONS.try_with(|count| count.set(count.get() + 1));
+            System.alloc(layout)
+        }
+
+        unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+            System.dealloc(ptr, layout)
+        }
+    }
+
+    #[global_allocator]
+    static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+    /// Runs `f` and asserts that it did not allocate.
+    fn assert_no_alloc<T>(f: impl FnOnce() -> T) -> T {
+        let before = ALLOCATIONS.with(Cell::get);
+        let value = f();
+        assert_eq!(ALLOCATIONS.with(Cell::get), before, "unexpected heap allocation");
+        value
+    }
+
+    #[test]
+    fn counting_allocator_sees_allocations() {
+        let before = ALLOCATIONS.with(Cell::get);
+        drop(std::boxed::Box::new(1u8));
+        assert!(ALLOCATIONS.with(Cell::get) > before);
+    }
+
+    #[test]
+    fn errors_and_contexts_do_not_allocate() {
+        let line = assert_no_alloc(|| {
+            let err = read_temperature(0x48, Some(2000)).unwrap_err();
+            let mut line = LogLine::new();
+            log_error(&mut line, &err).unwrap();
+            line
+        });
+        assert_eq!(
+            line.as_str(),
+            "warning: sensor 72 <- [i2c] (addr=72) <- reading 2000 out of range\n"
+        );
+    }
+
+    #[test]
+    fn question_mark_wraps_the_core_error() {
+        let err = assert_no_alloc(|| read_temperature(0x48, None).unwrap_err());
+        assert_eq!(*err.core_error(), SensorError::Nack);
+        assert_eq!(err.context_iter().count(), 1);
+        assert_eq!(assert_no_alloc(|| read_temperature(0x48, Some(215))), Ok(215));
+    }
+
+    #[test]
+    fn overflowing_contexts_are_counted() {
+        let line = assert_no_alloc(|| {
+            let mut err = FwError::new(SensorError::Nack).set_code(7);
+            for ctx in ["a", "b", "c", "d", "e"] {
+                err = err.with_context(ctx);
+            }
+            let mut line = LogLine::new();
+            write!(line, "{}", err).unwrap();
+            line
+        });
+        assert_eq!(
+            line.as_str(),
+            "... 2 more contexts elided ... -> c -> b -> a -> bus nack (code: 7)"
+        );
+    }
+
+    #[test]
+    fn long_messages_are_truncated_in_place() {
+        let channel = 3;
+        let ctx = assert_no_alloc(|| {
+            HeaplessContext::<1, 8>::format(format_args!("calibrating channel {}", channel))
+                .with_metadata("unit", "millidegrees")
+        });
+        assert_eq!(ctx.message(), Some("calibrat"));
+        assert!(ctx.message.unwrap().is_truncated());
+        assert_eq!(ctx.value("unit"), Some(&Value::Str(Text::Static("millidegrees"))));
+    }
+
+    #[test]
+    fn bounded_validation_counts_overflow() {
+        let errors = assert_no_alloc(|| validate_config(0, 9).into_errors().unwrap());
+        assert_eq!(
+            errors.iter().copied().collect::<std::vec::Vec<_>>(),
+            [ConfigError::RateTooLow, ConfigError::BadChannel(9)]
+        );
+        assert_eq!(errors.overflow(), 1);
+        assert_eq!(errors.total(), 3);
+
+        let config = assert_no_alloc(|| validate_config(100, 2).into_value());
+        assert_eq!(config, Some(Config { rate_hz: 100, channel: 2 }));
+    }
+
+    #[test]
+    fn chain_writes_stop_at_the_sink_capacity() {
+        let line = assert_no_alloc(|| {
+            let err = read_temperature(0x48, Some(-999)).unwrap_err();
+            let mut line = InlineStr::<16>::new();
+            err.write_chain(&mut line).unwrap();
+            line
+        });
+        assert_eq!(line.as_str(), "warning: sensor ");
+        assert!(line.is_truncated());
+    }
+}
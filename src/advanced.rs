@@ -4,7 +4,7 @@
 //! Use these types when you need to extend the library or build custom error abstractions.
 
 // Core Internals
-pub use crate::types::composable_error::ComposableError;
+pub use crate::types::composable_error::{Causes, ComposableError, ErrorCause};
 pub use crate::types::ErrorVec;
 
 // Context Builders
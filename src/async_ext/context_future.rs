@@ -12,7 +12,7 @@ use futures_core::future::FusedFuture;
 use pin_project_lite::pin_project;
 
 use crate::traits::IntoErrorContext;
-use crate::types::ComposableError;
+use crate::types::{ComposableError, ContextLimit};
 
 pin_project! {
     /// A Future wrapper that attaches error context lazily.
@@ -42,6 +42,7 @@ pin_project! {
         #[pin]
         future: Fut,
         context_fn: Option<F>,
+        context_limit: Option<ContextLimit>,
     }
 }
 
@@ -49,7 +50,36 @@ impl<Fut, F> ContextFuture<Fut, F> {
     /// Creates a new `ContextFuture` with the given future and context generator.
     #[inline]
     pub const fn new(future: Fut, context_fn: F) -> Self {
-        Self { future, context_fn: Some(context_fn) }
+        Self { future, context_fn: Some(context_fn), context_limit: None }
+    }
+
+    /// Caps the number of contexts kept on the resulting error.
+    ///
+    /// The limit stays on the [`ComposableError`], so contexts added by later
+    /// layers are bounded as well.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use error_rail::prelude_async::*;
+    /// use error_rail::types::ContextLimit;
+    ///
+    /// # #[tokio::main(flavor = "current_thread")]
+    /// # async fn main() {
+    /// let err = async { Err::<(), _>("timeout") }
+    ///     .ctx("fetching user")
+    ///     .with_context_limit(ContextLimit::keep_first(1))
+    ///     .await
+    ///     .unwrap_err()
+    ///     .with_context("handling request");
+    ///
+    /// assert_eq!(err.error_chain(), "... 1 more context elided ... -> fetching user -> timeout");
+    /// # }
+    /// ```
+    #[inline]
+    pub const fn with_context_limit(mut self, limit: ContextLimit) -> Self {
+        self.context_limit = Some(limit);
+        self
     }
 }
 
@@ -74,7 +104,11 @@ where
                     .context_fn
                     .take()
                     .expect("ContextFuture polled after completion");
-                Poll::Ready(Err(ComposableError::new(err).with_context(context_fn())))
+                let error = match *this.context_limit {
+                    Some(limit) => ComposableError::from_core(err).with_context_limit(limit),
+                    None => ComposableError::from_core(err),
+                };
+                Poll::Ready(Err(error.with_context(context_fn())))
             },
         }
     }
@@ -247,7 +247,7 @@ where
             Ok(value) => return Ok(value),
             Err(e) => {
                 if !e.is_transient() {
-                    return Err(ComposableError::new(e)
+                    return Err(ComposableError::from_core(e)
                         .with_context(crate::context!("permanent error, no retry")));
                 }
 
@@ -257,7 +257,7 @@ where
                         attempt += 1;
                     },
                     None => {
-                        return Err(ComposableError::new(e).with_context(crate::context!(
+                        return Err(ComposableError::from_core(e).with_context(crate::context!(
                             "exhausted after {} attempts",
                             attempt + 1
                         )));
@@ -399,7 +399,7 @@ where
             Ok(value) => break Ok(value),
             Err(e) => {
                 if !e.is_transient() {
-                    break Err(ComposableError::new(e)
+                    break Err(ComposableError::from_core(e)
                         .with_context(crate::context!("permanent error, no retry")));
                 }
 
@@ -410,7 +410,7 @@ where
                         attempt += 1;
                     },
                     None => {
-                        break Err(ComposableError::new(e).with_context(crate::context!(
+                        break Err(ComposableError::from_core(e).with_context(crate::context!(
                             "exhausted after {} attempts",
                             attempt + 1
                         )));
@@ -168,7 +168,7 @@ impl<T, E> TimeoutResult<T, E> {
         match self {
             Self::Ok(v) => Ok(v),
             Self::Err(e) => Err(e),
-            Self::Timeout(d) => Err(Box::new(ComposableError::new(E::from(TimeoutError(d))))),
+            Self::Timeout(d) => Err(Box::new(ComposableError::from_core(E::from(TimeoutError(d))))),
         }
     }
 }
@@ -210,7 +210,7 @@ where
 {
     match tokio::time::timeout(duration, future).await {
         Ok(Ok(value)) => TimeoutResult::Ok(value),
-        Ok(Err(e)) => TimeoutResult::Err(Box::new(ComposableError::new(e))),
+        Ok(Err(e)) => TimeoutResult::Err(Box::new(ComposableError::from_core(e))),
         Err(_) => TimeoutResult::Timeout(duration),
     }
 }
@@ -49,7 +49,8 @@ pub trait FutureSpanExt<T, E>: Future<Output = Result<T, E>> + Sized {
     /// Captures the current span's metadata as error context on failure.
     ///
     /// When the future resolves to an error, the current span's name and
-    /// metadata are attached as context to the error.
+    /// metadata are attached as context to the error, and its ID is recorded
+    /// as the error's span ID.
     #[inline]
     fn with_span_context(self) -> SpanContextFuture<Self> {
         SpanContextFuture { inner: self, span: Span::current() }
@@ -92,7 +93,9 @@ where
         match this.inner.poll(cx) {
             Poll::Ready(Ok(value)) => Poll::Ready(Ok(value)),
             Poll::Ready(Err(error)) => Poll::Ready(Err(Box::new(
-                ComposableError::new(error).with_context(span_to_context(this.span)),
+                ComposableError::from_core(error)
+                    .with_context(span_to_context(this.span))
+                    .with_span_ids(this.span),
             ))),
             Poll::Pending => Poll::Pending,
         }
@@ -167,7 +170,11 @@ impl<T, E> ResultSpanExt<T, E> for Result<T, E> {
     fn with_span(self, span: &Span) -> BoxedComposableResult<T, E> {
         match self {
             Ok(v) => Ok(v),
-            Err(e) => Err(Box::new(ComposableError::new(e).with_context(span_to_context(span)))),
+            Err(e) => Err(Box::new(
+                ComposableError::from_core(e)
+                    .with_context(span_to_context(span))
+                    .with_span_ids(span),
+            )),
         }
     }
 }
@@ -187,6 +194,10 @@ impl<T, E> ResultSpanExt<T, E> for Result<T, E> {
 /// // Error now contains context from all active spans
 /// ```
 #[inline]
+#[cfg_attr(feature = "caller-location", track_caller)]
 pub fn instrument_error<E>(error: E) -> ComposableError<E> {
-    ComposableError::new(error).with_context(span_to_context(&Span::current()))
+    let span = Span::current();
+    ComposableError::new(error)
+        .with_context(span_to_context(&span))
+        .with_span_ids(&span)
 }
@@ -36,6 +36,7 @@ use core::fmt::Display;
 /// assert_eq!(err.context().len(), 1);
 /// ```
 #[inline]
+#[cfg_attr(feature = "caller-location", track_caller)]
 pub fn with_context<E, C>(error: E, context: C) -> ComposableError<E>
 where
     C: IntoErrorContext,
@@ -113,6 +114,7 @@ where
 ///     .with_context(context!("step failed"));
 /// ```
 #[inline]
+#[cfg_attr(feature = "caller-location", track_caller)]
 pub fn error_pipeline<T, E>(result: Result<T, E>) -> ErrorPipeline<T, E> {
     ErrorPipeline::new(result)
 }
@@ -136,6 +138,7 @@ pub fn error_pipeline<T, E>(result: Result<T, E>) -> ErrorPipeline<T, E> {
 /// assert_eq!(err.context().len(), 2);
 /// ```
 #[inline]
+#[cfg_attr(feature = "caller-location", track_caller)]
 pub fn accumulate_context<E, I, C>(error: E, contexts: I) -> ComposableError<E>
 where
     I: IntoIterator<Item = C>,
@@ -148,6 +148,7 @@ pub fn composable_to_core<E>(composable: ComposableError<E>) -> E {
 /// assert_eq!(composable.core_error(), &"something failed");
 /// ```
 #[inline]
+#[cfg_attr(feature = "caller-location", track_caller)]
 pub fn core_to_composable<E>(error: E) -> ComposableError<E> {
     ComposableError::new(error)
 }
@@ -201,8 +202,10 @@ pub fn flatten_composable_result<T, E>(result: Result<T, ComposableError<E>>) ->
 /// assert!(wrapped.is_err());
 /// ```
 #[inline]
+#[cfg_attr(feature = "caller-location", track_caller)]
 pub fn wrap_in_composable_result<T, E>(result: Result<T, E>) -> Result<T, ComposableError<E>> {
-    result.map_err(ComposableError::new)
+    let caller = core::panic::Location::caller();
+    result.map_err(|e| ComposableError::new_at(e, caller))
 }
 
 /// Wraps a plain `Result<T, E>` into a boxed `ComposableError`.
@@ -227,8 +230,10 @@ pub fn wrap_in_composable_result<T, E>(result: Result<T, E>) -> Result<T, Compos
 /// assert!(boxed.is_err());
 /// ```
 #[inline]
+#[cfg_attr(feature = "caller-location", track_caller)]
 pub fn wrap_in_composable_result_boxed<T, E>(result: Result<T, E>) -> BoxedComposableResult<T, E> {
-    result.map_err(|e| Box::new(ComposableError::new(e)))
+    let caller = core::panic::Location::caller();
+    result.map_err(|e| Box::new(ComposableError::new_at(e, caller)))
 }
 
 /// Collects multiple errors into a single `Validation`.
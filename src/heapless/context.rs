@@ -0,0 +1,277 @@
+//! Fixed-capacity error contexts.
+
+use crate::heapless::{FixedVec, InlineStr, Text};
+use core::fmt::{Arguments, Display, Formatter};
+use core::panic::Location;
+
+/// The value of a [`HeaplessContext`] metadata entry.
+///
+/// Text longer than `S` bytes is truncated. Floats compare by their bit pattern.
+#[derive(Debug, Clone, Copy)]
+pub enum Value<const S: usize> {
+    /// Text.
+    Str(Text<S>),
+    /// Signed integer.
+    I64(i64),
+    /// Unsigned integer.
+    U64(u64),
+    /// Floating-point number.
+    F64(f64),
+    /// Boolean.
+    Bool(bool),
+}
+
+impl<const S: usize> PartialEq for Value<S> {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::Str(a), Self::Str(b)) => a == b,
+            (Self::I64(a), Self::I64(b)) => a == b,
+            (Self::U64(a), Self::U64(b)) => a == b,
+            (Self::F64(a), Self::F64(b)) => a.to_bits() == b.to_bits(),
+            (Self::Bool(a), Self::Bool(b)) => a == b,
+            _ => false,
+        }
+    }
+}
+
+impl<const S: usize> Eq for Value<S> {}
+
+impl<const S: usize> Display for Value<S> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Str(s) => Display::fmt(s, f),
+            Self::I64(n) => Display::fmt(n, f),
+            Self::U64(n) => Display::fmt(n, f),
+            Self::F64(n) => Display::fmt(n, f),
+            Self::Bool(b) => Display::fmt(b, f),
+        }
+    }
+}
+
+macro_rules! impl_from {
+    ($variant:ident($target:ty): $($source:ty),*) => {
+        $(
+            impl<const S: usize> From<$source> for Value<S> {
+                #[inline]
+                fn from(value: $source) -> Self {
+                    Self::$variant(value as $target)
+                }
+            }
+        )*
+    };
+}
+
+impl_from!(I64(i64): i8, i16, i32, i64, isize);
+impl_from!(U64(u64): u8, u16, u32, u64, usize);
+impl_from!(F64(f64): f32, f64);
+
+impl<const S: usize> From<bool> for Value<S> {
+    #[inline]
+    fn from(value: bool) -> Self {
+        Self::Bool(value)
+    }
+}
+
+impl<const S: usize> From<&'static str> for Value<S> {
+    #[inline]
+    fn from(value: &'static str) -> Self {
+        Self::Str(Text::Static(value))
+    }
+}
+
+impl<const S: usize> From<InlineStr<S>> for Value<S> {
+    #[inline]
+    fn from(value: InlineStr<S>) -> Self {
+        Self::Str(Text::Inline(value))
+    }
+}
+
+impl<const S: usize> From<Text<S>> for Value<S> {
+    #[inline]
+    fn from(value: Text<S>) -> Self {
+        Self::Str(value)
+    }
+}
+
+/// Context entry with up to `M` metadata pairs and strings of up to `S` bytes.
+///
+/// The heapless counterpart of a [`GroupContext`](crate::types::GroupContext),
+/// rendered the same way: `"[tag] at file:line:col: message (key=value, ...)"`.
+/// Metadata beyond the capacity is dropped and counted.
+///
+/// # Examples
+///
+/// ```
+/// use error_rail::heapless::HeaplessContext;
+///
+/// let ctx = HeaplessContext::<2, 16>::tag("i2c")
+///     .with_message("reading sensor")
+///     .with_metadata("addr", 0x48)
+///     .with_metadata("retries", 3)
+///     .with_metadata("bus", 1);
+///
+/// assert_eq!(ctx.dropped_metadata(), 1);
+///
+/// let mut out = error_rail::heapless::InlineStr::<64>::new();
+/// core::fmt::write(&mut out, format_args!("{ctx}")).unwrap();
+/// assert_eq!(out.as_str(), "[i2c] reading sensor (addr=72, retries=3)");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HeaplessContext<const M: usize = 4, const S: usize = 32> {
+    /// Message describing what was being done.
+    pub message: Option<Text<S>>,
+    /// Short label such as a subsystem name.
+    pub tag: Option<&'static str>,
+    /// Source location the context was attached at.
+    pub location: Option<&'static Location<'static>>,
+    /// Key-value pairs, in insertion order.
+    pub metadata: FixedVec<(&'static str, Value<S>), M>,
+    dropped_metadata: usize,
+}
+
+impl<const M: usize, const S: usize> HeaplessContext<M, S> {
+    /// Creates a context with a static message.
+    #[inline]
+    pub const fn new(message: &'static str) -> Self {
+        Self { message: Some(Text::Static(message)), ..Self::empty() }
+    }
+
+    /// Creates a context from `format_args!` output; see [`Text::format`].
+    #[inline]
+    pub fn format(args: Arguments<'_>) -> Self {
+        Self { message: Some(Text::format(args)), ..Self::empty() }
+    }
+
+    /// Creates a context with only a tag.
+    #[inline]
+    pub const fn tag(tag: &'static str) -> Self {
+        Self { tag: Some(tag), ..Self::empty() }
+    }
+
+    /// Creates a context that records the caller's location.
+    #[inline]
+    #[track_caller]
+    pub const fn here() -> Self {
+        Self { location: Some(Location::caller()), ..Self::empty() }
+    }
+
+    const fn empty() -> Self {
+        Self {
+            message: None,
+            tag: None,
+            location: None,
+            metadata: FixedVec::new(),
+            dropped_metadata: 0,
+        }
+    }
+
+    /// Sets the message.
+    #[inline]
+    pub fn with_message<T: Into<Text<S>>>(mut self, message: T) -> Self {
+        self.message = Some(message.into());
+        self
+    }
+
+    /// Sets the tag.
+    #[inline]
+    pub const fn with_tag(mut self, tag: &'static str) -> Self {
+        self.tag = Some(tag);
+        self
+    }
+
+    /// Sets the location.
+    #[inline]
+    pub const fn at(mut self, location: &'static Location<'static>) -> Self {
+        self.location = Some(location);
+        self
+    }
+
+    /// Adds a metadata pair, or counts it as dropped when `M` pairs are already stored.
+    #[inline]
+    pub fn with_metadata<V: Into<Value<S>>>(mut self, key: &'static str, value: V) -> Self {
+        if self.metadata.push((key, value.into())).is_err() {
+            self.dropped_metadata += 1;
+        }
+        self
+    }
+
+    /// Returns the message text, if any.
+    #[inline]
+    pub fn message(&self) -> Option<&str> {
+        self.message.as_ref().map(Text::as_str)
+    }
+
+    /// Returns the value stored under `key`.
+    #[inline]
+    pub fn value(&self, key: &str) -> Option<&Value<S>> {
+        self.metadata
+            .iter()
+            .find(|(k, _)| *k == key)
+            .map(|(_, v)| v)
+    }
+
+    /// Returns how many metadata pairs did not fit.
+    #[inline]
+    pub const fn dropped_metadata(&self) -> usize {
+        self.dropped_metadata
+    }
+}
+
+impl<const M: usize, const S: usize> From<&'static str> for HeaplessContext<M, S> {
+    #[inline]
+    fn from(message: &'static str) -> Self {
+        Self::new(message)
+    }
+}
+
+impl<const M: usize, const S: usize> From<InlineStr<S>> for HeaplessContext<M, S> {
+    #[inline]
+    fn from(message: InlineStr<S>) -> Self {
+        Self { message: Some(Text::Inline(message)), ..Self::empty() }
+    }
+}
+
+impl<const M: usize, const S: usize> Display for HeaplessContext<M, S> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        let mut has_content = false;
+
+        if let Some(tag) = self.tag {
+            write!(f, "[{}]", tag)?;
+            has_content = true;
+        }
+
+        if let Some(loc) = self.location {
+            if has_content {
+                write!(f, " ")?;
+            }
+            write!(f, "at {}", loc)?;
+            has_content = true;
+        }
+
+        if let Some(message) = &self.message {
+            if has_content {
+                if self.location.is_some() {
+                    write!(f, ": ")?;
+                } else {
+                    write!(f, " ")?;
+                }
+            }
+            f.write_str(message.as_str())?;
+            has_content = true;
+        }
+
+        let mut metadata = self.metadata.iter();
+        if let Some((k, v)) = metadata.next() {
+            if has_content {
+                write!(f, " ")?;
+            }
+            write!(f, "({}={}", k, v)?;
+            for (k, v) in metadata {
+                write!(f, ", {}={}", k, v)?;
+            }
+            write!(f, ")")?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,272 @@
+//! Fixed-capacity composable errors and allocation-free chain formatting.
+
+use crate::heapless::{FixedVec, HeaplessContext};
+use crate::types::Severity;
+use core::error::Error;
+use core::fmt::{Debug, Display, Formatter, Write};
+use core::panic::Location;
+
+/// Options for writing a [`HeaplessError`] chain.
+///
+/// The defaults match the `Display` output of
+/// [`ComposableError`](crate::types::ComposableError):
+/// `"severity: ctxN -> ... -> ctx1 -> core (code: N)"`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ChainFormat {
+    separator: &'static str,
+    show_code: bool,
+    show_severity: bool,
+    show_locations: bool,
+}
+
+impl ChainFormat {
+    /// The default format.
+    pub const DEFAULT: Self =
+        Self { separator: " -> ", show_code: true, show_severity: true, show_locations: false };
+
+    /// Creates the default format.
+    #[inline]
+    pub const fn new() -> Self {
+        Self::DEFAULT
+    }
+
+    /// Sets the text written between entries (default: `" -> "`).
+    #[inline]
+    pub const fn separator(mut self, separator: &'static str) -> Self {
+        self.separator = separator;
+        self
+    }
+
+    /// Shows the error code after the core error (default: true).
+    #[inline]
+    pub const fn show_code(mut self, show: bool) -> Self {
+        self.show_code = show;
+        self
+    }
+
+    /// Shows the severity as a prefix (default: true).
+    #[inline]
+    pub const fn show_severity(mut self, show: bool) -> Self {
+        self.show_severity = show;
+        self
+    }
+
+    /// Shows the location the error was created at (default: false).
+    ///
+    /// Locations are only captured with the `caller-location` feature.
+    #[inline]
+    pub const fn show_locations(mut self, show: bool) -> Self {
+        self.show_locations = show;
+        self
+    }
+}
+
+impl Default for ChainFormat {
+    #[inline]
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+/// Error with up to `C` contexts, stored entirely inline.
+///
+/// The heapless counterpart of [`ComposableError`](crate::types::ComposableError).
+/// Each context holds up to `M` metadata pairs and strings of up to `S` bytes.
+/// When `C` contexts are already attached, newer ones are dropped and counted,
+/// like [`ContextLimit::keep_first`](crate::types::ContextLimit::keep_first),
+/// and the chain shows a `"... N more contexts elided ..."` entry in their place.
+///
+/// The error is as large as its capacities, so pick them to fit the stack budget
+/// of the target; clippy's `result_large_err` lint is expected to fire on results
+/// that carry it.
+///
+/// # Examples
+///
+/// ```
+/// use error_rail::heapless::{HeaplessContext, HeaplessError, InlineStr};
+///
+/// #[derive(Debug)]
+/// enum BusError {
+///     Nack,
+/// }
+///
+/// impl core::fmt::Display for BusError {
+///     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+///         f.write_str("bus nack")
+///     }
+/// }
+///
+/// let err = HeaplessError::<_>::new(BusError::Nack)
+///     .with_context(HeaplessContext::tag("i2c").with_metadata("addr", 0x48))
+///     .with_context("reading temperature")
+///     .set_code(17);
+///
+/// let mut out = InlineStr::<96>::new();
+/// err.write_chain(&mut out).unwrap();
+/// assert_eq!(out.as_str(), "reading temperature -> [i2c] (addr=72) -> bus nack (code: 17)");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HeaplessError<E, const C: usize = 4, const M: usize = 4, const S: usize = 32> {
+    core_error: E,
+    contexts: FixedVec<HeaplessContext<M, S>, C>,
+    dropped_contexts: usize,
+    error_code: Option<u32>,
+    severity: Option<Severity>,
+    location: Option<&'static Location<'static>>,
+}
+
+impl<E, const C: usize, const M: usize, const S: usize> HeaplessError<E, C, M, S> {
+    /// Wraps `error` without context or code.
+    ///
+    /// With the `caller-location` feature, the caller is recorded as the error's location.
+    #[inline]
+    #[cfg_attr(feature = "caller-location", track_caller)]
+    pub fn new(error: E) -> Self {
+        Self {
+            core_error: error,
+            contexts: FixedVec::new(),
+            dropped_contexts: 0,
+            error_code: None,
+            severity: None,
+            location: if cfg!(feature = "caller-location") {
+                Some(Location::caller())
+            } else {
+                None
+            },
+        }
+    }
+
+    /// Adds a context entry, or counts it as dropped when `C` contexts are attached.
+    #[inline]
+    pub fn with_context<T: Into<HeaplessContext<M, S>>>(mut self, ctx: T) -> Self {
+        if self.contexts.push(ctx.into()).is_err() {
+            self.dropped_contexts += 1;
+        }
+        self
+    }
+
+    /// Sets the error code.
+    #[inline]
+    pub const fn set_code(mut self, code: u32) -> Self {
+        self.error_code = Some(code);
+        self
+    }
+
+    /// Sets the severity.
+    #[inline]
+    pub const fn set_severity(mut self, severity: Severity) -> Self {
+        self.severity = Some(severity);
+        self
+    }
+
+    /// Returns the core error.
+    #[inline]
+    pub const fn core_error(&self) -> &E {
+        &self.core_error
+    }
+
+    /// Consumes the error, returning the core error.
+    #[inline]
+    pub fn into_core(self) -> E {
+        self.core_error
+    }
+
+    /// Returns an iterator in LIFO order (most recent first) over the kept contexts.
+    #[inline]
+    pub fn context_iter(
+        &self,
+    ) -> core::iter::Rev<crate::heapless::fixed_vec::Iter<'_, HeaplessContext<M, S>>> {
+        self.contexts.iter().rev()
+    }
+
+    /// Returns how many contexts did not fit.
+    #[inline]
+    pub const fn dropped_contexts(&self) -> usize {
+        self.dropped_contexts
+    }
+
+    /// Returns the error code, if set.
+    #[inline]
+    pub const fn error_code(&self) -> Option<u32> {
+        self.error_code
+    }
+
+    /// Returns the severity, if set.
+    #[inline]
+    pub const fn severity(&self) -> Option<Severity> {
+        self.severity
+    }
+
+    /// Returns the location the error was created at, if it was captured.
+    #[inline]
+    pub const fn location(&self) -> Option<&'static Location<'static>> {
+        self.location
+    }
+
+    /// Writes the error chain with the default [`ChainFormat`].
+    #[inline]
+    pub fn write_chain<W: Write + ?Sized>(&self, w: &mut W) -> core::fmt::Result
+    where
+        E: Display,
+    {
+        self.write_chain_with(w, &ChainFormat::DEFAULT)
+    }
+
+    /// Writes the error chain, most recent context first, directly into `w`.
+    pub fn write_chain_with<W: Write + ?Sized>(
+        &self,
+        w: &mut W,
+        format: &ChainFormat,
+    ) -> core::fmt::Result
+    where
+        E: Display,
+    {
+        if let (true, Some(severity)) = (format.show_severity, self.severity) {
+            write!(w, "{}: ", severity)?;
+        }
+        if self.dropped_contexts > 0 {
+            write!(
+                w,
+                "... {} more contexts elided ...{}",
+                self.dropped_contexts, format.separator
+            )?;
+        }
+        for ctx in self.context_iter() {
+            write!(w, "{}{}", ctx, format.separator)?;
+        }
+        write!(w, "{}", self.core_error)?;
+        if let (true, Some(code)) = (format.show_code, self.error_code) {
+            write!(w, " (code: {})", code)?;
+        }
+        if let (true, Some(location)) = (format.show_locations, self.location) {
+            write!(w, " (at {})", location)?;
+        }
+        Ok(())
+    }
+}
+
+impl<E, const C: usize, const M: usize, const S: usize> From<E> for HeaplessError<E, C, M, S> {
+    #[inline]
+    #[cfg_attr(feature = "caller-location", track_caller)]
+    fn from(error: E) -> Self {
+        Self::new(error)
+    }
+}
+
+impl<E: Display, const C: usize, const M: usize, const S: usize> Display
+    for HeaplessError<E, C, M, S>
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        self.write_chain(f)
+    }
+}
+
+impl<E, const C: usize, const M: usize, const S: usize> Error for HeaplessError<E, C, M, S>
+where
+    E: Error + 'static,
+{
+    #[inline]
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(&self.core_error)
+    }
+}
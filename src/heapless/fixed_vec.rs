@@ -0,0 +1,181 @@
+//! Inline vector with a fixed capacity.
+
+use core::fmt::{Debug, Formatter};
+
+/// Vector that stores up to `N` items inline and never allocates.
+///
+/// Pushing onto a full vector hands the item back instead of growing.
+///
+/// # Examples
+///
+/// ```
+/// use error_rail::heapless::FixedVec;
+///
+/// let mut v = FixedVec::<u8, 2>::new();
+/// assert_eq!(v.push(1), Ok(()));
+/// assert_eq!(v.push(2), Ok(()));
+/// assert_eq!(v.push(3), Err(3));
+/// assert!(v.is_full());
+/// assert_eq!(v.iter().copied().sum::<u8>(), 3);
+/// ```
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct FixedVec<T, const N: usize> {
+    items: [Option<T>; N],
+    len: usize,
+}
+
+impl<T, const N: usize> FixedVec<T, N> {
+    /// Creates an empty vector.
+    #[inline]
+    pub const fn new() -> Self {
+        Self { items: [const { None }; N], len: 0 }
+    }
+
+    /// Returns the maximum number of items.
+    #[inline]
+    pub const fn capacity(&self) -> usize {
+        N
+    }
+
+    /// Returns the number of items.
+    #[inline]
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if the vector holds no items.
+    #[inline]
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns `true` if no more items fit.
+    #[inline]
+    pub const fn is_full(&self) -> bool {
+        self.len == N
+    }
+
+    /// Appends `item`, or returns it if the vector is full.
+    #[inline]
+    pub fn push(&mut self, item: T) -> Result<(), T> {
+        match self.items.get_mut(self.len) {
+            Some(slot) => {
+                *slot = Some(item);
+                self.len += 1;
+                Ok(())
+            },
+            None => Err(item),
+        }
+    }
+
+    /// Removes and returns the last item.
+    #[inline]
+    pub fn pop(&mut self) -> Option<T> {
+        let last = self.len.checked_sub(1)?;
+        self.len = last;
+        self.items[last].take()
+    }
+
+    /// Returns the item at `index`.
+    #[inline]
+    pub fn get(&self, index: usize) -> Option<&T> {
+        self.items.get(index)?.as_ref()
+    }
+
+    /// Removes all items.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.items.iter_mut().for_each(|slot| *slot = None);
+        self.len = 0;
+    }
+
+    /// Returns an iterator over the items in insertion order.
+    #[inline]
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter { slots: self.items[..self.len].iter() }
+    }
+}
+
+impl<T, const N: usize> Default for FixedVec<T, N> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Debug, const N: usize> Debug for FixedVec<T, N> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<'a, T, const N: usize> IntoIterator for &'a FixedVec<T, N> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<T, const N: usize> IntoIterator for FixedVec<T, N> {
+    type Item = T;
+    type IntoIter = IntoIter<T, N>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter { slots: self.items.into_iter().flatten() }
+    }
+}
+
+/// Borrowing iterator over a [`FixedVec`].
+#[derive(Debug, Clone)]
+pub struct Iter<'a, T> {
+    slots: core::slice::Iter<'a, Option<T>>,
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.slots.next()?.as_ref()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.slots.size_hint()
+    }
+}
+
+impl<T> DoubleEndedIterator for Iter<'_, T> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.slots.next_back()?.as_ref()
+    }
+}
+
+impl<T> ExactSizeIterator for Iter<'_, T> {}
+
+/// Owning iterator over a [`FixedVec`].
+#[derive(Debug, Clone)]
+pub struct IntoIter<T, const N: usize> {
+    slots: core::iter::Flatten<core::array::IntoIter<Option<T>, N>>,
+}
+
+impl<T, const N: usize> Iterator for IntoIter<T, N> {
+    type Item = T;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.slots.next()
+    }
+}
+
+impl<T, const N: usize> DoubleEndedIterator for IntoIter<T, N> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.slots.next_back()
+    }
+}
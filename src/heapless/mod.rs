@@ -0,0 +1,63 @@
+//! Fixed-capacity errors for targets without an allocator.
+//!
+//! [`ComposableError`](crate::types::ComposableError), [`ErrorContext`](crate::types::ErrorContext)
+//! and [`Validation`](crate::validation::Validation) store their contexts and
+//! errors on the heap and need the `alloc` feature. This module is always
+//! available, including with `default-features = false`, and never allocates:
+//!
+//! - [`HeaplessError`] keeps up to `C` [`HeaplessContext`]s inline, each with
+//!   up to `M` metadata pairs and strings of up to `S` bytes.
+//! - Strings are `&'static str`s or [`InlineStr`]s that truncate at a
+//!   character boundary instead of growing.
+//! - [`BoundedValidation`] accumulates up to `N` errors in an [`ErrorBuffer`]
+//!   and counts the rest.
+//! - [`HeaplessError::write_chain`] writes the error chain directly into any
+//!   [`core::fmt::Write`](::core::fmt::Write), such as a UART or a log ring buffer.
+//!
+//! Contexts, metadata and errors that do not fit are dropped and counted, so
+//! the output always says when something was left out.
+//!
+//! [`HeaplessError`]: crate::heapless::HeaplessError
+//! [`HeaplessError::write_chain`]: crate::heapless::HeaplessError::write_chain
+//! [`HeaplessContext`]: crate::heapless::HeaplessContext
+//! [`InlineStr`]: crate::heapless::InlineStr
+//! [`BoundedValidation`]: crate::heapless::BoundedValidation
+//! [`ErrorBuffer`]: crate::heapless::ErrorBuffer
+//!
+//! # Examples
+//!
+//! ```
+//! use error_rail::heapless::{HeaplessContext, HeaplessError, InlineStr};
+//!
+//! type FwError = HeaplessError<&'static str, 2>;
+//!
+//! fn read_register(addr: u8) -> Result<u16, FwError> {
+//!     Err(FwError::new("timeout")
+//!         .with_context(HeaplessContext::format(format_args!("reading register {addr:#04x}")))
+//!         .set_code(3))
+//! }
+//!
+//! let err = read_register(0x1f)
+//!     .map_err(|e| e.with_context("sampling"))
+//!     .map_err(|e| e.with_context("reporting"))
+//!     .unwrap_err();
+//!
+//! let mut out = InlineStr::<96>::new();
+//! err.write_chain(&mut out).unwrap();
+//! assert_eq!(
+//!     out.as_str(),
+//!     "... 1 more contexts elided ... -> sampling -> reading register 0x1f -> timeout (code: 3)"
+//! );
+//! ```
+
+pub mod context;
+pub mod error;
+pub mod fixed_vec;
+pub mod text;
+pub mod validation;
+
+pub use context::{HeaplessContext, Value};
+pub use error::{ChainFormat, HeaplessError};
+pub use fixed_vec::FixedVec;
+pub use text::{InlineStr, Text};
+pub use validation::{BoundedValidation, ErrorBuffer};
@@ -0,0 +1,243 @@
+//! Bounded strings for messages and metadata values.
+
+use core::fmt::{Arguments, Debug, Display, Formatter, Write};
+use core::hash::{Hash, Hasher};
+use core::ops::Deref;
+
+/// UTF-8 string stored inline in `N` bytes.
+///
+/// Text that does not fit is cut at the last whole character and the string
+/// is marked as truncated; later writes are ignored. Writing never fails, so
+/// `write!` into an `InlineStr` always succeeds.
+///
+/// # Examples
+///
+/// ```
+/// use error_rail::heapless::InlineStr;
+///
+/// let s = InlineStr::<8>::format(format_args!("sensor {}", 12));
+/// assert_eq!(s.as_str(), "sensor 1");
+/// assert!(s.is_truncated());
+///
+/// let s = InlineStr::<4>::from("héllo");
+/// assert_eq!(s.as_str(), "hél");
+/// ```
+#[derive(Clone, Copy)]
+pub struct InlineStr<const N: usize> {
+    buf: [u8; N],
+    len: usize,
+    truncated: bool,
+}
+
+impl<const N: usize> InlineStr<N> {
+    /// Creates an empty string.
+    #[inline]
+    pub const fn new() -> Self {
+        Self { buf: [0; N], len: 0, truncated: false }
+    }
+
+    /// Renders `format_args!` output, truncating what does not fit.
+    #[inline]
+    pub fn format(args: Arguments<'_>) -> Self {
+        let mut s = Self::new();
+        let _ = s.write_fmt(args);
+        s
+    }
+
+    /// Appends `s`, returning `false` if it had to be truncated.
+    pub fn push_str(&mut self, s: &str) -> bool {
+        if self.truncated {
+            return s.is_empty();
+        }
+        let mut end = s.len().min(N - self.len);
+        while !s.is_char_boundary(end) {
+            end -= 1;
+        }
+        self.buf[self.len..self.len + end].copy_from_slice(&s.as_bytes()[..end]);
+        self.len += end;
+        self.truncated = end < s.len();
+        !self.truncated
+    }
+
+    /// Returns the stored text.
+    #[inline]
+    pub fn as_str(&self) -> &str {
+        // Only whole characters are ever copied in.
+        core::str::from_utf8(&self.buf[..self.len]).unwrap_or_default()
+    }
+
+    /// Returns the length in bytes.
+    #[inline]
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if the string is empty.
+    #[inline]
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the capacity in bytes.
+    #[inline]
+    pub const fn capacity(&self) -> usize {
+        N
+    }
+
+    /// Returns `true` if some text did not fit.
+    #[inline]
+    pub const fn is_truncated(&self) -> bool {
+        self.truncated
+    }
+}
+
+impl<const N: usize> Default for InlineStr<N> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const N: usize> From<&str> for InlineStr<N> {
+    #[inline]
+    fn from(s: &str) -> Self {
+        let mut inline = Self::new();
+        inline.push_str(s);
+        inline
+    }
+}
+
+impl<const N: usize> Write for InlineStr<N> {
+    #[inline]
+    fn write_str(&mut self, s: &str) -> core::fmt::Result {
+        self.push_str(s);
+        Ok(())
+    }
+}
+
+impl<const N: usize> Deref for InlineStr<N> {
+    type Target = str;
+
+    #[inline]
+    fn deref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl<const N: usize> AsRef<str> for InlineStr<N> {
+    #[inline]
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl<const N: usize> PartialEq for InlineStr<N> {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.as_str() == other.as_str() && self.truncated == other.truncated
+    }
+}
+
+impl<const N: usize> Eq for InlineStr<N> {}
+
+impl<const N: usize> Hash for InlineStr<N> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.as_str().hash(state);
+        self.truncated.hash(state);
+    }
+}
+
+impl<const N: usize> Debug for InlineStr<N> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        Debug::fmt(self.as_str(), f)
+    }
+}
+
+impl<const N: usize> Display for InlineStr<N> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Text that is either a `&'static str` or an [`InlineStr`] of up to `N` bytes.
+///
+/// Static text costs nothing to store; formatted text is copied inline.
+#[derive(Clone, Copy)]
+pub enum Text<const N: usize> {
+    /// Borrowed static text.
+    Static(&'static str),
+    /// Text rendered into inline storage.
+    Inline(InlineStr<N>),
+}
+
+impl<const N: usize> Text<N> {
+    /// Renders `format_args!` output into `N` bytes, or borrows it when it
+    /// is a plain string literal.
+    #[inline]
+    pub fn format(args: Arguments<'_>) -> Self {
+        match args.as_str() {
+            Some(s) => Self::Static(s),
+            None => Self::Inline(InlineStr::format(args)),
+        }
+    }
+
+    /// Returns the text.
+    #[inline]
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::Static(s) => s,
+            Self::Inline(s) => s.as_str(),
+        }
+    }
+
+    /// Returns `true` if the text was truncated to fit.
+    #[inline]
+    pub const fn is_truncated(&self) -> bool {
+        match self {
+            Self::Static(_) => false,
+            Self::Inline(s) => s.is_truncated(),
+        }
+    }
+}
+
+impl<const N: usize> From<&'static str> for Text<N> {
+    #[inline]
+    fn from(s: &'static str) -> Self {
+        Self::Static(s)
+    }
+}
+
+impl<const N: usize> From<InlineStr<N>> for Text<N> {
+    #[inline]
+    fn from(s: InlineStr<N>) -> Self {
+        Self::Inline(s)
+    }
+}
+
+impl<const N: usize> PartialEq for Text<N> {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.as_str() == other.as_str() && self.is_truncated() == other.is_truncated()
+    }
+}
+
+impl<const N: usize> Eq for Text<N> {}
+
+impl<const N: usize> Hash for Text<N> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.as_str().hash(state);
+        self.is_truncated().hash(state);
+    }
+}
+
+impl<const N: usize> Debug for Text<N> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        Debug::fmt(self.as_str(), f)
+    }
+}
+
+impl<const N: usize> Display for Text<N> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
@@ -0,0 +1,267 @@
+//! Bounded error accumulation.
+
+use crate::heapless::fixed_vec::{FixedVec, Iter};
+
+/// Buffer that keeps the first `N` errors and counts the rest.
+///
+/// # Examples
+///
+/// ```
+/// use error_rail::heapless::ErrorBuffer;
+///
+/// let mut errors = ErrorBuffer::<&str, 2>::new();
+/// errors.push("a");
+/// errors.push("b");
+/// errors.push("c");
+///
+/// assert_eq!(errors.len(), 2);
+/// assert_eq!(errors.overflow(), 1);
+/// assert_eq!(errors.total(), 3);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ErrorBuffer<E, const N: usize> {
+    errors: FixedVec<E, N>,
+    overflow: usize,
+}
+
+impl<E, const N: usize> ErrorBuffer<E, N> {
+    /// Creates an empty buffer.
+    #[inline]
+    pub const fn new() -> Self {
+        Self { errors: FixedVec::new(), overflow: 0 }
+    }
+
+    /// Creates a buffer holding one error.
+    #[inline]
+    pub fn single(error: E) -> Self {
+        let mut buffer = Self::new();
+        buffer.push(error);
+        buffer
+    }
+
+    /// Stores `error`, or counts it as overflow when the buffer is full.
+    #[inline]
+    pub fn push(&mut self, error: E) {
+        if self.errors.push(error).is_err() {
+            self.overflow += 1;
+        }
+    }
+
+    /// Appends the errors of `other` and adds its overflow count.
+    #[inline]
+    pub fn merge(&mut self, other: Self) {
+        self.overflow += other.overflow;
+        for error in other.errors {
+            self.push(error);
+        }
+    }
+
+    /// Maps each stored error, keeping the overflow count.
+    #[inline]
+    pub fn map<F, G>(self, f: F) -> ErrorBuffer<G, N>
+    where
+        F: FnMut(E) -> G,
+    {
+        let mut mapped = ErrorBuffer { errors: FixedVec::new(), overflow: self.overflow };
+        self.errors
+            .into_iter()
+            .map(f)
+            .for_each(|error| mapped.push(error));
+        mapped
+    }
+
+    /// Returns the number of stored errors.
+    #[inline]
+    pub const fn len(&self) -> usize {
+        self.errors.len()
+    }
+
+    /// Returns `true` if no error was pushed.
+    #[inline]
+    pub const fn is_empty(&self) -> bool {
+        self.errors.is_empty() && self.overflow == 0
+    }
+
+    /// Returns how many errors did not fit.
+    #[inline]
+    pub const fn overflow(&self) -> usize {
+        self.overflow
+    }
+
+    /// Returns how many errors were pushed, stored or not.
+    #[inline]
+    pub const fn total(&self) -> usize {
+        self.errors.len() + self.overflow
+    }
+
+    /// Returns an iterator over the stored errors, oldest first.
+    #[inline]
+    pub fn iter(&self) -> Iter<'_, E> {
+        self.errors.iter()
+    }
+}
+
+impl<E, const N: usize> Default for ErrorBuffer<E, N> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'a, E, const N: usize> IntoIterator for &'a ErrorBuffer<E, N> {
+    type Item = &'a E;
+    type IntoIter = Iter<'a, E>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// [`Validation`](crate::validation::Validation) that accumulates at most `N` errors.
+///
+/// Errors past the capacity are counted in the [`ErrorBuffer`]'s overflow
+/// instead of being stored.
+///
+/// # Examples
+///
+/// ```
+/// use error_rail::heapless::BoundedValidation;
+///
+/// fn check(value: i32) -> BoundedValidation<&'static str, i32, 2> {
+///     if value > 0 {
+///         BoundedValidation::valid(value)
+///     } else {
+///         BoundedValidation::invalid("must be positive")
+///     }
+/// }
+///
+/// let all = check(1).zip(check(-1)).zip(check(-2)).zip(check(-3));
+/// let errors = all.into_errors().unwrap();
+/// assert_eq!(errors.len(), 2);
+/// assert_eq!(errors.overflow(), 1);
+/// ```
+#[must_use]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum BoundedValidation<E, A, const N: usize> {
+    Valid(A),
+    Invalid(ErrorBuffer<E, N>),
+}
+
+impl<E, A, const N: usize> BoundedValidation<E, A, N> {
+    /// Creates a valid value.
+    #[inline]
+    pub const fn valid(value: A) -> Self {
+        Self::Valid(value)
+    }
+
+    /// Creates an invalid value from a single error.
+    #[inline]
+    pub fn invalid(error: E) -> Self {
+        Self::Invalid(ErrorBuffer::single(error))
+    }
+
+    /// Returns `true` if the validation contains a value.
+    #[must_use]
+    #[inline]
+    pub const fn is_valid(&self) -> bool {
+        matches!(self, Self::Valid(_))
+    }
+
+    /// Returns `true` if the validation contains errors.
+    #[must_use]
+    #[inline]
+    pub const fn is_invalid(&self) -> bool {
+        matches!(self, Self::Invalid(_))
+    }
+
+    /// Maps the valid value, preserving errors.
+    #[inline]
+    pub fn map<B, F>(self, f: F) -> BoundedValidation<E, B, N>
+    where
+        F: FnOnce(A) -> B,
+    {
+        match self {
+            Self::Valid(value) => BoundedValidation::Valid(f(value)),
+            Self::Invalid(errors) => BoundedValidation::Invalid(errors),
+        }
+    }
+
+    /// Chains a computation that runs only on a valid value.
+    #[inline]
+    pub fn and_then<B, F>(self, f: F) -> BoundedValidation<E, B, N>
+    where
+        F: FnOnce(A) -> BoundedValidation<E, B, N>,
+    {
+        match self {
+            Self::Valid(value) => f(value),
+            Self::Invalid(errors) => BoundedValidation::Invalid(errors),
+        }
+    }
+
+    /// Combines two validations, accumulating the errors of both.
+    ///
+    /// Errors from `self` come first.
+    #[inline]
+    pub fn zip<B>(self, other: BoundedValidation<E, B, N>) -> BoundedValidation<E, (A, B), N> {
+        match (self, other) {
+            (Self::Valid(a), BoundedValidation::Valid(b)) => BoundedValidation::Valid((a, b)),
+            (Self::Invalid(e), BoundedValidation::Valid(_))
+            | (Self::Valid(_), BoundedValidation::Invalid(e)) => BoundedValidation::Invalid(e),
+            (Self::Invalid(mut e1), BoundedValidation::Invalid(e2)) => {
+                e1.merge(e2);
+                BoundedValidation::Invalid(e1)
+            },
+        }
+    }
+
+    /// Maps each stored error, preserving the success branch.
+    #[inline]
+    pub fn map_err<F, G>(self, f: F) -> BoundedValidation<G, A, N>
+    where
+        F: FnMut(E) -> G,
+    {
+        match self {
+            Self::Valid(value) => BoundedValidation::Valid(value),
+            Self::Invalid(errors) => BoundedValidation::Invalid(errors.map(f)),
+        }
+    }
+
+    /// Wraps a `Result`, turning the error into a single-entry buffer.
+    #[inline]
+    pub fn from_result(result: Result<A, E>) -> Self {
+        match result {
+            Ok(value) => Self::Valid(value),
+            Err(error) => Self::invalid(error),
+        }
+    }
+
+    /// Converts into a `Result`.
+    #[inline]
+    pub fn to_result(self) -> Result<A, ErrorBuffer<E, N>> {
+        match self {
+            Self::Valid(value) => Ok(value),
+            Self::Invalid(errors) => Err(errors),
+        }
+    }
+
+    /// Extracts the errors, if any.
+    #[must_use]
+    #[inline]
+    pub fn into_errors(self) -> Option<ErrorBuffer<E, N>> {
+        match self {
+            Self::Valid(_) => None,
+            Self::Invalid(errors) => Some(errors),
+        }
+    }
+
+    /// Extracts the value, if valid.
+    #[must_use]
+    #[inline]
+    pub fn into_value(self) -> Option<A> {
+        match self {
+            Self::Valid(value) => Some(value),
+            Self::Invalid(_) => None,
+        }
+    }
+}
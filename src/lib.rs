@@ -6,6 +6,7 @@
 //! ## Basic Error with Context
 //!
 //! ```
+//! # #[cfg(feature = "alloc")] {
 //! use error_rail::{ComposableError, ErrorContext, group};
 //!
 //! let err = ComposableError::new("database connection failed")
@@ -17,11 +18,13 @@
 //!
 //! assert!(err.to_string().contains("database connection failed"));
 //! assert_eq!(err.error_code(), Some(500));
+//! # }
 //! ```
 //!
 //! ## Validation Accumulation
 //!
 //! ```
+//! # #[cfg(feature = "alloc")] {
 //! use error_rail::validation::Validation;
 //!
 //! let v1: Validation<&str, i32> = Validation::Valid(10);
@@ -29,11 +32,13 @@
 //! let combined: Validation<&str, Vec<i32>> = vec![v1, v2].into_iter().collect();
 //!
 //! assert!(combined.is_invalid());
+//! # }
 //! ```
 //!
 //! ## Error Pipeline
 //!
 //! ```
+//! # #[cfg(feature = "alloc")] {
 //! use error_rail::{ErrorPipeline, context};
 //!
 //! let result = ErrorPipeline::<i32, &str>::new(Err("failed"))
@@ -47,35 +52,48 @@
 //!     assert!(chain.contains("operation: load_config"));
 //!     assert!(chain.contains("failed"));
 //! }
+//! # }
 //! ```
 #![cfg_attr(not(feature = "std"), no_std)]
 
-#[cfg(not(feature = "std"))]
+#[cfg(all(feature = "alloc", not(feature = "std")))]
 extern crate alloc;
 
 #[cfg(feature = "std")]
 extern crate std;
 
 /// Error context management and accumulation
+#[cfg(feature = "alloc")]
 pub mod context;
 /// Error type conversions between Result, Validation, and ComposableError
+#[cfg(feature = "alloc")]
 pub mod convert;
 /// Error handling macros for context creation
+#[cfg(feature = "alloc")]
 pub mod macros;
 /// Convenience re-exports for quick starts
+#[cfg(feature = "alloc")]
 pub mod prelude;
 /// Minimal API for beginners - start here
+#[cfg(feature = "alloc")]
 pub mod simple;
 /// Core traits for error handling and composition
+#[cfg(feature = "alloc")]
 pub mod traits;
 /// ComposableError and error context structures
 pub mod types;
 /// Validation type and associated traits for error accumulation
+#[cfg(feature = "alloc")]
 pub mod validation;
 
+/// Fixed-capacity errors and validation that never allocate
+pub mod heapless;
+
 /// Advanced API level for library authors
+#[cfg(feature = "alloc")]
 pub mod advanced;
 /// Intermediate API level for service developers
+#[cfg(feature = "alloc")]
 pub mod intermediate;
 
 /// Async extensions for error handling (requires `async` feature)
@@ -92,12 +110,176 @@ pub mod tower;
 
 // Re-export common types that might be needed at root,
 // but encourage using prelude/intermediate/advanced modules.
+#[cfg(feature = "alloc")]
 pub use context::*;
+#[cfg(feature = "alloc")]
 pub use convert::*;
+#[cfg(feature = "alloc")]
 pub use prelude::BoxedResult;
+#[cfg(feature = "alloc")]
 pub use traits::*;
+pub use types::Severity;
+#[cfg(feature = "alloc")]
 pub use types::{
     error_formatter::ErrorFormatConfig, BoxedComposableResult, ComposableError, ComposableResult,
-    ErrorContext, ErrorPipeline, ErrorVec, GroupContext, LazyContext, LazyGroupContext,
+    ErrorContext, ErrorPipeline, ErrorVec, GroupContext, LazyContext, LazyGroupContext, RailError,
+    RailResult, RailResultExt,
 };
+#[cfg(feature = "alloc")]
 pub use validation::*;
+
+/// Attaches a lazily formatted context to every error leaving a function
+/// (requires `derive` feature).
+///
+/// The message may name the function's parameters and is only formatted on
+/// the error path. The context also records the function name and location.
+/// A `Result<T, E>` return type becomes [`BoxedResult<T, E>`]; results whose
+/// error already carries contexts, such as `BoxedResult` or [`RailResult`],
+/// keep their type.
+///
+/// # Examples
+///
+/// ```
+/// use error_rail::rail_context;
+///
+/// #[rail_context("loading user {id}")]
+/// fn load_user(id: u64) -> Result<String, &'static str> {
+///     Err("not found")
+/// }
+///
+/// let err = load_user(42).unwrap_err();
+/// let ctx = err.context()[0].to_string();
+/// assert!(ctx.starts_with("at "));
+/// assert!(ctx.ends_with(": loading user 42 (fn=load_user)"));
+/// ```
+#[cfg(feature = "derive")]
+pub use error_rail_derive::rail_context;
+
+/// Support items for code generated by the derive and attribute macros. Not public API.
+#[doc(hidden)]
+#[cfg(feature = "alloc")]
+pub mod __private {
+    pub use crate::types::alloc_type::Box;
+    use crate::types::alloc_type::String;
+    use crate::types::{Location, MetadataValue};
+    use crate::{ComposableError, ErrorContext, RailError};
+    use core::fmt::{Arguments, Display, Write};
+
+    /// Renders `value` with `Display`, without requiring `ToString` in scope.
+    #[inline]
+    pub fn to_string<T: Display + ?Sized>(value: &T) -> String {
+        let mut rendered = String::new();
+        let _ = write!(rendered, "{}", value);
+        rendered
+    }
+
+    /// Wraps a field stored as metadata by `derive(IntoErrorContext)`.
+    ///
+    /// The derived code calls `(&MetadataField(value)).metadata_value()`, which
+    /// resolves to [`TypedField`] when the field converts into a
+    /// [`MetadataValue`] and to [`DisplayField`] otherwise.
+    pub struct MetadataField<'a, T: ?Sized>(pub &'a T);
+
+    /// Keeps the type of fields that convert into a [`MetadataValue`].
+    pub trait TypedField {
+        fn metadata_value(&self) -> MetadataValue;
+    }
+
+    impl<T: Clone + Into<MetadataValue>> TypedField for MetadataField<'_, T> {
+        #[inline]
+        fn metadata_value(&self) -> MetadataValue {
+            self.0.clone().into()
+        }
+    }
+
+    /// Stores any other field as its `Display` output.
+    pub trait DisplayField {
+        fn metadata_value(&self) -> MetadataValue;
+    }
+
+    impl<T: Display + ?Sized> DisplayField for &MetadataField<'_, T> {
+        #[inline]
+        fn metadata_value(&self) -> MetadataValue {
+            MetadataValue::from(to_string(self.0))
+        }
+    }
+
+    /// Renders `format_args!` output without requiring `format!` in scope.
+    #[inline]
+    pub fn format(args: Arguments<'_>) -> String {
+        let mut rendered = String::new();
+        let _ = rendered.write_fmt(args);
+        rendered
+    }
+
+    /// Builds the context attached by `#[rail_context]`.
+    #[inline]
+    pub fn fn_context(
+        message: Option<String>,
+        function: &'static str,
+        location: Location,
+    ) -> ErrorContext {
+        let mut builder = ErrorContext::builder().at(location);
+        if let Some(message) = message {
+            builder = builder.message(message);
+        }
+        builder.metadata("fn", function).build()
+    }
+
+    /// Error types whose context stack `#[rail_context]` pushes onto in place.
+    pub trait ContextStack {
+        fn push_context(&mut self, context: ErrorContext);
+    }
+
+    impl<E> ContextStack for ComposableError<E> {
+        #[inline]
+        fn push_context(&mut self, context: ErrorContext) {
+            self.with_context_inplace(context);
+        }
+    }
+
+    impl<E> ContextStack for Box<ComposableError<E>> {
+        #[inline]
+        fn push_context(&mut self, context: ErrorContext) {
+            self.with_context_inplace(context);
+        }
+    }
+
+    impl ContextStack for RailError {
+        #[inline]
+        fn push_context(&mut self, context: ErrorContext) {
+            self.with_context_inplace(context);
+        }
+    }
+
+    /// Pushes a lazily built context onto an error that already has a context stack.
+    #[inline]
+    pub fn attach<T, E, F>(result: Result<T, E>, context: F) -> Result<T, E>
+    where
+        E: ContextStack,
+        F: FnOnce() -> ErrorContext,
+    {
+        result.map_err(|mut err| {
+            err.push_context(context());
+            err
+        })
+    }
+
+    /// Wraps a plain error together with a lazily built context.
+    #[inline]
+    #[cfg_attr(feature = "caller-location", track_caller)]
+    pub fn attach_boxed<T, E, F>(
+        result: Result<T, E>,
+        context: F,
+    ) -> Result<T, Box<ComposableError<E>>>
+    where
+        F: FnOnce() -> ErrorContext,
+    {
+        let caller = core::panic::Location::caller();
+        result.map_err(|err| {
+            let mut error = ComposableError::new_at(err, caller);
+            error.push_context_from(context(), caller);
+            Box::new(error)
+        })
+    }
+}
@@ -222,14 +222,22 @@ macro_rules! impl_error_context {
 ///
 /// The macro accepts function-call style arguments:
 /// * `message("format string", args...)` - Optional formatted message
+/// * `message_key("order.not_found")` - Optional catalog key of a [localized](crate::types::i18n) message
 /// * `tag("label")` - Categorical tags (can be repeated)
 /// * `location(file, line)` - Source file and line number
-/// * `metadata("key", "value")` - Key-value pairs (can be repeated)
+/// * `metadata("key", value)` - Key-value pairs with typed [values](crate::types::MetadataValue) (can be repeated)
+/// * `set(KEY, value)` - Values written through a typed [`ContextKey`](crate::types::ContextKey) (can be repeated)
+/// * `secret("key", "value")` - Key-value pairs whose value is [redacted](crate::types::redaction) (can be repeated)
+/// * `secret_message("format string", args...)` - Optional formatted message that is redacted
+/// * `severity(Severity::Warning)` - Optional [`Severity`](crate::types::Severity) of the context
+/// * `timestamp(clock.now())` - Optional [`Timestamp`](crate::types::Timestamp), read when the error occurs
+/// * `public()` - Marks the context as safe to show to end users; see [`visibility`](crate::types::visibility)
 ///
 /// # Examples
 ///
 /// ```
 /// use error_rail::{group, ComposableError};
+/// use error_rail::types::Severity;
 ///
 /// let attempts = 3;
 /// let err = ComposableError::<&str>::new("auth failed")
@@ -238,8 +246,11 @@ macro_rules! impl_error_context {
 ///         tag("auth"),
 ///         location(file!(), line!()),
 ///         metadata("retry_count", "3"),
-///         metadata("timeout", "30s")
+///         metadata("timeout", "30s"),
+///         severity(Severity::Warning)
 ///     ));
+///
+/// assert_eq!(err.max_severity(), Some(Severity::Warning));
 /// ```
 #[macro_export]
 macro_rules! group {
@@ -273,6 +284,11 @@ macro_rules! __group_field {
         $builder = $builder.message(format!($($arg)*));
     };
 
+    // Message key field
+    ($builder:expr, message_key, $key:expr) => {
+        $builder = $builder.message_key($key);
+    };
+
     // Tag field
     ($builder:expr, tag, $tag:expr) => {
         $builder = $builder.tag($tag);
@@ -287,16 +303,50 @@ macro_rules! __group_field {
     ($builder:expr, metadata, $key:expr, $value:expr) => {
         $builder = $builder.metadata($key, $value);
     };
+
+    // Typed key field
+    ($builder:expr, set, $key:expr, $value:expr) => {
+        $builder = $builder.set($key, $value);
+    };
+
+    // Secret metadata field
+    ($builder:expr, secret, $key:expr, $value:expr) => {
+        $builder = $builder.secret_metadata($key, $value);
+    };
+
+    // Secret message field
+    ($builder:expr, secret_message, $($arg:tt)*) => {
+        $builder = $builder.secret_message(format!($($arg)*));
+    };
+
+    // Severity field
+    ($builder:expr, severity, $severity:expr) => {
+        $builder = $builder.severity($severity);
+    };
+
+    // Timestamp field
+    ($builder:expr, timestamp, $timestamp:expr) => {
+        $builder = $builder.timestamp($timestamp);
+    };
+
+    // Public visibility field
+    ($builder:expr, public $(,)?) => {
+        $builder = $builder.public();
+    };
 }
 
-/// Captures the current backtrace as lazy error context.
+/// Captures the current backtrace as a structured [`ErrorBacktrace`](crate::types::ErrorBacktrace).
 ///
-/// This macro creates a [`LazyContext`](crate::types::LazyContext) that captures the stack
-/// backtrace only when the error actually occurs, avoiding the performance overhead of
-/// backtrace generation on the success path.
+/// Like [`std::backtrace::Backtrace::capture()`], this only captures frames when
+/// `RUST_LIB_BACKTRACE` or `RUST_BACKTRACE` enables backtraces, and yields an empty
+/// backtrace otherwise. Frames from the standard library, async runtimes and
+/// error-rail are filtered out unless the variable is set to `full`.
 ///
-/// The backtrace is captured using [`std::backtrace::Backtrace::capture()`] and converted
-/// to a string representation when the context is evaluated.
+/// Capture is eager: the stack is walked as soon as the macro is evaluated,
+/// not deferred until an error occurs as with the earlier `LazyContext`-based
+/// macro. Invoke it on the error path, e.g. inside `map_err`, and attach the
+/// result with [`ComposableError::with_backtrace`](crate::types::ComposableError::with_backtrace);
+/// it is only rendered in verbose output.
 ///
 /// # Examples
 ///
@@ -304,21 +354,24 @@ macro_rules! __group_field {
 /// use error_rail::{ComposableError, backtrace};
 ///
 /// let err = ComposableError::<&str>::new("panic occurred")
-///     .with_context(backtrace!());
+///     .with_backtrace(backtrace!());
+///
+/// assert_eq!(err.to_string(), "panic occurred");
 /// ```
 #[macro_export]
 #[cfg(feature = "std")]
 macro_rules! backtrace {
     () => {{
-        $crate::types::LazyContext::new(|| std::backtrace::Backtrace::capture().to_string())
+        $crate::types::ErrorBacktrace::capture()
     }};
 }
 
-/// Creates a backtrace context that always captures regardless of environment.
+/// Captures a structured backtrace regardless of environment.
 ///
-/// This macro uses `force_capture()` to always generate a backtrace, ignoring
-/// `RUST_BACKTRACE`/`RUST_LIB_BACKTRACE` settings. Use this for debugging
-/// scenarios where you need guaranteed backtrace information.
+/// This macro uses [`ErrorBacktrace::force_capture`](crate::types::ErrorBacktrace::force_capture)
+/// to always generate a backtrace, ignoring whether `RUST_BACKTRACE`/`RUST_LIB_BACKTRACE`
+/// enable it. Use this for debugging scenarios where you need guaranteed backtrace information.
+/// Like [`backtrace!`], capture is eager.
 ///
 /// # Performance Note
 ///
@@ -331,13 +384,15 @@ macro_rules! backtrace {
 /// use error_rail::{ComposableError, backtrace_force};
 ///
 /// let err = ComposableError::<&str>::new("panic occurred")
-///     .with_context(backtrace_force!());
+///     .with_backtrace(backtrace_force!());
+///
+/// println!("{}", err.fmt().verbose());
 /// ```
 #[macro_export]
 #[cfg(feature = "std")]
 macro_rules! backtrace_force {
     () => {{
-        $crate::types::LazyContext::new(|| std::backtrace::Backtrace::force_capture().to_string())
+        $crate::types::ErrorBacktrace::force_capture()
     }};
 }
 
@@ -524,7 +579,8 @@ macro_rules! assert_err_eq {
                 let mut found = false;
 
                 // Check core error message
-                if e.core_error().to_string() == expected {
+                let core = e.core_error().to_string();
+                if core == expected {
                     found = true;
                 }
 
@@ -547,7 +603,14 @@ macro_rules! assert_err_eq {
                                     found = true;
                                     break;
                                 }
-                                if g.metadata.iter().any(|(_, v)| v.as_ref() == expected) {
+                                if g.metadata.iter().any(|(_, v)| *v == expected) {
+                                    found = true;
+                                    break;
+                                }
+                            }
+                            ErrorContext::Custom(payload) => {
+                                let rendered = payload.to_string();
+                                if rendered == expected {
                                     found = true;
                                     break;
                                 }
@@ -32,7 +32,7 @@ use pin_project_lite::pin_project;
 use tower::{Layer, Service};
 
 use crate::traits::IntoErrorContext;
-use crate::types::ComposableError;
+use crate::types::{ComposableError, ContextLimit};
 
 /// A Tower [`Layer`] that wraps service errors in [`ComposableError`] with context.
 ///
@@ -61,6 +61,7 @@ use crate::types::ComposableError;
 #[derive(Clone, Debug)]
 pub struct ErrorRailLayer<C> {
     context: C,
+    context_limit: Option<ContextLimit>,
 }
 
 impl<C> ErrorRailLayer<C> {
@@ -69,7 +70,17 @@ impl<C> ErrorRailLayer<C> {
     /// The context will be attached to all errors from the wrapped service.
     #[inline]
     pub const fn new(context: C) -> Self {
-        Self { context }
+        Self { context, context_limit: None }
+    }
+
+    /// Caps the number of contexts kept on errors from the wrapped service.
+    ///
+    /// The limit stays on each [`ComposableError`], so contexts added by outer
+    /// layers are bounded as well.
+    #[inline]
+    pub const fn with_context_limit(mut self, limit: ContextLimit) -> Self {
+        self.context_limit = Some(limit);
+        self
     }
 
     /// Returns a reference to the context.
@@ -77,6 +88,12 @@ impl<C> ErrorRailLayer<C> {
     pub const fn context(&self) -> &C {
         &self.context
     }
+
+    /// Returns the context limit, if one was set.
+    #[inline]
+    pub const fn context_limit(&self) -> Option<ContextLimit> {
+        self.context_limit
+    }
 }
 
 impl<S, C: Clone> Layer<S> for ErrorRailLayer<C> {
@@ -84,7 +101,7 @@ impl<S, C: Clone> Layer<S> for ErrorRailLayer<C> {
 
     #[inline]
     fn layer(&self, inner: S) -> Self::Service {
-        ErrorRailService { inner, context: self.context.clone() }
+        ErrorRailService { inner, context: self.context.clone(), context_limit: self.context_limit }
     }
 }
 
@@ -96,13 +113,23 @@ impl<S, C: Clone> Layer<S> for ErrorRailLayer<C> {
 pub struct ErrorRailService<S, C> {
     inner: S,
     context: C,
+    context_limit: Option<ContextLimit>,
 }
 
 impl<S, C> ErrorRailService<S, C> {
     /// Creates a new `ErrorRailService` wrapping the given service.
     #[inline]
     pub const fn new(inner: S, context: C) -> Self {
-        Self { inner, context }
+        Self { inner, context, context_limit: None }
+    }
+
+    /// Caps the number of contexts kept on errors from the inner service.
+    ///
+    /// See [`ErrorRailLayer::with_context_limit`].
+    #[inline]
+    pub const fn with_context_limit(mut self, limit: ContextLimit) -> Self {
+        self.context_limit = Some(limit);
+        self
     }
 
     /// Returns a reference to the inner service.
@@ -128,6 +155,12 @@ impl<S, C> ErrorRailService<S, C> {
     pub const fn context(&self) -> &C {
         &self.context
     }
+
+    /// Returns the context limit, if one was set.
+    #[inline]
+    pub const fn context_limit(&self) -> Option<ContextLimit> {
+        self.context_limit
+    }
 }
 
 impl<S, C, Request> Service<Request> for ErrorRailService<S, C>
@@ -144,12 +177,12 @@ where
     fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
         self.inner
             .poll_ready(cx)
-            .map_err(|e| ComposableError::new(e).with_context(self.context.clone()))
+            .map_err(|e| wrap_error(e, self.context.clone(), self.context_limit))
     }
 
     #[inline]
     fn call(&mut self, request: Request) -> Self::Future {
-        ErrorRailFuture::new(self.inner.call(request), self.context.clone())
+        ErrorRailFuture::new(self.inner.call(request), self.context.clone(), self.context_limit)
     }
 }
 
@@ -162,14 +195,15 @@ pin_project! {
         #[pin]
         inner: F,
         context: Option<C>,
+        context_limit: Option<ContextLimit>,
     }
 }
 
 impl<F, C> ErrorRailFuture<F, C> {
     /// Creates a new `ErrorRailFuture` with the given inner future and context.
     #[inline]
-    fn new(inner: F, context: C) -> Self {
-        Self { inner, context: Some(context) }
+    fn new(inner: F, context: C, context_limit: Option<ContextLimit>) -> Self {
+        Self { inner, context: Some(context), context_limit }
     }
 }
 
@@ -189,7 +223,7 @@ where
             Poll::Ready(Err(error)) => {
                 // SAFETY: context is always Some until first Ready result
                 let context = this.context.take().expect("polled after completion");
-                Poll::Ready(Err(ComposableError::new(error).with_context(context)))
+                Poll::Ready(Err(wrap_error(error, context, *this.context_limit)))
             },
             Poll::Pending => Poll::Pending,
         }
@@ -207,6 +241,20 @@ where
     }
 }
 
+/// Wraps a service error, applying the context limit before adding `context`.
+#[inline]
+fn wrap_error<E, C: IntoErrorContext>(
+    error: E,
+    context: C,
+    limit: Option<ContextLimit>,
+) -> ComposableError<E> {
+    let error = match limit {
+        Some(limit) => ComposableError::from_core(error).with_context_limit(limit),
+        None => ComposableError::from_core(error),
+    };
+    error.with_context(context)
+}
+
 /// Extension trait for easily wrapping services with error context.
 pub trait ServiceErrorExt<Request>: Service<Request> + Sized {
     /// Wraps this service to add error context to all errors.
@@ -4,8 +4,43 @@
 
 use crate::types::alloc_type::{Box, Cow, String};
 use crate::types::{ComposableError, ErrorContext, ErrorVec, LazyContext};
+use core::panic::Location;
 use core::time::Duration;
 
+/// Derives [`IntoErrorContext`] for structs (requires `derive` feature).
+///
+/// Fields become metadata keyed by their name unless marked with
+/// `#[context(tag)]`, `#[context(metadata = "key")]`, `#[context(message)]`
+/// or `#[context(skip)]`.
+///
+/// # Examples
+///
+/// ```
+/// use error_rail::{ComposableError, IntoErrorContext};
+///
+/// #[derive(IntoErrorContext)]
+/// struct LoadUser {
+///     #[context(message)]
+///     action: &'static str,
+///     #[context(metadata = "user_id")]
+///     id: u64,
+///     #[context(tag)]
+///     tenant: String,
+///     #[context(skip)]
+///     password: String,
+/// }
+///
+/// let err = ComposableError::new("not found").with_context(LoadUser {
+///     action: "loading user",
+///     id: 42,
+///     tenant: "acme".into(),
+///     password: "hunter2".into(),
+/// });
+/// assert_eq!(err.to_string(), "[acme] loading user (user_id=42) -> not found");
+/// ```
+#[cfg(feature = "derive")]
+pub use error_rail_derive::IntoErrorContext;
+
 /// Converts a type into an [`ErrorContext`] for error annotation.
 #[diagnostic::on_unimplemented(
     message = "`{Self}` cannot be used as error context",
@@ -46,6 +81,38 @@ impl IntoErrorContext for ErrorContext {
     }
 }
 
+/// Derives [`TransientError`] from variant attributes (requires `derive` feature).
+///
+/// Variants are permanent unless marked `#[transient]`, given a retry hint with
+/// `#[retry_after(ms = ...)]`/`#[retry_after(secs = ...)]` or `#[max_retries(n)]`,
+/// or marked `#[transient(delegate)]` to forward to a wrapped inner error.
+///
+/// # Examples
+///
+/// ```
+/// use error_rail::TransientError;
+/// use std::time::Duration;
+///
+/// #[derive(Debug, TransientError)]
+/// enum FetchError {
+///     #[transient]
+///     Timeout,
+///     #[retry_after(ms = 500)]
+///     #[max_retries(3)]
+///     RateLimited,
+///     NotFound,
+///     #[transient(delegate)]
+///     Io(std::io::Error),
+/// }
+///
+/// assert!(FetchError::Timeout.is_transient());
+/// assert_eq!(FetchError::RateLimited.retry_after_hint(), Some(Duration::from_millis(500)));
+/// assert!(FetchError::NotFound.is_permanent());
+/// assert!(FetchError::Io(std::io::ErrorKind::TimedOut.into()).is_transient());
+/// ```
+#[cfg(feature = "derive")]
+pub use error_rail_derive::TransientError;
+
 /// Classification of errors as transient or permanent.
 pub trait TransientError {
     /// Returns `true` if this error is transient and may succeed on retry.
@@ -260,16 +327,28 @@ pub trait ResultExt<T, E> {
 
 impl<T, E> ResultExt<T, E> for Result<T, E> {
     #[inline]
+    #[cfg_attr(feature = "caller-location", track_caller)]
     fn ctx<C: IntoErrorContext>(self, msg: C) -> Result<T, Box<ComposableError<E>>> {
-        self.map_err(|e| Box::new(ComposableError::new(e).with_context(msg)))
+        let caller = Location::caller();
+        self.map_err(|e| {
+            let mut error = ComposableError::new_at(e, caller);
+            error.push_context_from(msg.into_error_context(), caller);
+            Box::new(error)
+        })
     }
 
     #[inline]
+    #[cfg_attr(feature = "caller-location", track_caller)]
     fn ctx_with<F>(self, f: F) -> Result<T, Box<ComposableError<E>>>
     where
         F: FnOnce() -> String,
     {
-        self.map_err(|e| Box::new(ComposableError::new(e).with_context(LazyContext::new(f))))
+        let caller = Location::caller();
+        self.map_err(|e| {
+            let mut error = ComposableError::new_at(e, caller);
+            error.push_context_from(LazyContext::new(f).into_error_context(), caller);
+            Box::new(error)
+        })
     }
 }
 
@@ -336,20 +415,26 @@ pub trait BoxedResultExt<T, E> {
 
 impl<T, E> BoxedResultExt<T, E> for Result<T, Box<ComposableError<E>>> {
     #[inline]
+    #[cfg_attr(feature = "caller-location", track_caller)]
     fn ctx_boxed<C: IntoErrorContext>(self, msg: C) -> Self {
+        let caller = Location::caller();
         self.map_err(|mut e| {
-            e.with_context_inplace(msg);
+            e.capture_location(caller);
+            e.push_context_from(msg.into_error_context(), caller);
             e
         })
     }
 
     #[inline]
+    #[cfg_attr(feature = "caller-location", track_caller)]
     fn ctx_boxed_with<F>(self, f: F) -> Self
     where
         F: FnOnce() -> String,
     {
+        let caller = Location::caller();
         self.map_err(|mut e| {
-            e.with_context_inplace(LazyContext::new(f));
+            e.capture_location(caller);
+            e.push_context_from(LazyContext::new(f).into_error_context(), caller);
             e
         })
     }
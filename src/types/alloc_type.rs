@@ -17,3 +17,8 @@ pub type Vec<T> = alloc::vec::Vec<T>;
 pub type String = std::string::String;
 #[cfg(not(feature = "std"))]
 pub type String = alloc::string::String;
+
+#[cfg(feature = "std")]
+pub type Arc<T> = std::sync::Arc<T>;
+#[cfg(not(feature = "std"))]
+pub type Arc<T> = alloc::sync::Arc<T>;
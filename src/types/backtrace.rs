@@ -0,0 +1,350 @@
+//! Structured backtraces attached to errors.
+//!
+//! An [`ErrorBacktrace`] is a list of parsed [`BacktraceFrame`]s (function,
+//! file, line and column) instead of one large string. Frames from the
+//! standard library, async runtimes and error-rail itself are dropped at
+//! capture time according to a [`BacktraceFilter`], which can also cap the
+//! number of frames kept.
+//!
+//! Backtraces are attached with
+//! [`ComposableError::with_backtrace`](crate::types::ComposableError::with_backtrace)
+//! or the [`backtrace!`](crate::backtrace) macro, serialized as an array of
+//! frames, and only rendered by
+//! [`ErrorFormatBuilder::verbose`](crate::types::error_formatter::ErrorFormatBuilder::verbose)
+//! or [`show_backtrace`](crate::types::error_formatter::ErrorFormatBuilder::show_backtrace).
+//!
+//! # Examples
+//!
+//! ```
+//! use error_rail::types::{BacktraceFilter, ErrorBacktrace};
+//!
+//! let trace = ErrorBacktrace::parse(
+//!     "   0: app::db::connect\n             at ./src/db.rs:12:9\n   \
+//!         1: core::ops::function::FnOnce::call_once\n   \
+//!         2: app::main\n             at ./src/main.rs:4:5",
+//!     &BacktraceFilter::new(),
+//! );
+//!
+//! let functions: Vec<_> = trace.frames().iter().map(|f| f.function.as_str()).collect();
+//! assert_eq!(functions, ["app::db::connect", "app::main"]);
+//! assert_eq!(trace.frames()[0].line, Some(12));
+//! ```
+
+use crate::types::alloc_type::{String, Vec};
+#[cfg(not(feature = "std"))]
+use alloc::borrow::ToOwned;
+use core::fmt::{Display, Formatter};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Crates whose frames belong to the standard library.
+const STD_CRATES: &[&str] =
+    &["std", "core", "alloc", "__rustc", "panic_unwind", "compiler_builtins"];
+
+/// Crates whose frames belong to async runtimes and test harnesses.
+const RUNTIME_CRATES: &[&str] = &[
+    "tokio",
+    "futures",
+    "futures_core",
+    "futures_util",
+    "futures_executor",
+    "async_std",
+    "async_executor",
+    "async_task",
+    "smol",
+    "test",
+];
+
+/// Crates whose frames belong to error-rail itself.
+const ERROR_RAIL_CRATES: &[&str] = &["error_rail", "error_rail_derive"];
+
+/// A single parsed stack frame.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct BacktraceFrame {
+    /// Demangled function path, e.g. `app::db::connect`
+    pub function: String,
+    /// Source file, when debug info is available
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
+    pub file: Option<String>,
+    /// Line number in `file`
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
+    pub line: Option<u32>,
+    /// Column number in `file`
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
+    pub column: Option<u32>,
+}
+
+impl BacktraceFrame {
+    /// Creates a frame for `function` without source information.
+    #[inline]
+    pub fn new(function: impl Into<String>) -> Self {
+        Self { function: function.into(), file: None, line: None, column: None }
+    }
+
+    /// Returns the name of the crate the function belongs to, if it has a path.
+    ///
+    /// Trait-impl frames such as `<app::Db as core::fmt::Debug>::fmt` report
+    /// the implementing type's crate (`app`).
+    pub fn crate_name(&self) -> Option<&str> {
+        let mut name = self.function.as_str();
+        loop {
+            let trimmed = name
+                .trim_start_matches(['<', '&', '*', '('])
+                .trim_start_matches("dyn ")
+                .trim_start_matches("mut ")
+                .trim_start_matches("const ");
+            if trimmed == name {
+                break;
+            }
+            name = trimmed;
+        }
+        let end = name.find("::")?;
+        let krate = &name[..end];
+        // Full-format symbols carry a disambiguator, e.g. `std[e28293b1aa0f68bd]`.
+        Some(krate.split('[').next().unwrap_or(krate))
+    }
+
+    /// Returns `true` for standard library and system frames (`main`, `_start`, ...).
+    pub fn is_std(&self) -> bool {
+        match self.crate_name() {
+            Some(krate) => STD_CRATES.contains(&krate),
+            None => true,
+        }
+    }
+
+    /// Returns `true` for frames of async runtimes and test harnesses.
+    pub fn is_runtime(&self) -> bool {
+        self.crate_name()
+            .is_some_and(|krate| RUNTIME_CRATES.contains(&krate))
+    }
+
+    /// Returns `true` for frames inside error-rail itself.
+    pub fn is_error_rail(&self) -> bool {
+        self.crate_name()
+            .is_some_and(|krate| ERROR_RAIL_CRATES.contains(&krate))
+    }
+}
+
+impl Display for BacktraceFrame {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.write_str(&self.function)?;
+        if let Some(file) = &self.file {
+            write!(f, " at {}", file)?;
+            if let Some(line) = self.line {
+                write!(f, ":{}", line)?;
+            }
+            if let Some(column) = self.column {
+                write!(f, ":{}", column)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Selects which frames an [`ErrorBacktrace`] keeps.
+///
+/// The default hides standard library, runtime and error-rail frames and keeps
+/// any number of the remaining ones.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct BacktraceFilter {
+    hide_std: bool,
+    hide_runtime: bool,
+    hide_error_rail: bool,
+    max_frames: Option<usize>,
+}
+
+impl BacktraceFilter {
+    /// Hides standard library, runtime and error-rail frames.
+    #[inline]
+    pub const fn new() -> Self {
+        Self { hide_std: true, hide_runtime: true, hide_error_rail: true, max_frames: None }
+    }
+
+    /// Keeps every frame.
+    #[inline]
+    pub const fn all() -> Self {
+        Self { hide_std: false, hide_runtime: false, hide_error_rail: false, max_frames: None }
+    }
+
+    /// Returns the filter selected by `RUST_LIB_BACKTRACE`, or `RUST_BACKTRACE`
+    /// if it is unset: `full` keeps every frame, anything else uses [`BacktraceFilter::new`].
+    #[cfg(feature = "std")]
+    pub fn from_env() -> Self {
+        let var = std::env::var("RUST_LIB_BACKTRACE").or_else(|_| std::env::var("RUST_BACKTRACE"));
+        match var.as_deref() {
+            Ok("full") => Self::all(),
+            _ => Self::new(),
+        }
+    }
+
+    /// Sets whether standard library and system frames are hidden.
+    #[inline]
+    pub const fn hide_std(mut self, hide: bool) -> Self {
+        self.hide_std = hide;
+        self
+    }
+
+    /// Sets whether frames of async runtimes and test harnesses are hidden.
+    #[inline]
+    pub const fn hide_runtime(mut self, hide: bool) -> Self {
+        self.hide_runtime = hide;
+        self
+    }
+
+    /// Sets whether frames inside error-rail are hidden.
+    #[inline]
+    pub const fn hide_error_rail(mut self, hide: bool) -> Self {
+        self.hide_error_rail = hide;
+        self
+    }
+
+    /// Keeps at most `max` frames, the innermost ones.
+    #[inline]
+    pub const fn max_frames(mut self, max: usize) -> Self {
+        self.max_frames = Some(max);
+        self
+    }
+
+    /// Returns `true` if `frame` passes the filter, ignoring the frame limit.
+    pub fn keeps(&self, frame: &BacktraceFrame) -> bool {
+        !(self.hide_std && frame.is_std()
+            || self.hide_runtime && frame.is_runtime()
+            || self.hide_error_rail && frame.is_error_rail())
+    }
+}
+
+impl Default for BacktraceFilter {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A backtrace stored as parsed, filtered frames, innermost first.
+///
+/// Equality and hashing compare the frames.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct ErrorBacktrace {
+    frames: Vec<BacktraceFrame>,
+}
+
+impl ErrorBacktrace {
+    /// Captures the current backtrace if `RUST_LIB_BACKTRACE` or `RUST_BACKTRACE`
+    /// enables it, filtered by [`BacktraceFilter::from_env`].
+    ///
+    /// Returns an empty backtrace when capturing is disabled.
+    #[cfg(feature = "std")]
+    #[inline]
+    pub fn capture() -> Self {
+        Self::from_std(&std::backtrace::Backtrace::capture(), &BacktraceFilter::from_env())
+    }
+
+    /// Captures the current backtrace regardless of environment variables.
+    #[cfg(feature = "std")]
+    #[inline]
+    pub fn force_capture() -> Self {
+        Self::capture_with(&BacktraceFilter::from_env())
+    }
+
+    /// Captures the current backtrace regardless of environment variables, keeping
+    /// the frames selected by `filter`.
+    #[cfg(feature = "std")]
+    #[inline]
+    pub fn capture_with(filter: &BacktraceFilter) -> Self {
+        Self::from_std(&std::backtrace::Backtrace::force_capture(), filter)
+    }
+
+    /// Converts a standard library backtrace, keeping the frames selected by `filter`.
+    #[cfg(feature = "std")]
+    pub fn from_std(backtrace: &std::backtrace::Backtrace, filter: &BacktraceFilter) -> Self {
+        use std::backtrace::BacktraceStatus;
+
+        match backtrace.status() {
+            BacktraceStatus::Captured => Self::parse(&backtrace.to_string(), filter),
+            _ => Self::default(),
+        }
+    }
+
+    /// Parses the text form of a standard library backtrace, keeping the frames
+    /// selected by `filter`.
+    ///
+    /// Each `N: function` line starts a frame, and a following
+    /// `at file:line:column` line fills in its source location. Inlined
+    /// functions printed without an index become frames of their own.
+    pub fn parse(text: &str, filter: &BacktraceFilter) -> Self {
+        let mut frames: Vec<BacktraceFrame> = Vec::new();
+        for line in text.lines().map(str::trim).filter(|line| !line.is_empty()) {
+            if let Some(location) = line.strip_prefix("at ") {
+                if let Some(frame) = frames.last_mut() {
+                    set_location(frame, location);
+                }
+                continue;
+            }
+            let function = match line.split_once(": ") {
+                Some((index, rest)) if index.bytes().all(|b| b.is_ascii_digit()) => rest,
+                _ => line,
+            };
+            frames.push(BacktraceFrame::new(function.to_owned()));
+        }
+        Self::from_frames(frames, filter)
+    }
+
+    /// Builds a backtrace from frames, innermost first, keeping those selected by `filter`.
+    pub fn from_frames(
+        frames: impl IntoIterator<Item = BacktraceFrame>,
+        filter: &BacktraceFilter,
+    ) -> Self {
+        let frames = frames
+            .into_iter()
+            .filter(|frame| filter.keeps(frame))
+            .take(filter.max_frames.unwrap_or(usize::MAX))
+            .collect();
+        Self { frames }
+    }
+
+    /// Returns the kept frames, innermost first.
+    #[inline]
+    pub fn frames(&self) -> &[BacktraceFrame] {
+        &self.frames
+    }
+
+    /// Returns `true` if no frame was captured or kept.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+}
+
+impl Display for ErrorBacktrace {
+    /// Writes one `N: frame` line per frame.
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        for (i, frame) in self.frames.iter().enumerate() {
+            if i > 0 {
+                f.write_str("\n")?;
+            }
+            write!(f, "{:>4}: {}", i, frame)?;
+        }
+        Ok(())
+    }
+}
+
+/// Fills `frame` from a `file:line:column` location, where the file may itself
+/// contain colons.
+fn set_location(frame: &mut BacktraceFrame, location: &str) {
+    let mut parts = location.rsplitn(3, ':');
+    let (last, middle, rest) = (parts.next(), parts.next(), parts.next());
+    let number = |part: Option<&str>| part.and_then(|p| p.parse::<u32>().ok());
+    let (file, line, column) = match (number(middle), number(last), rest) {
+        (Some(line), Some(column), Some(file)) => (file, Some(line), Some(column)),
+        (None, Some(line), _) => {
+            (location.rsplit_once(':').map_or(location, |(f, _)| f), Some(line), None)
+        },
+        _ => (location, None, None),
+    };
+    frame.file = Some(file.to_owned());
+    frame.line = line;
+    frame.column = column;
+}
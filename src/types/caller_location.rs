@@ -0,0 +1,149 @@
+//! Automatic capture of the source location where an error entered the rail.
+//!
+//! With the `caller-location` feature, [`ComposableError::new`](crate::types::ComposableError::new),
+//! `From<E>` for `ComposableError<E>`, [`ResultExt`](crate::traits::ResultExt),
+//! [`BoxedResultExt`](crate::traits::BoxedResultExt), [`ErrorPipeline`](crate::types::ErrorPipeline)
+//! and the other entry points are `#[track_caller]` and record the file, line
+//! and column of their caller. The first site that sees an error becomes its
+//! [`location`](crate::types::ComposableError::location), and every context
+//! added by `ctx`, `ctx_boxed` or `ErrorPipeline::with_context` keeps the site
+//! it was added at; see [`context_sites`](crate::types::ComposableError::context_sites).
+//! `#[track_caller]` does not expose the module path, so
+//! [`Location::module_path`] is left unset. Without the feature nothing is
+//! captured and the entry points carry no extra cost.
+//!
+//! Capture can also be switched off at runtime with [`set_enabled`]. Sites are
+//! not rendered by default; use
+//! [`ErrorFormatBuilder::show_locations`](crate::types::error_formatter::ErrorFormatBuilder::show_locations)
+//! to print the site of each context and layer.
+//!
+//! # Examples
+//!
+//! ```
+//! use error_rail::ResultExt;
+//!
+//! let err = Err::<(), _>("timeout").ctx("fetching user").unwrap_err();
+//!
+//! # #[cfg(feature = "caller-location")]
+//! # {
+//! let site = err.location().unwrap();
+//! assert_eq!((site.file.as_ref(), site.line), (file!(), line!() - 5));
+//! assert_eq!(err.context_sites().next(), Some(Some(site)));
+//! assert!(err.fmt().show_locations(true).to_string().contains(file!()));
+//! # }
+//! ```
+
+use crate::types::{ContextLimit, ErrorVec, Location};
+use core::sync::atomic::{AtomicBool, Ordering};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+static ENABLED: AtomicBool = AtomicBool::new(true);
+
+/// Turns automatic location capture on or off for the whole process.
+///
+/// Capture is on by default when the `caller-location` feature is enabled.
+/// Errors created while it is off carry no location.
+#[inline]
+pub fn set_enabled(enabled: bool) {
+    ENABLED.store(enabled, Ordering::Relaxed);
+}
+
+/// Returns `true` if locations are captured, which requires the
+/// `caller-location` feature and capture not being switched off.
+#[inline]
+pub fn is_enabled() -> bool {
+    cfg!(feature = "caller-location") && ENABLED.load(Ordering::Relaxed)
+}
+
+/// Converts `caller` into a [`Location`] if capture is enabled.
+#[inline]
+pub(crate) fn capture(caller: &'static core::panic::Location<'static>) -> Option<Location> {
+    is_enabled().then(|| caller.into())
+}
+
+/// Call sites at which the contexts of one error layer were attached, in push order.
+///
+/// Stays empty until a site is recorded and from then on holds one entry per
+/// context, so it is truncated and folded together with the context stack.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(transparent))]
+#[derive(Debug, Clone, Default)]
+pub(crate) struct ContextSites(ErrorVec<Option<Location>>);
+
+impl ContextSites {
+    /// Returns `true` when no site was recorded.
+    #[inline]
+    pub(crate) fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Returns the sites of a stack of `len` contexts, or an empty slice when
+    /// none were recorded or they do not line up with the stack.
+    #[inline]
+    pub(crate) fn of(&self, len: usize) -> &[Option<Location>] {
+        if self.0.len() == len {
+            &self.0
+        } else {
+            &[]
+        }
+    }
+
+    /// Records `site` for a context pushed onto a stack of `before` contexts.
+    ///
+    /// When the stack did not `grow` the context was folded into the last one,
+    /// which then takes the new site.
+    pub(crate) fn record(&mut self, before: usize, grew: bool, site: Option<Location>) {
+        if self.0.is_empty() {
+            if site.is_none() {
+                return;
+            }
+            self.0.resize(before, None);
+        }
+        match self.0.last_mut() {
+            Some(last) if !grew => {
+                if site.is_some() {
+                    *last = site;
+                }
+            },
+            _ => self.0.push(site),
+        }
+    }
+
+    /// Keeps the sites in line with `count` contexts added without one.
+    #[inline]
+    pub(crate) fn pad(&mut self, count: usize) {
+        if !self.0.is_empty() {
+            self.0.extend(core::iter::repeat(None).take(count));
+        }
+    }
+
+    /// Appends the sites of `count` contexts pushed after the `len` contexts of
+    /// this stack.
+    pub(crate) fn append(&mut self, len: usize, newer: ContextSites, count: usize) {
+        match (self.0.is_empty(), newer.0.len() == count && count > 0) {
+            (true, false) => {},
+            (_, true) => {
+                self.0.resize(len, None);
+                self.0.extend(newer.0);
+            },
+            (false, false) => self.pad(count),
+        }
+    }
+
+    /// Removes the sites from index `from` on, in push order, and returns them.
+    #[inline]
+    pub(crate) fn split_off(&mut self, from: usize) -> ErrorVec<Option<Location>> {
+        match self.0.len() > from {
+            true => self.0.drain(from..).collect(),
+            false => ErrorVec::new(),
+        }
+    }
+
+    /// Drops the sites of the contexts removed by `limit`.
+    #[inline]
+    pub(crate) fn truncate(&mut self, limit: ContextLimit) {
+        if !self.0.is_empty() {
+            limit.truncate(&mut self.0);
+        }
+    }
+}
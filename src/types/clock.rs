@@ -0,0 +1,223 @@
+//! Timestamps and the clocks that produce them.
+//!
+//! Timestamps are opt-in: an error or context only carries one when it is
+//! captured explicitly, e.g. with [`ComposableError::stamp_with`](crate::types::ComposableError::stamp_with)
+//! or [`ComposableError::with_context_at`](crate::types::ComposableError::with_context_at).
+//! Time is read through the [`Clock`] trait so `no_std` targets can plug in
+//! their own time source, and tests can use a [`ManualClock`].
+//!
+//! Timestamps are serialized, and rendered when
+//! [`show_timestamps`](crate::types::error_formatter::ErrorFormatBuilder::show_timestamps)
+//! is enabled, but they are never part of the default fingerprint.
+//!
+//! # Examples
+//!
+//! ```
+//! use error_rail::ComposableError;
+//! use error_rail::types::{ManualClock, Timestamp};
+//! use std::time::Duration;
+//!
+//! let clock = ManualClock::new(Timestamp::from_unix_millis(1_700_000_000_000));
+//! let err = ComposableError::new("upstream timeout").stamp_with(&clock);
+//!
+//! clock.advance(Duration::from_millis(250));
+//! let err = err.with_context_at("retry 1", &clock);
+//!
+//! assert_eq!(err.timestamp(), Some(Timestamp::from_unix_millis(1_700_000_000_000)));
+//! assert_eq!(
+//!     err.fmt().show_timestamps(true).to_string(),
+//!     "2023-11-14T22:13:20.250Z retry 1 -> 2023-11-14T22:13:20.000Z upstream timeout"
+//! );
+//! ```
+
+use core::fmt::{Display, Formatter};
+use core::time::Duration;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// A point in time, stored as the duration since the Unix epoch (UTC).
+///
+/// `Display` renders RFC 3339 with millisecond precision, e.g.
+/// `2023-11-14T22:13:20.250Z`.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Timestamp {
+    secs: u64,
+    nanos: u32,
+}
+
+impl Timestamp {
+    /// The Unix epoch, `1970-01-01T00:00:00Z`.
+    pub const UNIX_EPOCH: Self = Self { secs: 0, nanos: 0 };
+
+    /// Creates a timestamp from a duration since the Unix epoch.
+    #[inline]
+    pub const fn from_unix_duration(since_epoch: Duration) -> Self {
+        Self { secs: since_epoch.as_secs(), nanos: since_epoch.subsec_nanos() }
+    }
+
+    /// Creates a timestamp from milliseconds since the Unix epoch.
+    #[inline]
+    pub const fn from_unix_millis(millis: u64) -> Self {
+        Self::from_unix_duration(Duration::from_millis(millis))
+    }
+
+    /// Returns the duration since the Unix epoch.
+    #[inline]
+    pub const fn as_unix_duration(&self) -> Duration {
+        Duration::new(self.secs, self.nanos)
+    }
+
+    /// Returns the whole milliseconds since the Unix epoch.
+    #[inline]
+    pub const fn unix_millis(&self) -> u64 {
+        self.secs * 1000 + (self.nanos / 1_000_000) as u64
+    }
+
+    /// Returns the time elapsed from `earlier` to `self`, or `None` if `earlier` is later.
+    #[inline]
+    pub fn duration_since(&self, earlier: Timestamp) -> Option<Duration> {
+        self.as_unix_duration()
+            .checked_sub(earlier.as_unix_duration())
+    }
+}
+
+impl Display for Timestamp {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        let days = self.secs / 86_400;
+        let rem = self.secs % 86_400;
+        let (year, month, day) = civil_from_days(days);
+        write!(
+            f,
+            "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}.{:03}Z",
+            year,
+            month,
+            day,
+            rem / 3600,
+            rem % 3600 / 60,
+            rem % 60,
+            self.nanos / 1_000_000
+        )
+    }
+}
+
+/// Converts days since the Unix epoch into a proleptic Gregorian `(year, month, day)`.
+fn civil_from_days(days: u64) -> (u64, u64, u64) {
+    // Shift the epoch to 0000-03-01 so leap days fall at the end of each year.
+    let z = days + 719_468;
+    let era = z / 146_097;
+    let doe = z % 146_097;
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + u64::from(month <= 2);
+    (year, month, day)
+}
+
+#[cfg(feature = "std")]
+impl From<std::time::SystemTime> for Timestamp {
+    /// Converts a system time, clamping times before the Unix epoch to the epoch.
+    #[inline]
+    fn from(time: std::time::SystemTime) -> Self {
+        time.duration_since(std::time::UNIX_EPOCH)
+            .map_or(Self::UNIX_EPOCH, Self::from_unix_duration)
+    }
+}
+
+/// A source of the current time.
+///
+/// Implemented for [`SystemClock`] under `std`, for [`ManualClock`], and for
+/// any `Fn() -> Timestamp`, so `no_std` targets can wrap their own RTC or tick counter.
+///
+/// # Examples
+///
+/// ```
+/// use error_rail::ComposableError;
+/// use error_rail::types::Timestamp;
+///
+/// let rtc = || Timestamp::from_unix_millis(42_000);
+/// let err = ComposableError::new("sensor offline").stamp_with(&rtc);
+///
+/// assert_eq!(err.timestamp().unwrap().unix_millis(), 42_000);
+/// ```
+pub trait Clock {
+    /// Returns the current time.
+    fn now(&self) -> Timestamp;
+}
+
+impl<F> Clock for F
+where
+    F: Fn() -> Timestamp,
+{
+    #[inline]
+    fn now(&self) -> Timestamp {
+        self()
+    }
+}
+
+/// Reads the time from [`std::time::SystemTime`].
+#[cfg(feature = "std")]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+#[cfg(feature = "std")]
+impl Clock for SystemClock {
+    #[inline]
+    fn now(&self) -> Timestamp {
+        std::time::SystemTime::now().into()
+    }
+}
+
+/// A clock that only moves when told to, for tests and simulations.
+///
+/// It can be shared by reference across threads.
+#[cfg(target_has_atomic = "64")]
+#[derive(Debug, Default)]
+pub struct ManualClock {
+    nanos: core::sync::atomic::AtomicU64,
+}
+
+#[cfg(target_has_atomic = "64")]
+impl ManualClock {
+    /// Creates a clock stopped at `start`.
+    ///
+    /// Times are kept as nanoseconds in a `u64`, which covers dates up to the year 2554.
+    #[inline]
+    pub fn new(start: Timestamp) -> Self {
+        Self { nanos: core::sync::atomic::AtomicU64::new(Self::to_nanos(start)) }
+    }
+
+    /// Moves the clock to `time`.
+    #[inline]
+    pub fn set(&self, time: Timestamp) {
+        self.nanos
+            .store(Self::to_nanos(time), core::sync::atomic::Ordering::SeqCst);
+    }
+
+    /// Moves the clock forward by `by`.
+    #[inline]
+    pub fn advance(&self, by: Duration) {
+        let by = u64::try_from(by.as_nanos()).unwrap_or(u64::MAX);
+        let _ = self.nanos.fetch_update(
+            core::sync::atomic::Ordering::SeqCst,
+            core::sync::atomic::Ordering::SeqCst,
+            |nanos| Some(nanos.saturating_add(by)),
+        );
+    }
+
+    #[inline]
+    fn to_nanos(time: Timestamp) -> u64 {
+        u64::try_from(time.as_unix_duration().as_nanos()).unwrap_or(u64::MAX)
+    }
+}
+
+#[cfg(target_has_atomic = "64")]
+impl Clock for ManualClock {
+    #[inline]
+    fn now(&self) -> Timestamp {
+        let nanos = self.nanos.load(core::sync::atomic::Ordering::SeqCst);
+        Timestamp::from_unix_duration(Duration::from_nanos(nanos))
+    }
+}
@@ -0,0 +1,338 @@
+//! Compaction of repeated contexts.
+//!
+//! Retry loops and helpers called in a loop attach the same context over and
+//! over, or contexts that differ only in an attempt counter. A
+//! [`ContextCompaction`] folds such runs of consecutive contexts into one entry:
+//!
+//! - Identical contexts become one context with a repeat count, rendered as
+//!   `"message (x3)"`. Timestamps are ignored; the most recent one is kept.
+//! - Group contexts that differ only in the value of a counter key become one
+//!   context whose counter is rendered as a range, e.g. `(retry_attempt=1..5)`.
+//!
+//! Compaction is applied on push with
+//! [`ComposableError::with_compaction`](crate::types::ComposableError::with_compaction)
+//! or [`ErrorPipeline::with_compaction`](crate::types::ErrorPipeline::with_compaction),
+//! which also keeps the error small, or only at render time with
+//! [`ErrorFormatBuilder::compaction`](crate::types::error_formatter::ErrorFormatBuilder::compaction),
+//! which leaves the stored contexts untouched. The repeat count and range of a
+//! compacted context are stored in [`GroupContext::repeat`](crate::types::GroupContext::repeat)
+//! and survive serialization. Contexts are never folded across the
+//! `"... N more contexts elided ..."` marker of a [`ContextLimit`](crate::types::ContextLimit).
+//!
+//! # Examples
+//!
+//! ```
+//! use error_rail::{ComposableError, ErrorContext};
+//! use error_rail::types::ContextCompaction;
+//!
+//! let err = ComposableError::new("timeout")
+//!     .with_compaction(ContextCompaction::new().counter_key("retry_attempt"))
+//!     .with_contexts((1..=5).map(|attempt| ErrorContext::metadata("retry_attempt", attempt)))
+//!     .with_context("calling inventory")
+//!     .with_context("calling inventory");
+//!
+//! assert_eq!(err.context().len(), 2);
+//! assert_eq!(
+//!     err.error_chain(),
+//!     "calling inventory (x2) -> (retry_attempt=1..5) -> timeout"
+//! );
+//! ```
+
+use crate::types::alloc_type::{Box, Cow};
+use crate::types::{
+    ErrorContext, ErrorVec, GroupContext, Location, MetadataValue, Severity, Visibility,
+};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use smallvec::SmallVec;
+
+/// Rules for folding runs of consecutive, repeated contexts into one entry.
+///
+/// Identical contexts are always folded. Group contexts that differ only in
+/// the value of one of the [`counter_key`](Self::counter_key)s are folded into
+/// a range of that counter.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct ContextCompaction {
+    counter_keys: SmallVec<[Cow<'static, str>; 1]>,
+}
+
+/// How often a compacted context occurred.
+///
+/// Stored in [`GroupContext::repeat`] once contexts were folded on push.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ContextRepeat {
+    /// Number of contexts folded into this one.
+    pub count: usize,
+    /// Range of the counter key, when the folded contexts differed in it.
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
+    pub range: Option<CounterRange>,
+}
+
+/// First value of a counter whose contexts were folded into one.
+///
+/// The last value is the one stored in the context's metadata.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CounterRange {
+    /// Metadata key of the counter.
+    pub key: Cow<'static, str>,
+    /// Value of the counter in the oldest folded context.
+    pub first: MetadataValue,
+}
+
+/// Borrowed repeat count and counter range of a run of contexts.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct RepeatView<'a> {
+    pub(crate) count: usize,
+    /// Counter key and its first value.
+    pub(crate) range: Option<(&'a str, &'a MetadataValue)>,
+}
+
+impl ContextRepeat {
+    #[inline]
+    pub(crate) fn view(&self) -> RepeatView<'_> {
+        RepeatView {
+            count: self.count,
+            range: self
+                .range
+                .as_ref()
+                .map(|range| (range.key.as_ref(), &range.first)),
+        }
+    }
+}
+
+impl RepeatView<'_> {
+    #[inline]
+    fn into_owned(self) -> ContextRepeat {
+        ContextRepeat {
+            count: self.count,
+            range: self.range.map(|(key, first)| CounterRange {
+                key: Cow::Owned(key.into()),
+                first: first.clone(),
+            }),
+        }
+    }
+}
+
+/// How two consecutive contexts can be folded.
+#[derive(Clone, Copy)]
+enum Fold<'k> {
+    /// The contexts are identical.
+    Identical,
+    /// The contexts differ only in the value of this counter key.
+    Counter(&'k str),
+}
+
+impl ContextCompaction {
+    /// Creates a compaction that folds identical contexts only.
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Also folds group contexts that differ only in the value of metadata `key`.
+    ///
+    /// May be called several times; each key is a separate counter.
+    #[inline]
+    pub fn counter_key<K: Into<Cow<'static, str>>>(mut self, key: K) -> Self {
+        self.counter_keys.push(key.into());
+        self
+    }
+
+    /// Returns the counter keys.
+    #[inline]
+    pub fn counter_keys(&self) -> impl Iterator<Item = &str> + '_ {
+        self.counter_keys.iter().map(|key| key.as_ref())
+    }
+
+    /// Pushes `ctx`, folding it into the most recent context when possible.
+    pub(crate) fn push(&self, contexts: &mut ErrorVec<ErrorContext>, ctx: ErrorContext) {
+        match contexts.last_mut() {
+            Some(last) => {
+                if let Some(ctx) = self.merge(last, ctx) {
+                    contexts.push(ctx);
+                }
+            },
+            None => contexts.push(ctx),
+        }
+    }
+
+    /// Folds the runs of `contexts[from..]` in place.
+    pub(crate) fn compact(&self, contexts: &mut ErrorVec<ErrorContext>, from: usize) {
+        let mut tail = contexts.drain(from..).collect::<ErrorVec<_>>().into_iter();
+        contexts.extend(tail.next());
+        for ctx in tail {
+            self.push(contexts, ctx);
+        }
+    }
+
+    /// Splits `contexts` into runs of foldable contexts, in push order.
+    ///
+    /// Each run is rendered as its most recent context, returned with its index,
+    /// with the returned repeat, or with its own stored repeat when the run has
+    /// one context.
+    pub(crate) fn runs<'a>(
+        &'a self,
+        contexts: &'a [ErrorContext],
+    ) -> impl Iterator<Item = (usize, &'a ErrorContext, Option<RepeatView<'a>>)> + 'a {
+        let mut rest = contexts;
+        let mut start = 0;
+        core::iter::from_fn(move || {
+            let (first, _) = rest.split_first()?;
+            let mut view = repeat_of(first);
+            let mut end = 1;
+            while let Some(next) = rest.get(end) {
+                match self.extend(view, &rest[end - 1], next) {
+                    Some(extended) => view = extended,
+                    None => break,
+                }
+                end += 1;
+            }
+            let newest = &rest[end - 1];
+            rest = &rest[end..];
+            start += end;
+            Some((start - 1, newest, (end > 1).then_some(view)))
+        })
+    }
+
+    /// Folds `newer` into `older`, or hands it back if the two differ.
+    fn merge(&self, older: &mut ErrorContext, newer: ErrorContext) -> Option<ErrorContext> {
+        let repeat = match self.extend(repeat_of(older), older, &newer) {
+            Some(view) => view.into_owned(),
+            None => return Some(newer),
+        };
+        let mut group = match newer {
+            ErrorContext::Simple(message) => {
+                Box::new(GroupContext { message: Some(message), ..Default::default() })
+            },
+            ErrorContext::Group(group) => group,
+            custom @ ErrorContext::Custom(_) => return Some(custom),
+        };
+        group.repeat = Some(repeat);
+        *older = ErrorContext::Group(group);
+        None
+    }
+
+    /// Extends the repeat of a run ending in `older` with `newer`, if they fold.
+    fn extend<'a>(
+        &'a self,
+        run: RepeatView<'a>,
+        older: &'a ErrorContext,
+        newer: &'a ErrorContext,
+    ) -> Option<RepeatView<'a>> {
+        let own = repeat_of(newer);
+        let count = run.count + own.count;
+        let range = match self.fold(older, newer)? {
+            Fold::Identical => match (run.range, own.range) {
+                (Some((a, _)), Some((b, _))) if a != b => return None,
+                (range, own) => range.or(own),
+            },
+            Fold::Counter(key) => {
+                if [run.range, own.range]
+                    .iter()
+                    .flatten()
+                    .any(|&(range_key, _)| range_key != key)
+                {
+                    return None;
+                }
+                match run.range {
+                    Some(range) => Some(range),
+                    None => Some((key, older.as_group()?.value(key)?)),
+                }
+            },
+        };
+        Some(RepeatView { count, range })
+    }
+
+    /// Returns how `older` and `newer` can be folded, ignoring timestamps and repeats.
+    fn fold<'a>(&'a self, older: &'a ErrorContext, newer: &'a ErrorContext) -> Option<Fold<'a>> {
+        let (a, b) = match (older, newer) {
+            (ErrorContext::Group(a), ErrorContext::Group(b)) => (a, b),
+            _ => {
+                return (plain_message(older)? == plain_message(newer)?).then_some(Fold::Identical)
+            },
+        };
+        if identity(a) != identity(b) {
+            return None;
+        }
+        if a.metadata == b.metadata {
+            return Some(Fold::Identical);
+        }
+        self.counter_keys().find_map(|key| {
+            let others = |g: &'a GroupContext| g.metadata.iter().filter(move |(k, _)| k != key);
+            (a.value(key).is_some() && b.value(key).is_some() && others(a).eq(others(b)))
+                .then_some(Fold::Counter(key))
+        })
+    }
+}
+
+/// Returns the stored repeat of `ctx`, or a count of one.
+#[inline]
+fn repeat_of(ctx: &ErrorContext) -> RepeatView<'_> {
+    match ctx {
+        ErrorContext::Group(g) => g.repeat.as_ref().map(ContextRepeat::view),
+        ErrorContext::Simple(_) | ErrorContext::Custom(_) => None,
+    }
+    .unwrap_or(RepeatView { count: 1, range: None })
+}
+
+/// Returns the message of a simple context, or of a group that has nothing else.
+///
+/// Custom contexts have no place to store a repeat count and are never folded.
+fn plain_message(ctx: &ErrorContext) -> Option<&str> {
+    match ctx {
+        ErrorContext::Simple(message) => Some(message),
+        ErrorContext::Custom(_) => None,
+        ErrorContext::Group(g) => {
+            let empty = GroupContext::default();
+            let (message, rest) = identity(g);
+            (g.metadata.is_empty() && rest == identity(&empty).1).then_some(message?)
+        },
+    }
+}
+
+/// Fields that identify a group, apart from its metadata, timestamp and repeat:
+/// the message and everything else.
+#[allow(clippy::type_complexity)]
+fn identity(
+    g: &GroupContext,
+) -> (
+    Option<&str>,
+    (
+        Option<&str>,
+        &Option<Location>,
+        &[Cow<'static, str>],
+        Option<Severity>,
+        bool,
+        &[Cow<'static, str>],
+        Visibility,
+    ),
+) {
+    let GroupContext {
+        message,
+        message_key,
+        location,
+        tags,
+        metadata: _,
+        severity,
+        timestamp: _,
+        secret_message,
+        secret_keys,
+        visibility,
+        repeat: _,
+    } = g;
+    (
+        message.as_deref(),
+        (
+            message_key.as_deref(),
+            location,
+            tags,
+            *severity,
+            *secret_message,
+            secret_keys,
+            *visibility,
+        ),
+    )
+}
@@ -0,0 +1,97 @@
+//! Typed, non-string payloads carried by a [`ComposableError`](super::ComposableError).
+
+use crate::types::alloc_type::Arc;
+use core::any::{type_name, Any};
+use core::fmt::{Debug, Display, Formatter, Result};
+
+/// Renders a type-erased attachment value, installed by the printable constructors.
+type RenderFn = fn(&(dyn Any + Send + Sync), &mut Formatter<'_>) -> Result;
+
+/// A single attachment: a shared value plus an optional rendering hook.
+///
+/// Values are reference counted so cloning an error does not require `T: Clone`.
+#[derive(Clone)]
+pub(crate) struct Attachment {
+    value: Arc<dyn Any + Send + Sync>,
+    type_name: &'static str,
+    render: Option<RenderFn>,
+}
+
+impl Attachment {
+    /// Creates an attachment that is never rendered.
+    #[inline]
+    pub(crate) fn new<T: Any + Send + Sync>(value: T) -> Self {
+        Self { value: Arc::new(value), type_name: type_name::<T>(), render: None }
+    }
+
+    /// Creates an attachment rendered with its `Display` implementation.
+    #[inline]
+    pub(crate) fn display<T: Display + Any + Send + Sync>(value: T) -> Self {
+        Self { render: Some(render_display::<T>), ..Self::new(value) }
+    }
+
+    /// Creates an attachment rendered with its `Debug` implementation.
+    #[inline]
+    pub(crate) fn debug<T: Debug + Any + Send + Sync>(value: T) -> Self {
+        Self { render: Some(render_debug::<T>), ..Self::new(value) }
+    }
+
+    /// Returns the value if it is of type `T`.
+    #[inline]
+    pub(crate) fn downcast_ref<T: Any>(&self) -> Option<&T> {
+        let value: &dyn Any = &*self.value;
+        value.downcast_ref::<T>()
+    }
+
+    /// Returns `true` if this attachment has a rendering hook.
+    #[inline]
+    pub(crate) fn is_printable(&self) -> bool {
+        self.render.is_some()
+    }
+}
+
+fn render_display<T: Display + Any>(
+    value: &(dyn Any + Send + Sync),
+    f: &mut Formatter<'_>,
+) -> Result {
+    match value.downcast_ref::<T>() {
+        Some(value) => Display::fmt(value, f),
+        None => Ok(()),
+    }
+}
+
+fn render_debug<T: Debug + Any>(value: &(dyn Any + Send + Sync), f: &mut Formatter<'_>) -> Result {
+    match value.downcast_ref::<T>() {
+        Some(value) => Debug::fmt(value, f),
+        None => Ok(()),
+    }
+}
+
+impl Display for Attachment {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        match self.render {
+            Some(render) => render(&*self.value, f),
+            None => Ok(()),
+        }
+    }
+}
+
+impl Debug for Attachment {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        struct Rendered<'a>(&'a Attachment);
+
+        impl Debug for Rendered<'_> {
+            fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+                match self.0.render {
+                    Some(_) => Display::fmt(self.0, f),
+                    None => f.write_str(".."),
+                }
+            }
+        }
+
+        f.debug_struct("Attachment")
+            .field("type_name", &self.type_name)
+            .field("value", &Rendered(self))
+            .finish()
+    }
+}
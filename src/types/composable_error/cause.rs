@@ -0,0 +1,457 @@
+//! Nested cause chains for [`ComposableError`].
+//!
+//! An [`ErrorCause`] is a type-erased layer of an error chain or tree. It keeps
+//! the rendered core message together with the structured contexts, error code
+//! and its own nested causes, so wrapping a lower-level [`ComposableError`] does
+//! not flatten it into a single string.
+
+use crate::types::alloc_type::{Arc, Box, String, Vec};
+use crate::types::caller_location::ContextSites;
+use crate::types::composable_error::ComposableError;
+use crate::types::context_limit;
+use crate::types::error_code::{CodeSlot, ErrorCode};
+use crate::types::{
+    ContextKey, ContextLimit, ErrorBacktrace, ErrorContext, ErrorVec, FromMetadata, Location,
+    Severity, Timestamp, TraceIds,
+};
+#[cfg(not(feature = "std"))]
+use alloc::string::ToString;
+use core::fmt::{Debug, Display};
+use core::hash::{Hash, Hasher};
+#[cfg(feature = "serde")]
+use serde::Deserialize;
+
+/// A single layer in a [`ComposableError`] cause chain or tree.
+///
+/// Created from another [`ComposableError`] (preserving its contexts, code and
+/// nested causes) or from any [`core::error::Error`] via [`ErrorCause::from_error`].
+///
+/// Equality and hashing only consider the structured parts (message, contexts,
+/// code, nested causes and whether the layer is suppressed); the capture sites
+/// and backtrace are ignored, and the original error retained by `from_error`
+/// is not compared and is skipped by serde.
+///
+/// # Examples
+///
+/// ```
+/// use error_rail::{ComposableError, ErrorContext};
+///
+/// let db = ComposableError::new("connection refused")
+///     .with_context(ErrorContext::tag("db"))
+///     .set_code(42);
+///
+/// let err = ComposableError::new("loading user failed").with_cause(db);
+///
+/// let cause = err.cause().unwrap();
+/// assert_eq!(cause.message(), "connection refused");
+/// assert_eq!(cause.error_code(), Some(42));
+/// assert_eq!(cause.context_iter().count(), 1);
+/// ```
+#[cfg_attr(feature = "serde", derive(Deserialize))]
+#[derive(Clone)]
+pub struct ErrorCause {
+    pub(crate) message: String,
+    pub(crate) context: ErrorVec<ErrorContext>,
+    pub(crate) error_code: Option<u32>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub(crate) code: Option<CodeSlot>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub(crate) severity: Option<Severity>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub(crate) timestamp: Option<Timestamp>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub(crate) trace_ids: Option<TraceIds>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub(crate) causes: Vec<ErrorCause>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub(crate) context_limit: Option<ContextLimit>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub(crate) dropped_contexts: usize,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub(crate) location: Option<Location>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub(crate) context_sites: ContextSites,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub(crate) backtrace: Option<ErrorBacktrace>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub(crate) suppressed: bool,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub(crate) source: Option<Arc<dyn core::error::Error + Send + Sync>>,
+}
+
+impl ErrorCause {
+    /// Creates a cause layer from any error, retaining it for `source()` and downcasting.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use error_rail::types::ErrorCause;
+    ///
+    /// let io = std::io::Error::other("disk full");
+    /// let cause = ErrorCause::from_error(io);
+    ///
+    /// assert_eq!(cause.message(), "disk full");
+    /// assert!(cause.downcast_ref::<std::io::Error>().is_some());
+    /// ```
+    pub fn from_error<E>(error: E) -> Self
+    where
+        E: core::error::Error + Send + Sync + 'static,
+    {
+        let message = error.to_string();
+        Self { source: Some(Arc::new(error)), ..Self::from_message(message) }
+    }
+
+    /// Creates a layer holding only `message`.
+    #[inline]
+    fn from_message(message: String) -> Self {
+        Self {
+            message,
+            context: ErrorVec::new(),
+            error_code: None,
+            code: None,
+            severity: None,
+            timestamp: None,
+            trace_ids: None,
+            causes: Vec::new(),
+            context_limit: None,
+            dropped_contexts: 0,
+            location: None,
+            context_sites: ContextSites::default(),
+            backtrace: None,
+            suppressed: false,
+            source: None,
+        }
+    }
+
+    /// Creates a cause layer from any error and captures its `source()` chain.
+    ///
+    /// Like [`ErrorCause::from_error`], but every error reachable through
+    /// [`core::error::Error::source`] is also recorded as a nested layer, so the
+    /// whole chain shows up in formatted output, serde output and fingerprints.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use error_rail::types::ErrorCause;
+    /// use std::{error::Error, fmt, io};
+    ///
+    /// #[derive(Debug)]
+    /// struct ConfigError(io::Error);
+    ///
+    /// impl fmt::Display for ConfigError {
+    ///     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    ///         f.write_str("failed to read config")
+    ///     }
+    /// }
+    ///
+    /// impl Error for ConfigError {
+    ///     fn source(&self) -> Option<&(dyn Error + 'static)> {
+    ///         Some(&self.0)
+    ///     }
+    /// }
+    ///
+    /// let cause = ErrorCause::from_error_chain(ConfigError(io::Error::other("disk full")));
+    ///
+    /// assert_eq!(cause.message(), "failed to read config");
+    /// assert_eq!(cause.cause().unwrap().message(), "disk full");
+    /// ```
+    pub fn from_error_chain<E>(error: E) -> Self
+    where
+        E: core::error::Error + Send + Sync + 'static,
+    {
+        let causes = Self::from_sources(error.source()).into_iter().collect();
+        Self { causes, ..Self::from_error(error) }
+    }
+
+    /// Builds a linear chain of layers from an error's `source()` chain.
+    pub(crate) fn from_sources(
+        mut source: Option<&(dyn core::error::Error + 'static)>,
+    ) -> Option<Self> {
+        let mut messages = Vec::new();
+        while let Some(err) = source {
+            messages.push(err.to_string());
+            source = err.source();
+        }
+
+        messages.into_iter().rev().fold(None, |below, message| {
+            Some(Self { causes: below.into_iter().collect(), ..Self::from_message(message) })
+        })
+    }
+
+    /// Returns the rendered core error message of this layer.
+    #[inline]
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    /// Returns an iterator in LIFO order (most recent first) over this layer's contexts.
+    #[inline]
+    pub fn context_iter(&self) -> core::iter::Rev<core::slice::Iter<'_, ErrorContext>> {
+        self.context.iter().rev()
+    }
+
+    /// Reads a typed metadata value from this layer's contexts, most recent first.
+    #[inline]
+    pub fn metadata_value<T: FromMetadata>(&self, key: ContextKey<T>) -> Option<T> {
+        self.context_iter().find_map(|ctx| ctx.metadata_value(key))
+    }
+
+    /// Returns the numeric error code of this layer, if any.
+    #[inline]
+    pub const fn error_code(&self) -> Option<u32> {
+        self.error_code
+    }
+
+    /// Returns the error code of this layer, whatever its type.
+    #[inline]
+    pub fn code(&self) -> Option<&dyn ErrorCode> {
+        match &self.code {
+            Some(slot) => Some(slot.get()),
+            None => self.error_code.as_ref().map(|code| code as &dyn ErrorCode),
+        }
+    }
+
+    /// Returns the severity of this layer, if any.
+    #[inline]
+    pub const fn severity(&self) -> Option<Severity> {
+        self.severity
+    }
+
+    /// Returns the time at which this layer was recorded, if any.
+    #[inline]
+    pub const fn timestamp(&self) -> Option<Timestamp> {
+        self.timestamp
+    }
+
+    /// Returns the trace IDs recorded on this layer, if any.
+    #[inline]
+    pub fn trace_ids(&self) -> Option<&TraceIds> {
+        self.trace_ids.as_ref()
+    }
+
+    /// Returns how many contexts this layer dropped because of its context limit.
+    #[inline]
+    pub const fn dropped_contexts(&self) -> usize {
+        self.dropped_contexts
+    }
+
+    /// Returns where this layer entered the rail, if it was captured.
+    #[inline]
+    pub fn location(&self) -> Option<&Location> {
+        self.location.as_ref()
+    }
+
+    /// Returns where each context of this layer was attached, most recent
+    /// first; see [`ComposableError::context_sites`].
+    pub fn context_sites(&self) -> impl Iterator<Item = Option<&Location>> + '_ {
+        let sites = self.context_sites.of(self.context.len());
+        (0..self.context.len())
+            .rev()
+            .map(move |i| sites.get(i).and_then(Option::as_ref))
+    }
+
+    /// Returns the structured backtrace of this layer, if any.
+    #[inline]
+    pub fn backtrace(&self) -> Option<&ErrorBacktrace> {
+        self.backtrace.as_ref()
+    }
+
+    /// Returns `true` if this layer was suppressed while handling another error,
+    /// rather than causing it; see [`ComposableError::merge`].
+    #[inline]
+    pub const fn is_suppressed(&self) -> bool {
+        self.suppressed
+    }
+
+    /// Returns where dropped contexts were removed, in push order, and how many.
+    #[inline]
+    pub(crate) fn elision(&self) -> Option<(usize, usize)> {
+        context_limit::elision(self.context_limit, self.dropped_contexts, self.context.len())
+    }
+
+    /// Returns the first layer below this one that is not suppressed, if any.
+    #[inline]
+    pub fn cause(&self) -> Option<&ErrorCause> {
+        self.causes.iter().find(|cause| !cause.suppressed)
+    }
+
+    /// Returns the direct children of this layer.
+    #[inline]
+    pub fn children(&self) -> &[ErrorCause] {
+        &self.causes
+    }
+
+    /// Returns a depth-first iterator over every layer below this one.
+    #[inline]
+    pub fn causes(&self) -> Causes<'_> {
+        Causes::new(&self.causes)
+    }
+
+    /// Attempts to downcast the error retained by [`ErrorCause::from_error`].
+    #[inline]
+    pub fn downcast_ref<T>(&self) -> Option<&T>
+    where
+        T: core::error::Error + 'static,
+    {
+        let source: &(dyn core::error::Error + 'static) = self.source.as_deref()?;
+        source.downcast_ref::<T>()
+    }
+}
+
+/// Keeps everything that describes the failure: contexts, code, severity,
+/// timestamp, trace IDs, nested causes, context limit, location, context
+/// sites and backtrace.
+///
+/// Attachments, the public message and the hint are dropped, because only the
+/// outermost error is read back with `request_ref` or shown by
+/// [`public_view`](ComposableError::public_view). Compaction rules are dropped
+/// as well, since a cause layer never receives new contexts.
+impl<E: Display> From<ComposableError<E>> for ErrorCause {
+    fn from(error: ComposableError<E>) -> Self {
+        let extras = error.extras.map(|extras| *extras).unwrap_or_default();
+        Self {
+            context: error.context,
+            error_code: error.error_code,
+            code: extras.code,
+            severity: extras.severity,
+            timestamp: extras.timestamp,
+            trace_ids: extras.trace,
+            causes: extras.causes,
+            context_limit: extras.context_limit,
+            dropped_contexts: extras.dropped_contexts,
+            location: extras.location,
+            context_sites: extras.sites,
+            backtrace: extras.backtrace,
+            ..Self::from_message(error.core_error.to_string())
+        }
+    }
+}
+
+impl<E: Display> From<Box<ComposableError<E>>> for ErrorCause {
+    #[inline]
+    fn from(error: Box<ComposableError<E>>) -> Self {
+        Self::from(*error)
+    }
+}
+
+impl Debug for ErrorCause {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("ErrorCause")
+            .field("message", &self.message)
+            .field("context", &self.context)
+            .field("error_code", &self.error_code)
+            .field("code", &self.code)
+            .field("severity", &self.severity)
+            .field("timestamp", &self.timestamp)
+            .field("trace_ids", &self.trace_ids)
+            .field("causes", &self.causes)
+            .field("dropped_contexts", &self.dropped_contexts)
+            .field("location", &self.location)
+            .field("backtrace", &self.backtrace)
+            .field("suppressed", &self.suppressed)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Display for ErrorCause {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        for ctx in self.context_iter() {
+            write!(f, "{} -> ", ctx)?;
+        }
+        f.write_str(&self.message)?;
+        if let Some(code) = self.code() {
+            write!(f, " (code: {})", code)?;
+        }
+        Ok(())
+    }
+}
+
+impl PartialEq for ErrorCause {
+    fn eq(&self, other: &Self) -> bool {
+        self.message == other.message
+            && self.context == other.context
+            && self.error_code == other.error_code
+            && self.code == other.code
+            && self.severity == other.severity
+            && self.timestamp == other.timestamp
+            && self.trace_ids == other.trace_ids
+            && self.causes == other.causes
+            && self.suppressed == other.suppressed
+    }
+}
+
+impl Eq for ErrorCause {}
+
+impl Hash for ErrorCause {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.message.hash(state);
+        self.context.hash(state);
+        self.error_code.hash(state);
+        self.code.hash(state);
+        self.severity.hash(state);
+        self.timestamp.hash(state);
+        self.trace_ids.hash(state);
+        self.causes.hash(state);
+        self.suppressed.hash(state);
+    }
+}
+
+impl core::error::Error for ErrorCause {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self.cause() {
+            Some(cause) => Some(cause),
+            None => self.source.as_deref().and_then(|source| source.source()),
+        }
+    }
+}
+
+/// Depth-first iterator over the layers of a cause chain or tree.
+///
+/// Returned by [`ComposableError::causes`] and [`ErrorCause::causes`].
+#[derive(Debug, Clone)]
+pub struct Causes<'a> {
+    stack: ErrorVec<core::slice::Iter<'a, ErrorCause>>,
+}
+
+impl<'a> Causes<'a> {
+    #[inline]
+    pub(crate) fn new(children: &'a [ErrorCause]) -> Self {
+        let mut stack = ErrorVec::new();
+        stack.push(children.iter());
+        Self { stack }
+    }
+
+    /// Returns the next layer together with its depth (direct children are depth 1).
+    pub(crate) fn next_with_depth(&mut self) -> Option<(&'a ErrorCause, usize)> {
+        loop {
+            let top = self.stack.last_mut()?;
+            match top.next() {
+                Some(cause) => {
+                    let depth = self.stack.len();
+                    self.stack.push(cause.causes.iter());
+                    return Some((cause, depth));
+                },
+                None => {
+                    self.stack.pop();
+                },
+            }
+        }
+    }
+
+    /// Skips the layers below the one last returned by `next_with_depth`.
+    #[inline]
+    pub(crate) fn skip_children(&mut self) {
+        self.stack.pop();
+    }
+}
+
+impl<'a> Iterator for Causes<'a> {
+    type Item = &'a ErrorCause;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_with_depth().map(|(cause, _)| cause)
+    }
+}
+
+impl core::iter::FusedIterator for Causes<'_> {}
@@ -0,0 +1,98 @@
+//! Optional, rarely used parts of a [`ComposableError`](super::ComposableError).
+//!
+//! These live behind a single `Option<Box<Extras>>` so that errors which only
+//! carry a core error, contexts and a code stay as small as before.
+
+use crate::types::alloc_type::{Cow, Vec};
+use crate::types::caller_location::ContextSites;
+use crate::types::composable_error::attachment::Attachment;
+use crate::types::composable_error::ErrorCause;
+use crate::types::error_code::CodeSlot;
+use crate::types::{
+    ContextCompaction, ContextLimit, ErrorBacktrace, Location, Severity, Timestamp, TraceIds,
+};
+
+/// Heap-allocated storage for the optional parts of a composable error.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct Extras {
+    /// Child errors: a single entry forms a cause chain, several form a tree.
+    pub(crate) causes: Vec<ErrorCause>,
+    /// Typed payloads in attachment order.
+    pub(crate) attachments: Vec<Attachment>,
+    /// Severity set on the error itself.
+    pub(crate) severity: Option<Severity>,
+    /// Non-numeric error code; its numeric part is mirrored in `error_code`.
+    pub(crate) code: Option<CodeSlot>,
+    /// Time at which the error was recorded.
+    pub(crate) timestamp: Option<Timestamp>,
+    /// Trace, span and correlation IDs of the failed request.
+    pub(crate) trace: Option<TraceIds>,
+    /// Maximum number of contexts kept on the error.
+    pub(crate) context_limit: Option<ContextLimit>,
+    /// Number of contexts dropped because of `context_limit`.
+    pub(crate) dropped_contexts: usize,
+    /// Rules for folding repeated contexts on push.
+    pub(crate) compaction: Option<ContextCompaction>,
+    /// Source location where the error entered the rail.
+    pub(crate) location: Option<Location>,
+    /// Call sites at which the contexts were attached.
+    pub(crate) sites: ContextSites,
+    /// Structured backtrace captured when the error occurred.
+    pub(crate) backtrace: Option<ErrorBacktrace>,
+    /// Message that is safe to show to end users.
+    pub(crate) public_message: Option<Cow<'static, str>>,
+    /// User-facing suggestion on how to resolve the error.
+    pub(crate) hint: Option<Cow<'static, str>>,
+}
+
+impl Extras {
+    /// Returns `true` when no optional part is set.
+    #[cfg(feature = "serde")]
+    #[inline]
+    pub(crate) fn is_empty(&self) -> bool {
+        self.causes.is_empty()
+            && self.attachments.is_empty()
+            && self.severity.is_none()
+            && self.code.is_none()
+            && self.timestamp.is_none()
+            && self.trace.is_none()
+            && self.context_limit.is_none()
+            && self.dropped_contexts == 0
+            && self.compaction.is_none()
+            && self.location.is_none()
+            && self.sites.is_empty()
+            && self.backtrace.is_none()
+            && self.public_message.is_none()
+            && self.hint.is_none()
+    }
+
+    /// Moves every optional part of `other` into `self`, except the error code
+    /// and the context sites.
+    ///
+    /// `self` is treated as the older error, so its timestamp, trace IDs,
+    /// location and backtrace are kept when set, while the newer public message
+    /// and hint replace its own. The higher of the two severities is kept.
+    ///
+    /// The code is paired with the numeric `error_code` field outside of `Extras`,
+    /// so callers merge it together with that field. Likewise the sites line up
+    /// with the context stack and are merged together with it.
+    #[inline]
+    pub(crate) fn absorb(&mut self, other: Extras) {
+        self.causes.extend(other.causes);
+        self.attachments.extend(other.attachments);
+        self.severity = self.severity.max(other.severity);
+        self.timestamp = self.timestamp.or(other.timestamp);
+        self.context_limit = self.context_limit.or(other.context_limit);
+        self.dropped_contexts += other.dropped_contexts;
+        self.compaction = self.compaction.take().or(other.compaction);
+        self.location = self.location.take().or(other.location);
+        self.backtrace = self.backtrace.take().or(other.backtrace);
+        self.public_message = other.public_message.or(self.public_message.take());
+        self.hint = other.hint.or(self.hint.take());
+        if let Some(trace) = other.trace {
+            self.trace
+                .get_or_insert_with(Default::default)
+                .fill_from(trace);
+        }
+    }
+}
@@ -1,7 +1,9 @@
 use crate::types::alloc_type::String;
 use crate::types::composable_error::ComposableError;
-use crate::types::ErrorContext;
+use crate::types::error_code::CodeSlot;
+use crate::types::{ErrorContext, Severity, Timestamp, TraceIds};
 use core::fmt::{Display, Write};
+use core::hash::Hasher;
 
 /// Configuration builder for customizing fingerprint generation.
 pub struct FingerprintConfig<'a, E> {
@@ -10,6 +12,12 @@ pub struct FingerprintConfig<'a, E> {
     pub(crate) include_code: bool,
     pub(crate) include_message: bool,
     pub(crate) include_metadata: bool,
+    pub(crate) include_causes: bool,
+    pub(crate) include_suppressed: bool,
+    pub(crate) include_custom: bool,
+    pub(crate) include_severity: bool,
+    pub(crate) include_timestamps: bool,
+    pub(crate) include_trace_ids: bool,
     pub(crate) include_keys: Option<&'a [&'a str]>,
     pub(crate) exclude_keys: Option<&'a [&'a str]>,
 }
@@ -22,6 +30,12 @@ impl<'a, E> FingerprintConfig<'a, E> {
             include_code: true,
             include_message: true,
             include_metadata: false,
+            include_causes: true,
+            include_suppressed: false,
+            include_custom: true,
+            include_severity: false,
+            include_timestamps: false,
+            include_trace_ids: false,
             include_keys: None,
             exclude_keys: None,
         }
@@ -55,6 +69,68 @@ impl<'a, E> FingerprintConfig<'a, E> {
         self
     }
 
+    /// Whether to include nested cause layers in the fingerprint (default: true).
+    ///
+    /// The whole cause tree is hashed depth-first together with each layer's
+    /// depth. Every cause contributes its tags, code, message and metadata
+    /// according to the same options used for the outer error.
+    #[must_use]
+    pub fn include_causes(mut self, include: bool) -> Self {
+        self.include_causes = include;
+        self
+    }
+
+    /// Whether to include suppressed errors and the layers below them (default: false).
+    ///
+    /// Errors suppressed by [`ComposableError::merge`] happened while handling
+    /// this one, so by default they do not change how it is grouped. Only
+    /// applies when [`include_causes`](Self::include_causes) is set.
+    #[must_use]
+    pub fn include_suppressed(mut self, include: bool) -> Self {
+        self.include_suppressed = include;
+        self
+    }
+
+    /// Whether to include custom contexts in the fingerprint (default: true).
+    ///
+    /// Each [`ContextPayload`](crate::types::ContextPayload) contributes its
+    /// kind and whatever its [`fingerprint`](crate::types::ContextPayload::fingerprint)
+    /// method feeds into the hash.
+    #[must_use]
+    pub fn include_custom(mut self, include: bool) -> Self {
+        self.include_custom = include;
+        self
+    }
+
+    /// Whether to include severities in the fingerprint (default: false).
+    ///
+    /// Each layer contributes the highest severity among itself and its contexts.
+    #[must_use]
+    pub fn include_severity(mut self, include: bool) -> Self {
+        self.include_severity = include;
+        self
+    }
+
+    /// Whether to include recorded timestamps in the fingerprint (default: false).
+    ///
+    /// Timestamps make every occurrence unique, so this is only useful to
+    /// identify a single occurrence rather than to group similar errors.
+    #[must_use]
+    pub fn include_timestamps(mut self, include: bool) -> Self {
+        self.include_timestamps = include;
+        self
+    }
+
+    /// Whether to include trace, span and correlation IDs in the fingerprint (default: false).
+    ///
+    /// Like timestamps, IDs differ per request, so enabling this separates
+    /// otherwise identical errors.
+    #[must_use]
+    pub fn include_trace_ids(mut self, include: bool) -> Self {
+        self.include_trace_ids = include;
+        self
+    }
+
     /// Explicitly include only these metadata keys in the fingerprint.
     #[must_use]
     pub fn include_metadata_keys(mut self, keys: &'a [&'a str]) -> Self {
@@ -78,31 +154,91 @@ impl<'a, E> FingerprintConfig<'a, E> {
         E: Display,
     {
         let mut hasher = FnvHasher::new();
+        let error = self.error;
+
+        self.hash_layer(
+            &mut hasher,
+            Layer {
+                contexts: &error.context,
+                code: error.error_code,
+                rich_code: error.code_slot(),
+                severity: error.severity(),
+                timestamp: error.timestamp(),
+                trace: error.trace_ids(),
+                message: &error.core_error,
+            },
+        );
+
+        if self.include_causes {
+            let mut causes = error.causes();
+            while let Some((cause, depth)) = causes.next_with_depth() {
+                if cause.suppressed && !self.include_suppressed {
+                    causes.skip_children();
+                    continue;
+                }
+                hasher.write(b"cause:");
+                hasher.write(&(depth as u64).to_le_bytes());
+                self.hash_layer(
+                    &mut hasher,
+                    Layer {
+                        contexts: &cause.context,
+                        code: cause.error_code,
+                        rich_code: cause.code.as_ref(),
+                        severity: cause.severity,
+                        timestamp: cause.timestamp,
+                        trace: cause.trace_ids.as_ref(),
+                        message: &cause.message,
+                    },
+                );
+            }
+        }
+
+        hasher.finish()
+    }
+
+    #[inline]
+    fn hash_layer(&self, hasher: &mut FnvHasher, layer: Layer<'_>) {
+        let Layer { contexts, code, rich_code, severity, timestamp, trace, message } = layer;
 
         if self.include_tags {
-            self.hash_tags(&mut hasher);
+            Self::hash_tags(hasher, contexts);
         }
 
         if self.include_code {
-            self.hash_code(&mut hasher);
+            Self::hash_code(hasher, code, rich_code);
         }
 
         if self.include_message {
-            self.hash_message(&mut hasher);
+            Self::hash_message(hasher, message);
         }
 
         if self.include_metadata {
-            self.hash_metadata(&mut hasher);
+            self.hash_metadata(hasher, contexts);
         }
 
-        hasher.finish()
+        if self.include_custom {
+            Self::hash_custom(hasher, contexts);
+        }
+
+        if self.include_severity {
+            Self::hash_severity(hasher, contexts, severity);
+        }
+
+        if self.include_timestamps {
+            Self::hash_timestamps(hasher, contexts, timestamp);
+        }
+
+        if self.include_trace_ids {
+            if let Some(trace) = trace {
+                hasher.write(b"trace:");
+                let _ = write!(hasher, "{}", trace);
+            }
+        }
     }
 
     #[inline]
-    fn hash_tags(&self, hasher: &mut FnvHasher) {
-        let tag_count: usize = self
-            .error
-            .context
+    fn hash_tags(hasher: &mut FnvHasher, contexts: &[ErrorContext]) {
+        let tag_count: usize = contexts
             .iter()
             .filter_map(|ctx| match ctx {
                 ErrorContext::Group(g) => Some(g.tags.len()),
@@ -115,7 +251,7 @@ impl<'a, E> FingerprintConfig<'a, E> {
         }
 
         let mut tags = crate::types::alloc_type::Vec::with_capacity(tag_count);
-        for ctx in &self.error.context {
+        for ctx in contexts {
             if let ErrorContext::Group(g) = ctx {
                 tags.extend_from_slice(&g.tags);
             }
@@ -128,28 +264,64 @@ impl<'a, E> FingerprintConfig<'a, E> {
         }
     }
 
+    /// Plain numeric codes keep their historical encoding; other codes are
+    /// hashed by their rendered form.
     #[inline]
-    fn hash_code(&self, hasher: &mut FnvHasher) {
-        if let Some(code) = self.error.error_code {
+    fn hash_code(hasher: &mut FnvHasher, code: Option<u32>, rich_code: Option<&CodeSlot>) {
+        if let Some(rich) = rich_code {
+            hasher.write(b"code:");
+            let _ = write!(hasher, "{}", rich.get());
+        } else if let Some(code) = code {
             hasher.write(b"code:");
             hasher.write(&code.to_le_bytes());
         }
     }
 
     #[inline]
-    fn hash_message(&self, hasher: &mut FnvHasher)
-    where
-        E: Display,
-    {
+    fn hash_severity(hasher: &mut FnvHasher, contexts: &[ErrorContext], own: Option<Severity>) {
+        let severity = contexts
+            .iter()
+            .filter_map(ErrorContext::severity)
+            .chain(own)
+            .max();
+        if let Some(severity) = severity {
+            hasher.write(b"sev:");
+            hasher.write(&[severity as u8]);
+        }
+    }
+
+    #[inline]
+    fn hash_timestamps(hasher: &mut FnvHasher, contexts: &[ErrorContext], own: Option<Timestamp>) {
+        for timestamp in contexts
+            .iter()
+            .filter_map(ErrorContext::timestamp)
+            .chain(own)
+        {
+            let since_epoch = timestamp.as_unix_duration();
+            hasher.write(b"ts:");
+            hasher.write(&since_epoch.as_secs().to_le_bytes());
+            hasher.write(&since_epoch.subsec_nanos().to_le_bytes());
+        }
+    }
+
+    #[inline]
+    fn hash_custom(hasher: &mut FnvHasher, contexts: &[ErrorContext]) {
+        for payload in contexts.iter().filter_map(ErrorContext::as_custom) {
+            hasher.write(b"custom:");
+            hasher.write(payload.kind().as_bytes());
+            payload.fingerprint(hasher);
+        }
+    }
+
+    #[inline]
+    fn hash_message(hasher: &mut FnvHasher, message: &dyn Display) {
         hasher.write(b"msg:");
-        let _ = write!(hasher, "{}", self.error.core_error);
+        let _ = write!(hasher, "{}", message);
     }
 
     #[inline]
-    fn hash_metadata(&self, hasher: &mut FnvHasher) {
-        let meta_count: usize = self
-            .error
-            .context
+    fn hash_metadata(&self, hasher: &mut FnvHasher, contexts: &[ErrorContext]) {
+        let meta_count: usize = contexts
             .iter()
             .filter_map(|ctx| match ctx {
                 ErrorContext::Group(g) => Some(g.metadata.len()),
@@ -163,10 +335,10 @@ impl<'a, E> FingerprintConfig<'a, E> {
 
         let mut metadata = crate::types::alloc_type::Vec::with_capacity(meta_count);
 
-        for ctx in &self.error.context {
+        for ctx in contexts {
             if let ErrorContext::Group(g) = ctx {
-                for (k, v) in &g.metadata {
-                    if self.should_include_key(k.as_ref()) {
+                for (k, v) in g.redacted_metadata() {
+                    if self.should_include_key(k) {
                         metadata.push((k, v));
                     }
                 }
@@ -179,7 +351,7 @@ impl<'a, E> FingerprintConfig<'a, E> {
             hasher.write(b"meta:");
             hasher.write(key.as_bytes());
             hasher.write(b"=");
-            hasher.write(value.as_bytes());
+            let _ = write!(hasher, "{}", value);
         }
     }
 
@@ -203,6 +375,17 @@ impl<'a, E> FingerprintConfig<'a, E> {
     }
 }
 
+/// The parts of one error layer (the outer error or a cause) that can be hashed.
+struct Layer<'a> {
+    contexts: &'a [ErrorContext],
+    code: Option<u32>,
+    rich_code: Option<&'a CodeSlot>,
+    severity: Option<Severity>,
+    timestamp: Option<Timestamp>,
+    trace: Option<&'a TraceIds>,
+    message: &'a dyn Display,
+}
+
 /// FNV-1a hasher for 64-bit hash computation.
 struct FnvHasher {
     hash: u64,
@@ -231,6 +414,18 @@ impl FnvHasher {
     }
 }
 
+impl Hasher for FnvHasher {
+    #[inline]
+    fn finish(&self) -> u64 {
+        self.hash
+    }
+
+    #[inline]
+    fn write(&mut self, bytes: &[u8]) {
+        FnvHasher::write(self, bytes);
+    }
+}
+
 impl Write for FnvHasher {
     #[inline]
     fn write_str(&mut self, s: &str) -> core::fmt::Result {
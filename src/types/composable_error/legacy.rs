@@ -68,7 +68,7 @@ where
         Display::fmt(&self.error.core_error, f)?;
 
         if self.show_code {
-            if let Some(code) = &self.error.error_code {
+            if let Some(code) = self.error.code() {
                 write!(f, " (code: {})", code)?;
             }
         }
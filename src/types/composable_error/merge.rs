@@ -0,0 +1,207 @@
+//! Merging two composable errors into one.
+//!
+//! Cleanup paths often end up holding two errors: the primary failure and a
+//! secondary one raised while rolling back or closing resources.
+//! [`ComposableError::merge`] folds the secondary error into the primary one
+//! according to a [`MergeStrategy`], and resolves the error code with a
+//! [`CodeRule`].
+
+use super::ComposableError;
+use crate::types::composable_error::ErrorCause;
+use crate::types::ErrorContext;
+#[cfg(not(feature = "std"))]
+use alloc::string::ToString;
+use core::fmt::Display;
+
+/// How the secondary error is folded into the primary one.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum MergeStrategy {
+    /// Keeps the primary error as is and attaches the secondary error as a
+    /// suppressed child.
+    ///
+    /// Suppressed children are rendered with a `"suppressed: "` prefix, are
+    /// never returned by [`ComposableError::cause`] or `source()`, and are left
+    /// out of the fingerprint unless
+    /// [`FingerprintConfig::include_suppressed`](crate::types::FingerprintConfig::include_suppressed)
+    /// is set.
+    #[default]
+    Suppress,
+    /// Appends the secondary error's message and contexts to the primary
+    /// error's context stack, after the primary's own contexts.
+    ///
+    /// The secondary error's causes, attachments and other parts are moved
+    /// over as well. The primary's timestamp, location, public message and
+    /// hint win when set, and the higher of the two severities is kept.
+    Concat,
+}
+
+/// Which error code the merged error keeps.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum CodeRule {
+    /// Keeps the primary error's code, or the secondary's if the primary has none.
+    #[default]
+    First,
+    /// Keeps the secondary error's code, or the primary's if the secondary has none.
+    Last,
+    /// Keeps the code with the larger numeric value.
+    ///
+    /// Codes without a numeric value lose against numeric ones; the primary
+    /// error's code wins ties.
+    Max,
+}
+
+/// Options for [`ComposableError::merge_with`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct MergeOptions {
+    strategy: MergeStrategy,
+    code: CodeRule,
+}
+
+impl MergeOptions {
+    /// Creates options that suppress the secondary error and keep the first code.
+    #[inline]
+    pub const fn new() -> Self {
+        Self { strategy: MergeStrategy::Suppress, code: CodeRule::First }
+    }
+
+    /// Attaches the secondary error as a suppressed child.
+    #[inline]
+    pub const fn suppress() -> Self {
+        Self::new().strategy(MergeStrategy::Suppress)
+    }
+
+    /// Concatenates the context stacks of both errors.
+    #[inline]
+    pub const fn concat() -> Self {
+        Self::new().strategy(MergeStrategy::Concat)
+    }
+
+    /// Sets the merge strategy.
+    #[inline]
+    pub const fn strategy(mut self, strategy: MergeStrategy) -> Self {
+        self.strategy = strategy;
+        self
+    }
+
+    /// Sets the rule that picks the error code.
+    #[inline]
+    pub const fn code_rule(mut self, rule: CodeRule) -> Self {
+        self.code = rule;
+        self
+    }
+}
+
+impl CodeRule {
+    /// Returns `true` when the secondary error's code replaces the primary's.
+    fn takes_other<E, F>(self, primary: &ComposableError<E>, other: &ComposableError<F>) -> bool {
+        let has_code = |code: Option<u32>, slot: bool| code.is_some() || slot;
+        let primary_has = has_code(primary.error_code, primary.code_slot().is_some());
+        let other_has = has_code(other.error_code, other.code_slot().is_some());
+        match self {
+            Self::First => !primary_has && other_has,
+            Self::Last => other_has,
+            Self::Max => match (primary.error_code, other.error_code) {
+                (Some(a), Some(b)) => b > a,
+                (None, Some(_)) => true,
+                (Some(_), None) => false,
+                (None, None) => !primary_has && other_has,
+            },
+        }
+    }
+}
+
+impl<E> ComposableError<E> {
+    /// Merges `other` into this error, suppressing it and keeping this error's code.
+    ///
+    /// Equivalent to [`merge_with`](Self::merge_with) with the default [`MergeOptions`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use error_rail::ComposableError;
+    ///
+    /// let write = ComposableError::new("write failed").with_context("saving order").set_code(500);
+    /// let rollback = ComposableError::new("rollback failed").set_code(503);
+    ///
+    /// let err = write.merge(rollback);
+    /// assert_eq!(err.error_code(), Some(500));
+    /// assert!(err.cause().is_none());
+    /// assert_eq!(err.suppressed().count(), 1);
+    /// assert_eq!(
+    ///     err.error_chain(),
+    ///     "saving order -> write failed (code: 500) -> suppressed: rollback failed (code: 503)"
+    /// );
+    /// ```
+    #[inline]
+    pub fn merge<F: Display>(self, other: ComposableError<F>) -> Self {
+        self.merge_with(other, MergeOptions::new())
+    }
+
+    /// Merges `other` into this error according to `options`.
+    ///
+    /// This error is the primary one: its core error is kept, and `other` is
+    /// treated as a secondary error raised later, e.g. while cleaning up.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use error_rail::ComposableError;
+    /// use error_rail::types::{CodeRule, MergeOptions};
+    ///
+    /// let write = ComposableError::new("write failed").with_context("saving order").set_code(500);
+    /// let close = ComposableError::new("close failed").with_context("closing file").set_code(503);
+    ///
+    /// let err = write.merge_with(close, MergeOptions::concat().code_rule(CodeRule::Max));
+    /// assert_eq!(err.error_code(), Some(503));
+    /// assert_eq!(
+    ///     err.error_chain(),
+    ///     "closing file -> close failed -> saving order -> write failed (code: 503)"
+    /// );
+    /// ```
+    pub fn merge_with<F: Display>(
+        mut self,
+        other: ComposableError<F>,
+        options: MergeOptions,
+    ) -> Self {
+        let takes_code = options.code.takes_other(&self, &other);
+        match options.strategy {
+            MergeStrategy::Suppress => {
+                if takes_code {
+                    self.error_code = other.error_code;
+                    self.set_code_slot(other.code_slot().cloned());
+                }
+                let mut cause = ErrorCause::from(other);
+                cause.suppressed = true;
+                self.extras_mut().causes.push(cause);
+            },
+            MergeStrategy::Concat => {
+                let ComposableError { core_error, context, error_code, mut extras } = other;
+                let sites = extras
+                    .as_deref_mut()
+                    .map(|extras| core::mem::take(&mut extras.sites))
+                    .unwrap_or_default();
+                let sites = sites.of(context.len());
+                self.push_context(ErrorContext::new(core_error.to_string()));
+                for (i, ctx) in context.into_iter().enumerate() {
+                    self.push_context_at(ctx, sites.get(i).cloned().flatten());
+                }
+                let mut code = None;
+                if let Some(mut extras) = extras {
+                    code = extras.code.take();
+                    let public_message = extras.public_message.take();
+                    let hint = extras.hint.take();
+                    let own = self.extras_mut();
+                    own.absorb(*extras);
+                    own.public_message = own.public_message.take().or(public_message);
+                    own.hint = own.hint.take().or(hint);
+                    self.enforce_context_limit();
+                }
+                if takes_code {
+                    self.error_code = error_code;
+                    self.set_code_slot(code);
+                }
+            },
+        }
+        self
+    }
+}
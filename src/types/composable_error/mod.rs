@@ -3,45 +3,101 @@
 //! This module provides [`ComposableError`], a wrapper that enriches any error type with:
 //! - Multiple [`ErrorContext`] entries for structured metadata
 //! - Optional error codes (defaults to `u32`)
+//! - Optional nested [`ErrorCause`] chains or trees for wrapping lower-level errors
 //! - Builder pattern for incremental context accumulation
 
 use crate::traits::IntoErrorContext;
-use crate::types::alloc_type::String;
-use crate::types::{ErrorContext, ErrorVec};
+use crate::types::alloc_type::{Box, Cow, String, Vec};
+use crate::types::caller_location;
+use crate::types::error_code::{CodeSlot, ErrorCode};
+use crate::types::{context_limit, context_query};
+use crate::types::{
+    Clock, ContextCompaction, ContextKey, ContextLimit, ErrorBacktrace, ErrorContext, ErrorVec,
+    FromMetadata, Location, MetadataValue, PublicView, Severity, SharedComposableError,
+    ThinComposableError, Timestamp, TraceIds,
+};
 #[cfg(not(feature = "std"))]
 use alloc::string::ToString;
 #[cfg(feature = "serde")]
-use serde::{Deserialize, Serialize};
+use serde::Deserialize;
 
+mod attachment;
+mod cause;
+mod extras;
 mod fingerprint;
 mod legacy;
+mod merge;
+#[cfg(feature = "serde")]
+mod serde_impl;
 mod traits;
 
+pub(crate) use attachment::Attachment;
+use extras::Extras;
+
+pub use cause::{Causes, ErrorCause};
 pub use fingerprint::FingerprintConfig;
 #[allow(deprecated)]
 pub use legacy::LegacyErrorFormatter;
+pub use merge::{CodeRule, MergeOptions, MergeStrategy};
 
 /// Error wrapper that stores the original error plus structured contexts and an optional code.
 #[must_use]
-#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Deserialize),
+    serde(from = "serde_impl::ComposableErrorRepr<E>")
+)]
+#[derive(Debug, Clone)]
 pub struct ComposableError<E> {
     pub(crate) core_error: E,
     pub(crate) context: ErrorVec<ErrorContext>,
     pub(crate) error_code: Option<u32>,
+    pub(crate) extras: Option<Box<Extras>>,
 }
 
 impl<E> ComposableError<E> {
     /// Creates a composable error without context or code.
+    ///
+    /// With the `caller-location` feature, the caller is recorded as the error's
+    /// [`location`](Self::location); see [`caller_location`].
     #[inline(always)]
+    #[cfg_attr(feature = "caller-location", track_caller)]
     pub fn new(error: E) -> Self {
-        Self { core_error: error, context: ErrorVec::new(), error_code: None }
+        Self::new_at(error, core::panic::Location::caller())
+    }
+
+    /// Creates a composable error whose location, if captured, is `caller`.
+    #[inline(always)]
+    pub(crate) fn new_at(error: E, caller: &'static core::panic::Location<'static>) -> Self {
+        let mut error = Self::from_core(error);
+        error.capture_location(caller);
+        error
+    }
+
+    /// Creates a composable error without capturing a location, for internal
+    /// call sites that are not meaningful to users.
+    #[inline(always)]
+    pub(crate) fn from_core(error: E) -> Self {
+        Self { core_error: error, context: ErrorVec::new(), error_code: None, extras: None }
+    }
+
+    /// Records `caller` as the location if capture is enabled and none is set yet.
+    #[inline]
+    pub(crate) fn capture_location(&mut self, caller: &'static core::panic::Location<'static>) {
+        if self.location().is_none() {
+            if let Some(location) = caller_location::capture(caller) {
+                self.extras_mut().location = Some(location);
+            }
+        }
     }
 
     /// Creates a composable error with a pre-set error code.
+    ///
+    /// Accepts a plain `u32` or any other [`ErrorCode`]; see [`set_code`](Self::set_code).
     #[inline(always)]
-    pub fn with_code(error: E, code: u32) -> Self {
-        Self { core_error: error, context: ErrorVec::new(), error_code: Some(code) }
+    #[cfg_attr(feature = "caller-location", track_caller)]
+    pub fn with_code<C: ErrorCode>(error: E, code: C) -> Self {
+        Self::new(error).set_code(code)
     }
 
     /// Adds a single context entry produced by `IntoErrorContext`.
@@ -50,7 +106,7 @@ impl<E> ComposableError<E> {
     where
         Ctx: IntoErrorContext,
     {
-        self.context.push(ctx.into_error_context());
+        self.push_context(ctx.into_error_context());
         self
     }
 
@@ -60,10 +116,158 @@ impl<E> ComposableError<E> {
     where
         I: IntoIterator<Item = ErrorContext>,
     {
-        self.context.extend(contexts);
+        match (self.context_limit(), self.compaction()) {
+            (None, None) => {
+                let len = self.context.len();
+                self.context.extend(contexts);
+                if let Some(extras) = self.extras.as_deref_mut() {
+                    extras.sites.pad(self.context.len() - len);
+                }
+            },
+            _ => contexts.into_iter().for_each(|ctx| self.push_context(ctx)),
+        }
+        self
+    }
+
+    /// Caps the number of contexts kept on this error.
+    ///
+    /// Contexts beyond the limit are dropped right away and whenever more are
+    /// added, according to the limit's [`OverflowPolicy`](crate::types::OverflowPolicy).
+    /// See [`context_limit`] for details.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use error_rail::ComposableError;
+    /// use error_rail::types::ContextLimit;
+    ///
+    /// let err = ComposableError::new("timeout")
+    ///     .with_context("a")
+    ///     .with_context("b")
+    ///     .with_context("c")
+    ///     .with_context_limit(ContextLimit::keep_last(1))
+    ///     .with_context("d");
+    ///
+    /// assert_eq!(err.dropped_contexts(), 3);
+    /// assert_eq!(err.error_chain(), "d -> ... 3 more contexts elided ... -> timeout");
+    /// ```
+    #[inline]
+    pub fn with_context_limit(mut self, limit: ContextLimit) -> Self {
+        self.extras_mut().context_limit = Some(limit);
+        self.enforce_context_limit();
+        self
+    }
+
+    /// Returns the context limit of this error, if one was set.
+    #[inline]
+    pub fn context_limit(&self) -> Option<ContextLimit> {
+        self.extras
+            .as_deref()
+            .and_then(|extras| extras.context_limit)
+    }
+
+    /// Returns how many contexts were dropped because of the context limit.
+    #[inline]
+    pub fn dropped_contexts(&self) -> usize {
+        self.extras
+            .as_deref()
+            .map_or(0, |extras| extras.dropped_contexts)
+    }
+
+    /// Folds runs of repeated contexts into one entry, now and whenever more are added.
+    ///
+    /// Folding happens before the context limit is applied, so repeats take
+    /// up a single slot. See [`compaction`](crate::types::compaction) for details.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use error_rail::ComposableError;
+    /// use error_rail::types::ContextCompaction;
+    ///
+    /// let err = ComposableError::new("timeout")
+    ///     .with_context("polling")
+    ///     .with_context("polling")
+    ///     .with_compaction(ContextCompaction::new())
+    ///     .with_context("polling");
+    ///
+    /// assert_eq!(err.context().len(), 1);
+    /// assert_eq!(err.error_chain(), "polling (x3) -> timeout");
+    /// ```
+    #[inline]
+    pub fn with_compaction(mut self, compaction: ContextCompaction) -> Self {
+        let extras = self.extras.get_or_insert_with(Default::default);
+        context_limit::compact_bounded(
+            (&mut self.context, &mut extras.sites),
+            &compaction,
+            extras.context_limit,
+            extras.dropped_contexts,
+        );
+        extras.compaction = Some(compaction);
         self
     }
 
+    /// Returns the context compaction of this error, if one was set.
+    #[inline]
+    pub fn compaction(&self) -> Option<&ContextCompaction> {
+        self.extras
+            .as_deref()
+            .and_then(|extras| extras.compaction.as_ref())
+    }
+
+    /// Pushes a context, folding it into the previous one when compaction is
+    /// set and dropping contexts beyond the context limit.
+    #[inline]
+    pub(crate) fn push_context(&mut self, ctx: ErrorContext) {
+        self.push_context_at(ctx, None);
+    }
+
+    /// Pushes a context attached at `caller`, recording the site if capture is enabled.
+    #[inline]
+    pub(crate) fn push_context_from(
+        &mut self,
+        ctx: ErrorContext,
+        caller: &'static core::panic::Location<'static>,
+    ) {
+        self.push_context_at(ctx, caller_location::capture(caller));
+    }
+
+    /// Pushes a context like [`push_context`](Self::push_context), recording
+    /// `site` as where it was attached.
+    pub(crate) fn push_context_at(&mut self, ctx: ErrorContext, site: Option<Location>) {
+        if site.is_some() {
+            self.extras_mut();
+        }
+        match self.extras.as_deref_mut() {
+            Some(extras) => {
+                extras.dropped_contexts += context_limit::push_bounded(
+                    (&mut self.context, &mut extras.sites),
+                    (ctx, site),
+                    extras.context_limit,
+                    extras.compaction.as_ref(),
+                    extras.dropped_contexts,
+                );
+            },
+            None => self.context.push(ctx),
+        }
+    }
+
+    #[inline]
+    pub(crate) fn enforce_context_limit(&mut self) {
+        if let Some(extras) = self.extras.as_deref_mut() {
+            if let Some(limit) = extras.context_limit {
+                extras.sites.truncate(limit);
+                extras.dropped_contexts += limit.truncate(&mut self.context);
+            }
+        }
+    }
+
+    /// Returns where dropped contexts were removed, in push order, and how many.
+    #[inline]
+    pub(crate) fn elision(&self) -> Option<(usize, usize)> {
+        context_limit::elision(self.context_limit(), self.dropped_contexts(), self.context.len())
+    }
+
     /// Returns a reference to the underlying error.
     #[inline(always)]
     pub const fn core_error(&self) -> &E {
@@ -90,25 +294,268 @@ impl<E> ComposableError<E> {
         self.core_error
     }
 
+    /// Returns where each context was attached, most recent first, in the same
+    /// order as [`context_iter`](Self::context_iter).
+    ///
+    /// Sites are recorded by [`ResultExt::ctx`](crate::ResultExt::ctx),
+    /// [`BoxedResultExt::ctx_boxed`](crate::BoxedResultExt::ctx_boxed),
+    /// [`ErrorPipeline::with_context`](crate::ErrorPipeline::with_context) and
+    /// the related entry points; see [`caller_location`]. Contexts added without
+    /// a captured site yield `None`.
+    pub fn context_sites(&self) -> impl Iterator<Item = Option<&Location>> + '_ {
+        let sites = self.sites();
+        (0..self.context.len())
+            .rev()
+            .map(move |i| sites.get(i).and_then(Option::as_ref))
+    }
+
+    /// Returns the sites of the contexts in push order, or an empty slice when
+    /// none were recorded.
+    #[inline]
+    pub(crate) fn sites(&self) -> &[Option<Location>] {
+        self.extras
+            .as_deref()
+            .map_or(&[], |extras| extras.sites.of(self.context.len()))
+    }
+
     /// Returns an iterator in LIFO order (most recent first) that borrows the contexts.
     #[inline(always)]
     pub fn context_iter(&self) -> core::iter::Rev<core::slice::Iter<'_, ErrorContext>> {
         self.context.iter().rev()
     }
 
-    /// Returns the optional error code.
+    /// Reads a typed metadata value from this error's contexts, most recent first.
+    ///
+    /// Only the error's own contexts are searched; use [`causes`](Self::causes)
+    /// to look into lower layers.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use error_rail::types::ContextKey;
+    /// use error_rail::{ComposableError, ErrorContext};
+    ///
+    /// const ATTEMPT: ContextKey<u32> = ContextKey::new("attempt");
+    ///
+    /// let err = ComposableError::new("timeout")
+    ///     .with_context(ErrorContext::builder().set(ATTEMPT, 1).build())
+    ///     .with_context(ErrorContext::builder().set(ATTEMPT, 2).build());
+    ///
+    /// assert_eq!(err.metadata_value(ATTEMPT), Some(2));
+    /// ```
+    #[inline]
+    pub fn metadata_value<T: FromMetadata>(&self, key: ContextKey<T>) -> Option<T> {
+        self.context_iter().find_map(|ctx| ctx.metadata_value(key))
+    }
+
+    /// Returns `true` if any of this error's contexts carries `tag`.
+    ///
+    /// Like the other query methods, this only looks at the error's own
+    /// contexts, never at its causes, and does not allocate.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use error_rail::{ComposableError, ErrorContext};
+    ///
+    /// let err = ComposableError::new("timeout").with_context(ErrorContext::tag("db"));
+    ///
+    /// assert!(err.has_tag("db"));
+    /// assert!(!err.has_tag("http"));
+    /// ```
+    #[inline]
+    pub fn has_tag(&self, tag: &str) -> bool {
+        context_query::has_tag(self.context_iter(), tag)
+    }
+
+    /// Returns the most recent value stored under metadata `key` as text.
+    ///
+    /// Returns `None` if that value is not text, such as a number stored with
+    /// `ErrorContext::metadata("status", 503)`, even when an older context
+    /// holds a text value for the same key; read other types with
+    /// [`metadata_any`](Self::metadata_any) or
+    /// [`metadata_value`](Self::metadata_value). Secret values are returned
+    /// unredacted.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use error_rail::{ComposableError, ErrorContext};
+    ///
+    /// let err = ComposableError::new("forbidden")
+    ///     .with_context(ErrorContext::metadata("user_id", "u-1"))
+    ///     .with_context(ErrorContext::metadata("user_id", "u-2"));
+    ///
+    /// assert_eq!(err.metadata("user_id"), Some("u-2"));
+    ///
+    /// let ids: Vec<_> = err.metadata_all("user_id").map(|id| id.to_string()).collect();
+    /// assert_eq!(ids, ["u-2", "u-1"]);
+    /// ```
+    #[inline]
+    pub fn metadata(&self, key: &str) -> Option<&str> {
+        context_query::metadata(self.context_iter(), key)
+    }
+
+    /// Returns the most recent value stored under metadata `key`, whatever its type.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use error_rail::types::MetadataValue;
+    /// use error_rail::{ComposableError, ErrorContext};
+    ///
+    /// let err = ComposableError::new("unavailable")
+    ///     .with_context(ErrorContext::metadata("status", 503));
+    ///
+    /// assert_eq!(err.metadata("status"), None);
+    /// assert_eq!(err.metadata_any("status"), Some(&MetadataValue::I64(503)));
+    /// ```
+    #[inline]
+    pub fn metadata_any(&self, key: &str) -> Option<&MetadataValue> {
+        context_query::metadata_any(self.context_iter(), key)
+    }
+
+    /// Iterates over every value stored under metadata `key`, most recent first.
+    #[inline]
+    pub fn metadata_all<'a>(
+        &'a self,
+        key: &'a str,
+    ) -> impl Iterator<Item = &'a MetadataValue> + 'a {
+        context_query::metadata_all(self.context_iter(), key)
+    }
+
+    /// Iterates over the tags of this error's contexts, most recent first.
+    #[inline]
+    pub fn tags(&self) -> impl Iterator<Item = &str> + '_ {
+        context_query::tags(self.context_iter())
+    }
+
+    /// Iterates over the source locations attached as contexts, most recent first.
+    ///
+    /// The location where the error was created is returned by
+    /// [`location`](Self::location) instead.
+    #[inline]
+    pub fn locations(&self) -> impl Iterator<Item = &Location> + '_ {
+        context_query::locations(self.context_iter())
+    }
+
+    /// Iterates over the plain messages of this error's contexts, most recent first.
+    ///
+    /// Contexts without a stored message, such as a bare tag or a custom
+    /// payload, are skipped, and
+    /// messages are returned as stored, without tags, metadata or redaction.
+    #[inline]
+    pub fn messages(&self) -> impl Iterator<Item = &str> + '_ {
+        context_query::messages(self.context_iter())
+    }
+
+    /// Returns the most recent context matching `predicate`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use error_rail::types::Severity;
+    /// use error_rail::{ComposableError, ErrorContext};
+    ///
+    /// let err = ComposableError::new("timeout")
+    ///     .with_context(ErrorContext::builder().tag("db").severity(Severity::Critical).build())
+    ///     .with_context("retrying");
+    ///
+    /// let critical = err.find_context(|ctx| ctx.severity() == Some(Severity::Critical));
+    /// assert!(critical.is_some());
+    /// ```
+    #[inline]
+    pub fn find_context<P>(&self, mut predicate: P) -> Option<&ErrorContext>
+    where
+        P: FnMut(&ErrorContext) -> bool,
+    {
+        self.context_iter().find(|ctx| predicate(ctx))
+    }
+
+    /// Returns the numeric error code, if any.
+    ///
+    /// For non-numeric codes this is their [`ErrorCode::number`] part; use
+    /// [`code`](Self::code) to access the full code.
     #[inline(always)]
     pub const fn error_code(&self) -> Option<u32> {
         self.error_code
     }
 
+    /// Returns the error code set on this error, whatever its type.
+    #[inline]
+    pub fn code(&self) -> Option<&dyn ErrorCode> {
+        match self
+            .extras
+            .as_deref()
+            .and_then(|extras| extras.code.as_ref())
+        {
+            Some(slot) => Some(slot.get()),
+            None => self.error_code.as_ref().map(|code| code as &dyn ErrorCode),
+        }
+    }
+
+    /// Returns the error code if it is of type `C`.
+    #[inline]
+    pub fn code_as<C: ErrorCode>(&self) -> Option<&C> {
+        match self
+            .extras
+            .as_deref()
+            .and_then(|extras| extras.code.as_ref())
+        {
+            Some(slot) => slot.downcast_ref::<C>(),
+            None => (self.error_code.as_ref()? as &dyn core::any::Any).downcast_ref::<C>(),
+        }
+    }
+
     /// Sets (or overrides) the error code.
+    ///
+    /// Plain `u32` codes are stored inline as before. Any other [`ErrorCode`]
+    /// is kept as-is for formatting, serde and fingerprinting, and its
+    /// [`number`](ErrorCode::number) part is returned by [`error_code`](Self::error_code).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use error_rail::ComposableError;
+    ///
+    /// let numeric = ComposableError::new("not found").set_code(404);
+    /// assert_eq!(numeric.error_code(), Some(404));
+    ///
+    /// let named = ComposableError::new("pool exhausted").set_code("DB-POOL-EXHAUSTED");
+    /// assert_eq!(named.error_code(), None);
+    /// assert_eq!(named.to_string(), "pool exhausted (code: DB-POOL-EXHAUSTED)");
+    /// ```
     #[inline(always)]
-    pub fn set_code(mut self, code: u32) -> Self {
-        self.error_code = Some(code);
+    pub fn set_code<C: ErrorCode>(mut self, code: C) -> Self {
+        self.set_code_inplace(code);
         self
     }
 
+    #[inline]
+    pub(crate) fn set_code_inplace<C: ErrorCode>(&mut self, code: C) {
+        self.error_code = code.number();
+        self.set_code_slot(CodeSlot::new(code));
+    }
+
+    #[inline]
+    pub(crate) fn set_code_slot(&mut self, slot: Option<CodeSlot>) {
+        match slot {
+            Some(slot) => self.extras_mut().code = Some(slot),
+            None => {
+                if let Some(extras) = self.extras.as_deref_mut() {
+                    extras.code = None;
+                }
+            },
+        }
+    }
+
+    #[inline]
+    pub(crate) fn code_slot(&self) -> Option<&CodeSlot> {
+        self.extras
+            .as_deref()
+            .and_then(|extras| extras.code.as_ref())
+    }
+
     /// Adds context to the error in-place (modifying &mut self).
     /// This is useful for modifying boxed errors without reallocating.
     #[inline]
@@ -116,7 +563,7 @@ impl<E> ComposableError<E> {
     where
         Ctx: IntoErrorContext,
     {
-        self.context.push(ctx.into_error_context());
+        self.push_context(ctx.into_error_context());
     }
 
     /// Maps the core error type while preserving context/code.
@@ -129,7 +576,601 @@ impl<E> ComposableError<E> {
             core_error: f(self.core_error),
             context: self.context,
             error_code: self.error_code,
+            extras: self.extras,
+        }
+    }
+
+    /// Sets the severity of this error.
+    ///
+    /// The severity is shown as a prefix by [`ErrorFormatBuilder`](crate::types::error_formatter::ErrorFormatBuilder)
+    /// and kept when the error becomes an [`ErrorCause`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use error_rail::ComposableError;
+    /// use error_rail::types::Severity;
+    ///
+    /// let err = ComposableError::new("cache miss").set_severity(Severity::Info);
+    /// assert_eq!(err.severity(), Some(Severity::Info));
+    /// assert_eq!(err.to_string(), "info: cache miss");
+    /// ```
+    #[inline]
+    pub fn set_severity(mut self, severity: Severity) -> Self {
+        self.extras_mut().severity = Some(severity);
+        self
+    }
+
+    /// Returns the severity set on this error, if any.
+    #[inline]
+    pub fn severity(&self) -> Option<Severity> {
+        self.extras.as_deref().and_then(|extras| extras.severity)
+    }
+
+    /// Returns the highest severity of this error, its contexts and its whole cause tree.
+    #[inline]
+    pub fn max_severity(&self) -> Option<Severity> {
+        let own = self
+            .context
+            .iter()
+            .filter_map(ErrorContext::severity)
+            .chain(self.severity());
+        let causes = self.causes().flat_map(|cause| {
+            cause
+                .context
+                .iter()
+                .filter_map(ErrorContext::severity)
+                .chain(cause.severity)
+        });
+        own.chain(causes).max()
+    }
+
+    /// Records the time at which this error occurred.
+    #[inline]
+    pub fn with_timestamp(mut self, timestamp: Timestamp) -> Self {
+        self.extras_mut().timestamp = Some(timestamp);
+        self
+    }
+
+    /// Records the current time of `clock` as the time this error occurred.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use error_rail::ComposableError;
+    /// use error_rail::types::{ManualClock, Timestamp};
+    ///
+    /// let clock = ManualClock::new(Timestamp::from_unix_millis(1_700_000_000_000));
+    /// let err = ComposableError::new("disk full").stamp_with(&clock);
+    ///
+    /// assert_eq!(err.timestamp(), Some(Timestamp::from_unix_millis(1_700_000_000_000)));
+    /// assert_eq!(err.to_string(), "disk full");
+    /// ```
+    #[inline]
+    pub fn stamp_with<C: Clock + ?Sized>(self, clock: &C) -> Self {
+        self.with_timestamp(clock.now())
+    }
+
+    /// Records the current system time as the time this error occurred.
+    #[cfg(feature = "std")]
+    #[inline]
+    pub fn stamp(self) -> Self {
+        self.stamp_with(&crate::types::SystemClock)
+    }
+
+    /// Returns the time at which this error occurred, if it was recorded.
+    #[inline]
+    pub fn timestamp(&self) -> Option<Timestamp> {
+        self.extras.as_deref().and_then(|extras| extras.timestamp)
+    }
+
+    /// Sets the source location where this error entered the rail.
+    ///
+    /// Overrides a location captured automatically with the `caller-location` feature.
+    #[inline]
+    pub fn with_location(mut self, location: Location) -> Self {
+        self.extras_mut().location = Some(location);
+        self
+    }
+
+    /// Returns the source location where this error entered the rail, if it was recorded.
+    ///
+    /// See [`caller_location`] for automatic capture.
+    #[inline]
+    pub fn location(&self) -> Option<&Location> {
+        self.extras
+            .as_deref()
+            .and_then(|extras| extras.location.as_ref())
+    }
+
+    /// Attaches a structured backtrace, replacing any previous one.
+    ///
+    /// Empty backtraces, e.g. from [`ErrorBacktrace::capture`] while capturing is
+    /// disabled, are ignored. The backtrace is only rendered in verbose output; see
+    /// [`ErrorFormatBuilder::verbose`](crate::types::error_formatter::ErrorFormatBuilder::verbose).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use error_rail::types::{BacktraceFilter, ErrorBacktrace};
+    /// use error_rail::ComposableError;
+    ///
+    /// let trace = ErrorBacktrace::parse("   0: app::load\n             at src/app.rs:3:5", &BacktraceFilter::new());
+    /// let err = ComposableError::new("load failed").with_backtrace(trace);
+    ///
+    /// assert_eq!(err.backtrace().unwrap().frames()[0].function, "app::load");
+    /// assert_eq!(err.to_string(), "load failed");
+    /// assert!(err.fmt().verbose().to_string().contains("app::load at src/app.rs:3:5"));
+    /// ```
+    #[inline]
+    pub fn with_backtrace(mut self, backtrace: ErrorBacktrace) -> Self {
+        if !backtrace.is_empty() {
+            self.extras_mut().backtrace = Some(backtrace);
+        }
+        self
+    }
+
+    /// Returns the structured backtrace attached to this error, if any.
+    #[inline]
+    pub fn backtrace(&self) -> Option<&ErrorBacktrace> {
+        self.extras
+            .as_deref()
+            .and_then(|extras| extras.backtrace.as_ref())
+    }
+
+    /// Adds a context entry stamped with the current time of `clock`.
+    ///
+    /// Useful to build a timeline of retries or other layers added over time;
+    /// see [`ErrorContext::with_timestamp`].
+    #[inline]
+    pub fn with_context_at<Ctx, C>(mut self, ctx: Ctx, clock: &C) -> Self
+    where
+        Ctx: IntoErrorContext,
+        C: Clock + ?Sized,
+    {
+        self.push_context(ctx.into_error_context().with_timestamp(clock.now()));
+        self
+    }
+
+    /// Sets the trace, span and correlation IDs of this error, replacing any set before.
+    #[inline]
+    pub fn with_trace_ids(mut self, ids: TraceIds) -> Self {
+        self.extras_mut().trace = (!ids.is_empty()).then_some(ids);
+        self
+    }
+
+    /// Sets the distributed trace ID of this error.
+    #[inline]
+    pub fn with_trace_id<S: Into<Cow<'static, str>>>(mut self, id: S) -> Self {
+        self.trace_mut().trace_id = Some(id.into());
+        self
+    }
+
+    /// Sets the ID of the span in which this error occurred.
+    #[inline]
+    pub fn with_span_id<S: Into<Cow<'static, str>>>(mut self, id: S) -> Self {
+        self.trace_mut().span_id = Some(id.into());
+        self
+    }
+
+    /// Sets the correlation (request) ID of this error.
+    #[inline]
+    pub fn with_correlation_id<S: Into<Cow<'static, str>>>(mut self, id: S) -> Self {
+        self.trace_mut().correlation_id = Some(id.into());
+        self
+    }
+
+    /// Sets the trace and span IDs from a W3C `traceparent` header.
+    ///
+    /// The correlation ID is kept. Malformed headers are ignored; use
+    /// [`TraceIds::from_traceparent`] to detect them.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use error_rail::ComposableError;
+    ///
+    /// let err = ComposableError::new("timeout")
+    ///     .with_traceparent("00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01");
+    /// assert_eq!(err.span_id(), Some("00f067aa0ba902b7"));
+    ///
+    /// let ignored = ComposableError::new("timeout").with_traceparent("garbage");
+    /// assert_eq!(ignored.trace_ids(), None);
+    /// ```
+    #[inline]
+    pub fn with_traceparent(mut self, header: &str) -> Self {
+        if let Some(parsed) = TraceIds::from_traceparent(header) {
+            let ids = self.trace_mut();
+            ids.trace_id = parsed.trace_id;
+            ids.span_id = parsed.span_id;
+        }
+        self
+    }
+
+    /// Fills unset IDs from the active `tracing` span; see [`TraceIds::current`].
+    #[cfg(feature = "tracing")]
+    #[inline]
+    pub fn with_current_span_ids(self) -> Self {
+        self.with_span_ids(&tracing::Span::current())
+    }
+
+    #[cfg(feature = "tracing")]
+    #[inline]
+    pub(crate) fn with_span_ids(mut self, span: &tracing::Span) -> Self {
+        if let Some(ids) = TraceIds::from_span(span) {
+            self.trace_mut().fill_from(ids);
+        }
+        self
+    }
+
+    /// Returns the trace, span and correlation IDs of this error, if any is set.
+    #[inline]
+    pub fn trace_ids(&self) -> Option<&TraceIds> {
+        self.extras
+            .as_deref()
+            .and_then(|extras| extras.trace.as_ref())
+    }
+
+    /// Returns the distributed trace ID, if set.
+    #[inline]
+    pub fn trace_id(&self) -> Option<&str> {
+        self.trace_ids()?.trace_id.as_deref()
+    }
+
+    /// Returns the span ID, if set.
+    #[inline]
+    pub fn span_id(&self) -> Option<&str> {
+        self.trace_ids()?.span_id.as_deref()
+    }
+
+    /// Returns the correlation ID, if set.
+    #[inline]
+    pub fn correlation_id(&self) -> Option<&str> {
+        self.trace_ids()?.correlation_id.as_deref()
+    }
+
+    #[inline]
+    fn trace_mut(&mut self) -> &mut TraceIds {
+        self.extras_mut().trace.get_or_insert_with(Default::default)
+    }
+
+    /// Creates an error that aggregates several underlying failures as children.
+    ///
+    /// Each child keeps its own contexts, code and nested causes, forming a
+    /// tree that is rendered, fingerprinted and serialized as a whole.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use error_rail::ComposableError;
+    ///
+    /// let failures = ["eu-west", "us-east"]
+    ///     .into_iter()
+    ///     .map(|region| ComposableError::new("unreachable").with_context(region));
+    ///
+    /// let err = ComposableError::aggregate("all backends failed", failures);
+    /// assert_eq!(err.children().len(), 2);
+    /// assert_eq!(err.children()[1].context_iter().next().unwrap().message(), "us-east");
+    /// ```
+    #[inline]
+    #[cfg_attr(feature = "caller-location", track_caller)]
+    pub fn aggregate<I>(error: E, causes: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: Into<ErrorCause>,
+    {
+        Self::new(error).with_causes(causes)
+    }
+
+    /// Attaches a lower-level error as a cause of this error.
+    ///
+    /// The cause keeps its own contexts, error code and nested causes, so
+    /// wrapping another [`ComposableError`] does not flatten it. Attaching
+    /// more than one cause turns the chain into a tree.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use error_rail::{ComposableError, ErrorContext};
+    ///
+    /// let io = ComposableError::new("connection reset")
+    ///     .with_context(ErrorContext::tag("net"))
+    ///     .set_code(104);
+    ///
+    /// let err = ComposableError::new("sync failed")
+    ///     .with_context("syncing inventory")
+    ///     .with_cause(io);
+    ///
+    /// assert_eq!(
+    ///     err.error_chain(),
+    ///     "syncing inventory -> sync failed -> caused by: [net] -> connection reset (code: 104)"
+    /// );
+    /// ```
+    #[inline]
+    pub fn with_cause<C>(mut self, cause: C) -> Self
+    where
+        C: Into<ErrorCause>,
+    {
+        self.extras_mut().causes.push(cause.into());
+        self
+    }
+
+    /// Attaches several causes at once.
+    #[inline]
+    pub fn with_causes<I>(mut self, causes: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: Into<ErrorCause>,
+    {
+        let mut causes = causes.into_iter().map(Into::into).peekable();
+        if causes.peek().is_some() {
+            self.extras_mut().causes.extend(causes);
         }
+        self
+    }
+
+    /// Captures the core error's `source()` chain as nested cause layers.
+    ///
+    /// Every error reachable through [`core::error::Error::source`] becomes a
+    /// distinct "caused by" layer, so it appears in [`error_chain`](Self::error_chain),
+    /// serde output and the fingerprint. The chain is appended as one more child
+    /// and is a snapshot taken at call time; nothing is captured when the core
+    /// error has no source.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use error_rail::ComposableError;
+    /// use std::{error::Error, fmt, io};
+    ///
+    /// #[derive(Debug)]
+    /// struct ConfigError(io::Error);
+    ///
+    /// impl fmt::Display for ConfigError {
+    ///     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    ///         f.write_str("failed to read config")
+    ///     }
+    /// }
+    ///
+    /// impl Error for ConfigError {
+    ///     fn source(&self) -> Option<&(dyn Error + 'static)> {
+    ///         Some(&self.0)
+    ///     }
+    /// }
+    ///
+    /// let err = ComposableError::new(ConfigError(io::Error::other("disk full")))
+    ///     .with_source_chain();
+    ///
+    /// assert_eq!(err.error_chain(), "failed to read config -> caused by: disk full");
+    /// ```
+    pub fn with_source_chain(mut self) -> Self
+    where
+        E: core::error::Error,
+    {
+        if let Some(chain) = ErrorCause::from_sources(self.core_error.source()) {
+            self.extras_mut().causes.push(chain);
+        }
+        self
+    }
+
+    /// Returns the first direct cause of this error that is not suppressed, if any.
+    #[inline]
+    pub fn cause(&self) -> Option<&ErrorCause> {
+        self.children().iter().find(|cause| !cause.is_suppressed())
+    }
+
+    /// Returns the errors suppressed while handling this one; see [`merge`](Self::merge).
+    ///
+    /// They are direct children, so they are also part of [`children`](Self::children)
+    /// and [`causes`](Self::causes), but never of [`cause`](Self::cause) or `source()`.
+    #[inline]
+    pub fn suppressed(&self) -> impl Iterator<Item = &ErrorCause> + '_ {
+        self.children().iter().filter(|cause| cause.is_suppressed())
+    }
+
+    /// Returns the direct causes (children) of this error.
+    #[inline]
+    pub fn children(&self) -> &[ErrorCause] {
+        self.extras.as_deref().map_or(&[], |extras| &extras.causes)
+    }
+
+    /// Returns a depth-first iterator over every cause below this error.
+    ///
+    /// For a simple chain this yields the nearest layer first.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use error_rail::ComposableError;
+    ///
+    /// let root = ComposableError::new("timeout");
+    /// let mid = ComposableError::new("query failed").with_cause(root);
+    /// let top = ComposableError::new("request failed").with_cause(mid);
+    ///
+    /// let messages: Vec<_> = top.causes().map(|c| c.message()).collect();
+    /// assert_eq!(messages, ["query failed", "timeout"]);
+    /// ```
+    #[inline]
+    pub fn causes(&self) -> Causes<'_> {
+        Causes::new(self.children())
+    }
+
+    /// Attaches a typed, non-string payload to the error.
+    ///
+    /// Attachments are read back by type with [`request_ref`](Self::request_ref)
+    /// or [`attachments`](Self::attachments). They are never rendered, compared
+    /// or serialized; use [`attach_display`](Self::attach_display) or
+    /// [`attach_debug`](Self::attach_debug) for payloads that should be printable.
+    /// Attachments survive `map_core`, context additions and conversion into
+    /// [`RailError`](crate::types::RailError), but not conversion into an [`ErrorCause`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use error_rail::ComposableError;
+    /// use std::time::Duration;
+    ///
+    /// let err = ComposableError::new("request timed out").attach(Duration::from_millis(250));
+    ///
+    /// assert_eq!(err.request_ref::<Duration>(), Some(&Duration::from_millis(250)));
+    /// assert_eq!(err.to_string(), "request timed out");
+    /// ```
+    #[inline]
+    pub fn attach<T>(self, value: T) -> Self
+    where
+        T: core::any::Any + Send + Sync,
+    {
+        self.with_attachment(Attachment::new(value))
+    }
+
+    /// Attaches a payload that is rendered with its `Display` implementation
+    /// when formatting with [`show_attachments`](crate::types::error_formatter::ErrorFormatBuilder::show_attachments).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use error_rail::ComposableError;
+    ///
+    /// let err = ComposableError::new("upload failed").attach_display(413u16);
+    ///
+    /// assert_eq!(err.to_string(), "upload failed");
+    /// assert_eq!(
+    ///     err.fmt().show_attachments(true).to_string(),
+    ///     "upload failed -> attachment: 413"
+    /// );
+    /// ```
+    #[inline]
+    pub fn attach_display<T>(self, value: T) -> Self
+    where
+        T: core::fmt::Display + core::any::Any + Send + Sync,
+    {
+        self.with_attachment(Attachment::display(value))
+    }
+
+    /// Attaches a payload that is rendered with its `Debug` implementation
+    /// when formatting with [`show_attachments`](crate::types::error_formatter::ErrorFormatBuilder::show_attachments).
+    #[inline]
+    pub fn attach_debug<T>(self, value: T) -> Self
+    where
+        T: core::fmt::Debug + core::any::Any + Send + Sync,
+    {
+        self.with_attachment(Attachment::debug(value))
+    }
+
+    /// Returns the most recently attached value of type `T`, if any.
+    #[inline]
+    pub fn request_ref<T: core::any::Any>(&self) -> Option<&T> {
+        self.attachments::<T>().next()
+    }
+
+    /// Returns every attached value of type `T`, most recent first.
+    #[inline]
+    pub fn attachments<T: core::any::Any>(&self) -> impl Iterator<Item = &T> + '_ {
+        self.attachment_list()
+            .iter()
+            .rev()
+            .filter_map(Attachment::downcast_ref::<T>)
+    }
+
+    #[inline]
+    pub(crate) fn attachment_list(&self) -> &[Attachment] {
+        self.extras
+            .as_deref()
+            .map_or(&[], |extras| &extras.attachments)
+    }
+
+    #[inline]
+    pub(crate) fn with_attachments(mut self, attachments: Vec<Attachment>) -> Self {
+        if !attachments.is_empty() {
+            self.extras_mut().attachments.extend(attachments);
+        }
+        self
+    }
+
+    #[inline]
+    pub(crate) fn with_attachment(mut self, attachment: Attachment) -> Self {
+        self.extras_mut().attachments.push(attachment);
+        self
+    }
+
+    /// Sets a message that is safe to show to end users, replacing any previous one.
+    ///
+    /// It is only rendered by [`public_view`](Self::public_view); `Display` and
+    /// [`error_chain`](Self::error_chain) keep showing the core error.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use error_rail::ComposableError;
+    ///
+    /// let err = ComposableError::new("deadlock detected in tx 81723")
+    ///     .with_public_message("the payment could not be processed");
+    /// assert_eq!(err.public_message(), Some("the payment could not be processed"));
+    /// assert_eq!(err.to_string(), "deadlock detected in tx 81723");
+    /// ```
+    #[inline]
+    pub fn with_public_message<S: Into<Cow<'static, str>>>(mut self, message: S) -> Self {
+        self.extras_mut().public_message = Some(message.into());
+        self
+    }
+
+    /// Returns the message that is safe to show to end users, if one was set.
+    #[inline]
+    pub fn public_message(&self) -> Option<&str> {
+        self.extras
+            .as_deref()
+            .and_then(|extras| extras.public_message.as_deref())
+    }
+
+    /// Sets a user-facing hint on how to resolve the error, replacing any previous one.
+    #[inline]
+    pub fn with_hint<S: Into<Cow<'static, str>>>(mut self, hint: S) -> Self {
+        self.extras_mut().hint = Some(hint.into());
+        self
+    }
+
+    /// Returns the user-facing hint, if one was set.
+    #[inline]
+    pub fn hint(&self) -> Option<&str> {
+        self.extras
+            .as_deref()
+            .and_then(|extras| extras.hint.as_deref())
+    }
+
+    /// Returns a view that renders only the parts that are safe to show to end users.
+    ///
+    /// See [`visibility`](crate::types::visibility) and [`PublicView`].
+    #[inline]
+    pub fn public_view(&self) -> PublicView<'_, E> {
+        PublicView::new(self)
+    }
+
+    /// Moves the error behind a single pointer; see [`ThinComposableError`].
+    #[inline]
+    pub fn into_thin(self) -> ThinComposableError<E> {
+        ThinComposableError::from(self)
+    }
+
+    /// Moves the error behind an `Arc` so it clones cheaply; see [`SharedComposableError`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use error_rail::ComposableError;
+    ///
+    /// let shared = ComposableError::new("upstream closed").into_shared();
+    /// let copy = shared.clone();
+    /// assert!(copy.ptr_eq(&shared));
+    /// assert_eq!(shared.share_count(), 2);
+    /// ```
+    #[inline]
+    pub fn into_shared(self) -> SharedComposableError<E> {
+        SharedComposableError::from(self)
+    }
+
+    #[inline]
+    pub(crate) fn extras_mut(&mut self) -> &mut Extras {
+        self.extras.get_or_insert_with(Default::default)
     }
 
     /// Returns a builder for customizing the error formatting.
@@ -159,18 +1200,22 @@ impl<E> ComposableError<E> {
         E: core::fmt::Display,
         F: crate::types::error_formatter::ErrorFormatter,
     {
-        use crate::types::alloc_type::Vec;
         use core::fmt::Display;
 
-        let ctx_len = self.context.len();
-        let mut items: Vec<&dyn Display> = Vec::with_capacity(ctx_len + 1);
+        use crate::types::error_formatter::{chain_items, ChainOptions};
 
-        for ctx in self.context.iter().rev() {
-            items.push(ctx);
-        }
-        items.push(&self.core_error);
+        let items = chain_items(self, ChainOptions::PLAIN);
+        formatter.format_chain(items.iter().map(|item| item as &dyn Display))
+    }
 
-        formatter.format_chain(items.iter().copied())
+    /// Returns a privileged view whose `Display` and `Serialize` output shows
+    /// sensitive context parts in clear text.
+    ///
+    /// See [`redaction`](crate::types::redaction) for how parts are marked sensitive.
+    #[must_use]
+    #[inline]
+    pub fn unredacted(&self) -> crate::types::Unredacted<'_, Self> {
+        crate::types::Unredacted(self)
     }
 
     /// Returns the complete error chain as a formatted string.
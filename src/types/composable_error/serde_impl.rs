@@ -0,0 +1,246 @@
+//! Serde support for [`ComposableError`].
+//!
+//! Optional parts stored in [`Extras`] are serialized as flat, top-level fields
+//! that are omitted when empty, so plain errors keep their original shape.
+//! Sensitive context parts are redacted unless serialized through
+//! [`Unredacted`](crate::types::Unredacted).
+
+use super::extras::Extras;
+use super::{ComposableError, ErrorCause};
+use crate::types::alloc_type::{Box, Cow, Vec};
+use crate::types::caller_location::ContextSites;
+use crate::types::error_code::CodeSlot;
+use crate::types::error_context::ContextsSer;
+use crate::types::{
+    ContextCompaction, ContextLimit, ErrorBacktrace, ErrorContext, ErrorVec, Location, Severity,
+    Timestamp, TraceIds,
+};
+use serde::{Deserialize, Serialize, Serializer};
+
+#[derive(Serialize)]
+#[serde(rename = "ComposableError")]
+struct ComposableErrorRef<'a, E> {
+    core_error: &'a E,
+    context: ContextsSer<'a>,
+    error_code: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    code: Option<&'a CodeSlot>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    severity: Option<Severity>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    timestamp: Option<Timestamp>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    trace_ids: Option<&'a TraceIds>,
+    #[serde(skip_serializing_if = "CausesSer::is_empty")]
+    causes: CausesSer<'a>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    context_limit: Option<ContextLimit>,
+    #[serde(skip_serializing_if = "is_zero")]
+    dropped_contexts: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    compaction: Option<&'a ContextCompaction>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    location: Option<&'a Location>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    context_sites: Option<&'a ContextSites>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    backtrace: Option<&'a ErrorBacktrace>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    public_message: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    hint: Option<&'a str>,
+}
+
+#[inline]
+fn is_zero(count: &usize) -> bool {
+    *count == 0
+}
+
+impl<E: Serialize> Serialize for ComposableError<E> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.serialize_with(serializer, false)
+    }
+}
+
+impl<E: Serialize> ComposableError<E> {
+    /// Serializes the error, redacting sensitive context parts unless `reveal` is set.
+    pub(crate) fn serialize_with<S: Serializer>(
+        &self,
+        serializer: S,
+        reveal: bool,
+    ) -> Result<S::Ok, S::Error> {
+        ComposableErrorRef {
+            core_error: &self.core_error,
+            context: ContextsSer { contexts: &self.context, reveal },
+            error_code: self.error_code,
+            code: self.code_slot(),
+            severity: self.severity(),
+            timestamp: self.timestamp(),
+            trace_ids: self.trace_ids(),
+            causes: CausesSer { causes: self.children(), reveal },
+            context_limit: self.context_limit(),
+            dropped_contexts: self.dropped_contexts(),
+            compaction: self.compaction(),
+            location: self.location(),
+            context_sites: self
+                .extras
+                .as_deref()
+                .map(|extras| &extras.sites)
+                .filter(|sites| !sites.is_empty()),
+            backtrace: self.backtrace(),
+            public_message: self.public_message(),
+            hint: self.hint(),
+        }
+        .serialize(serializer)
+    }
+}
+
+impl Serialize for ErrorCause {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        CauseSer { cause: self, reveal: false }.serialize(serializer)
+    }
+}
+
+struct CauseSer<'a> {
+    cause: &'a ErrorCause,
+    reveal: bool,
+}
+
+impl Serialize for CauseSer<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        #[derive(Serialize)]
+        #[serde(rename = "ErrorCause")]
+        struct ErrorCauseRef<'a> {
+            message: &'a str,
+            context: ContextsSer<'a>,
+            error_code: Option<u32>,
+            #[serde(skip_serializing_if = "Option::is_none")]
+            code: Option<&'a CodeSlot>,
+            #[serde(skip_serializing_if = "Option::is_none")]
+            severity: Option<Severity>,
+            #[serde(skip_serializing_if = "Option::is_none")]
+            timestamp: Option<Timestamp>,
+            #[serde(skip_serializing_if = "Option::is_none")]
+            trace_ids: Option<&'a TraceIds>,
+            #[serde(skip_serializing_if = "CausesSer::is_empty")]
+            causes: CausesSer<'a>,
+            #[serde(skip_serializing_if = "Option::is_none")]
+            context_limit: Option<ContextLimit>,
+            #[serde(skip_serializing_if = "is_zero")]
+            dropped_contexts: usize,
+            #[serde(skip_serializing_if = "Option::is_none")]
+            location: Option<&'a Location>,
+            #[serde(skip_serializing_if = "Option::is_none")]
+            context_sites: Option<&'a ContextSites>,
+            #[serde(skip_serializing_if = "Option::is_none")]
+            backtrace: Option<&'a ErrorBacktrace>,
+            #[serde(skip_serializing_if = "core::ops::Not::not")]
+            suppressed: bool,
+        }
+
+        let (cause, reveal) = (self.cause, self.reveal);
+        ErrorCauseRef {
+            message: &cause.message,
+            context: ContextsSer { contexts: &cause.context, reveal },
+            error_code: cause.error_code,
+            code: cause.code.as_ref(),
+            severity: cause.severity,
+            timestamp: cause.timestamp,
+            trace_ids: cause.trace_ids.as_ref(),
+            causes: CausesSer { causes: &cause.causes, reveal },
+            context_limit: cause.context_limit,
+            dropped_contexts: cause.dropped_contexts,
+            location: cause.location.as_ref(),
+            context_sites: Some(&cause.context_sites).filter(|sites| !sites.is_empty()),
+            backtrace: cause.backtrace.as_ref(),
+            suppressed: cause.suppressed,
+        }
+        .serialize(serializer)
+    }
+}
+
+struct CausesSer<'a> {
+    causes: &'a [ErrorCause],
+    reveal: bool,
+}
+
+impl CausesSer<'_> {
+    #[inline]
+    fn is_empty(&self) -> bool {
+        self.causes.is_empty()
+    }
+}
+
+impl Serialize for CausesSer<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(
+            self.causes
+                .iter()
+                .map(|cause| CauseSer { cause, reveal: self.reveal }),
+        )
+    }
+}
+
+/// Owned representation used to deserialize a [`ComposableError`].
+#[derive(Deserialize)]
+#[serde(rename = "ComposableError")]
+pub(crate) struct ComposableErrorRepr<E> {
+    core_error: E,
+    #[serde(default)]
+    context: ErrorVec<ErrorContext>,
+    #[serde(default)]
+    error_code: Option<u32>,
+    #[serde(default)]
+    code: Option<CodeSlot>,
+    #[serde(default)]
+    severity: Option<Severity>,
+    #[serde(default)]
+    timestamp: Option<Timestamp>,
+    #[serde(default)]
+    trace_ids: Option<TraceIds>,
+    #[serde(default)]
+    causes: Vec<ErrorCause>,
+    #[serde(default)]
+    context_limit: Option<ContextLimit>,
+    #[serde(default)]
+    dropped_contexts: usize,
+    #[serde(default)]
+    compaction: Option<ContextCompaction>,
+    #[serde(default)]
+    location: Option<Location>,
+    #[serde(default)]
+    context_sites: ContextSites,
+    #[serde(default)]
+    backtrace: Option<ErrorBacktrace>,
+    #[serde(default)]
+    public_message: Option<Cow<'static, str>>,
+    #[serde(default)]
+    hint: Option<Cow<'static, str>>,
+}
+
+impl<E> From<ComposableErrorRepr<E>> for ComposableError<E> {
+    fn from(repr: ComposableErrorRepr<E>) -> Self {
+        let extras = Extras {
+            causes: repr.causes,
+            severity: repr.severity,
+            code: repr.code,
+            timestamp: repr.timestamp,
+            trace: repr.trace_ids,
+            context_limit: repr.context_limit,
+            dropped_contexts: repr.dropped_contexts,
+            compaction: repr.compaction,
+            location: repr.location,
+            sites: repr.context_sites,
+            backtrace: repr.backtrace,
+            public_message: repr.public_message,
+            hint: repr.hint,
+            ..Default::default()
+        };
+        Self {
+            core_error: repr.core_error,
+            context: repr.context,
+            error_code: repr.error_code,
+            extras: (!extras.is_empty()).then(|| Box::new(extras)),
+        }
+    }
+}
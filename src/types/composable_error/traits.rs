@@ -10,17 +10,42 @@ impl<E: Display> Display for ComposableError<E> {
     }
 }
 
+/// Compares the core error, contexts, code, severity, timestamp, trace IDs and
+/// cause tree; attachments and the location are ignored.
+impl<E: PartialEq> PartialEq for ComposableError<E> {
+    fn eq(&self, other: &Self) -> bool {
+        self.core_error == other.core_error
+            && self.context == other.context
+            && self.error_code == other.error_code
+            && self.code_slot() == other.code_slot()
+            && self.severity() == other.severity()
+            && self.timestamp() == other.timestamp()
+            && self.trace_ids() == other.trace_ids()
+            && self.children() == other.children()
+    }
+}
+
+impl<E: Eq> Eq for ComposableError<E> {}
+
 impl<E> core::error::Error for ComposableError<E>
 where
     E: core::error::Error + Send + Sync + 'static,
 {
+    /// Returns the next layer of the chain: the nearest attached
+    /// [`cause`](ComposableError::cause) if present, otherwise the core error.
+    ///
+    /// Chain walkers that follow `source()` therefore visit every cause layer.
     fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
-        Some(self.core_error())
+        match self.cause() {
+            Some(cause) => Some(cause),
+            None => Some(self.core_error()),
+        }
     }
 }
 
 impl<E> From<E> for ComposableError<E> {
     #[inline]
+    #[cfg_attr(feature = "caller-location", track_caller)]
     fn from(error: E) -> Self {
         Self::new(error)
     }
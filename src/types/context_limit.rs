@@ -0,0 +1,225 @@
+//! Bounded context stacks.
+//!
+//! Long retry loops and recursive processing can attach hundreds of contexts
+//! to a single error. A [`ContextLimit`] caps the number of contexts a
+//! [`ComposableError`](crate::types::ComposableError) keeps and chooses which
+//! ones survive through an [`OverflowPolicy`]. Dropped contexts are counted,
+//! and formatters show the count as a single `"... N more contexts elided ..."`
+//! entry where the contexts were removed.
+//!
+//! A limit is set with [`ComposableError::with_context_limit`](crate::types::ComposableError::with_context_limit),
+//! [`ErrorPipeline::with_context_limit`](crate::types::ErrorPipeline::with_context_limit),
+//! `ContextFuture::with_context_limit` or `ErrorRailLayer::with_context_limit`,
+//! and stays with the error as more contexts are added.
+//!
+//! # Examples
+//!
+//! ```
+//! use error_rail::ComposableError;
+//! use error_rail::types::ContextLimit;
+//!
+//! let mut err = ComposableError::new("timeout").with_context_limit(ContextLimit::keep_ends(2));
+//! for attempt in 1..=5 {
+//!     err = err.with_context(format!("attempt {attempt}"));
+//! }
+//!
+//! assert_eq!(err.dropped_contexts(), 3);
+//! assert_eq!(
+//!     err.error_chain(),
+//!     "attempt 5 -> ... 3 more contexts elided ... -> attempt 1 -> timeout"
+//! );
+//! ```
+
+use crate::types::caller_location::ContextSites;
+use crate::types::{ContextCompaction, ErrorContext, ErrorVec, Location};
+use core::fmt::{Display, Formatter};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Which contexts survive when a context stack exceeds its [`ContextLimit`].
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum OverflowPolicy {
+    /// Keeps the oldest contexts and drops newer ones.
+    KeepFirst,
+    /// Keeps the most recent contexts and drops the oldest ones.
+    KeepLast,
+    /// Keeps the oldest and the most recent contexts and drops the middle.
+    ///
+    /// The most recent half gets the extra slot when the limit is odd.
+    KeepEnds,
+}
+
+/// Maximum number of contexts kept on an error, with the policy applied on overflow.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ContextLimit {
+    max: usize,
+    policy: OverflowPolicy,
+}
+
+impl ContextLimit {
+    /// Creates a limit of `max` contexts with the given overflow policy.
+    #[inline]
+    pub const fn new(max: usize, policy: OverflowPolicy) -> Self {
+        Self { max, policy }
+    }
+
+    /// Keeps the `max` oldest contexts.
+    #[inline]
+    pub const fn keep_first(max: usize) -> Self {
+        Self::new(max, OverflowPolicy::KeepFirst)
+    }
+
+    /// Keeps the `max` most recent contexts.
+    #[inline]
+    pub const fn keep_last(max: usize) -> Self {
+        Self::new(max, OverflowPolicy::KeepLast)
+    }
+
+    /// Keeps `max` contexts split between the oldest and the most recent ones.
+    #[inline]
+    pub const fn keep_ends(max: usize) -> Self {
+        Self::new(max, OverflowPolicy::KeepEnds)
+    }
+
+    /// Returns the maximum number of contexts kept.
+    #[inline]
+    pub const fn max(&self) -> usize {
+        self.max
+    }
+
+    /// Returns the overflow policy.
+    #[inline]
+    pub const fn policy(&self) -> OverflowPolicy {
+        self.policy
+    }
+
+    /// Number of oldest contexts kept by [`OverflowPolicy::KeepEnds`].
+    #[inline]
+    const fn head(&self) -> usize {
+        self.max / 2
+    }
+
+    /// Drops contexts beyond the limit and returns how many were dropped.
+    ///
+    /// `items` is in push order, oldest first.
+    pub(crate) fn truncate<T>(&self, items: &mut ErrorVec<T>) -> usize {
+        let len = items.len();
+        if len <= self.max {
+            return 0;
+        }
+        let excess = len - self.max;
+        match self.policy {
+            OverflowPolicy::KeepFirst => items.truncate(self.max),
+            OverflowPolicy::KeepLast => {
+                items.drain(..excess);
+            },
+            OverflowPolicy::KeepEnds => {
+                let head = self.head();
+                items.drain(head..head + excess);
+            },
+        }
+        excess
+    }
+
+    /// Index, in push order, at which dropped contexts were removed from a stack of `len`.
+    #[inline]
+    pub(crate) fn elided_at(&self, len: usize) -> usize {
+        match self.policy {
+            OverflowPolicy::KeepFirst => len,
+            OverflowPolicy::KeepLast => 0,
+            OverflowPolicy::KeepEnds => self.head().min(len),
+        }
+    }
+}
+
+/// Returns where dropped contexts were removed from a stack of `len`, in push
+/// order, and how many.
+#[inline]
+pub(crate) fn elision(
+    limit: Option<ContextLimit>,
+    dropped: usize,
+    len: usize,
+) -> Option<(usize, usize)> {
+    match (limit, dropped) {
+        (_, 0) | (None, _) => None,
+        (Some(limit), dropped) => Some((limit.elided_at(len), dropped)),
+    }
+}
+
+/// Pushes `ctx` onto `contexts`, folding it into the previous context when
+/// `compaction` is set, then drops contexts beyond `limit`.
+///
+/// `site` is recorded in `sites` as where `ctx` was attached. `dropped` is the
+/// number of contexts dropped so far; returns how many this push dropped.
+pub(crate) fn push_bounded(
+    (contexts, sites): (&mut ErrorVec<ErrorContext>, &mut ContextSites),
+    (ctx, site): (ErrorContext, Option<Location>),
+    limit: Option<ContextLimit>,
+    compaction: Option<&ContextCompaction>,
+    dropped: usize,
+) -> usize {
+    let before = contexts.len();
+    // Contexts are not folded into one that precedes dropped contexts.
+    let open = elision(limit, dropped, before).map_or(true, |(slot, _)| slot < before);
+    match compaction {
+        Some(compaction) if open => compaction.push(contexts, ctx),
+        _ => contexts.push(ctx),
+    }
+    sites.record(before, contexts.len() > before, site);
+    match limit {
+        Some(limit) => {
+            sites.truncate(limit);
+            limit.truncate(contexts)
+        },
+        None => 0,
+    }
+}
+
+/// Folds the runs of `contexts` with `compaction`, keeping `sites` in line.
+///
+/// The head kept by [`OverflowPolicy::KeepEnds`] must keep its length for the
+/// elision marker to stay in place, so only the contexts after it are folded.
+pub(crate) fn compact_bounded(
+    (contexts, sites): (&mut ErrorVec<ErrorContext>, &mut ContextSites),
+    compaction: &ContextCompaction,
+    limit: Option<ContextLimit>,
+    dropped: usize,
+) {
+    let from = match (limit, elision(limit, dropped, contexts.len())) {
+        (Some(limit), Some((slot, _))) if limit.policy() == OverflowPolicy::KeepEnds => slot,
+        _ => 0,
+    };
+    if sites.is_empty() {
+        compaction.compact(contexts, from);
+        return;
+    }
+    let tail = contexts.drain(from..).collect::<ErrorVec<_>>();
+    let tail_sites = sites.split_off(from);
+    for (i, (ctx, site)) in tail
+        .into_iter()
+        .zip(tail_sites.into_iter().chain(core::iter::repeat(None)))
+        .enumerate()
+    {
+        let before = contexts.len();
+        match i {
+            0 => contexts.push(ctx),
+            _ => compaction.push(contexts, ctx),
+        }
+        sites.record(before, contexts.len() > before, site);
+    }
+}
+
+/// Chain entry that stands in for dropped contexts.
+pub(crate) struct Elided(pub(crate) usize);
+
+impl Display for Elided {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        match self.0 {
+            1 => f.write_str("... 1 more context elided ..."),
+            n => write!(f, "... {} more contexts elided ...", n),
+        }
+    }
+}
@@ -0,0 +1,252 @@
+//! User-defined context payloads.
+//!
+//! [`ErrorContext::Custom`](crate::types::ErrorContext::Custom) carries a value
+//! of any type implementing [`ContextPayload`], such as a SQL query, an HTTP
+//! request line or a span in a source file. The payload decides how it is
+//! rendered through `Display`, and optionally how it is serialized and how it
+//! contributes to the error's fingerprint.
+//!
+//! Formatters render custom contexts with their `Display` output wherever
+//! other contexts appear. They are always internal, so they are left out of
+//! [`ComposableError::public_view`](crate::types::ComposableError::public_view),
+//! and they are never folded by [`compaction`](crate::types::compaction).
+//!
+//! With the `serde` feature, a custom context serializes as its
+//! [`kind`](ContextPayload::kind), its rendered message and its
+//! [`value`](ContextPayload::value). The original type cannot be recovered
+//! from that, so it deserializes into a [`SerializedPayload`].
+//!
+//! # Examples
+//!
+//! ```
+//! use error_rail::types::{ContextPayload, MetadataValue};
+//! use error_rail::{ComposableError, ErrorContext};
+//! use std::fmt;
+//!
+//! #[derive(Debug, Clone, PartialEq)]
+//! struct SqlQuery {
+//!     table: &'static str,
+//!     rows: u64,
+//! }
+//!
+//! impl fmt::Display for SqlQuery {
+//!     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+//!         write!(f, "querying {} ({} rows)", self.table, self.rows)
+//!     }
+//! }
+//!
+//! impl ContextPayload for SqlQuery {
+//!     fn kind(&self) -> &str {
+//!         "sql_query"
+//!     }
+//!
+//!     fn value(&self) -> Option<MetadataValue> {
+//!         Some(MetadataValue::map([("table", MetadataValue::from(self.table))]))
+//!     }
+//! }
+//!
+//! let err = ComposableError::new("timeout")
+//!     .with_context(ErrorContext::custom(SqlQuery { table: "orders", rows: 3 }));
+//!
+//! assert_eq!(err.error_chain(), "querying orders (3 rows) -> timeout");
+//! let query = err.context_iter().find_map(|ctx| ctx.as_custom()?.downcast_ref::<SqlQuery>());
+//! assert_eq!(query.map(|q| q.table), Some("orders"));
+//! ```
+
+use crate::types::alloc_type::Box;
+#[cfg(feature = "serde")]
+use crate::types::alloc_type::String;
+use crate::types::MetadataValue;
+use core::any::Any;
+use core::fmt::{Debug, Display};
+use core::hash::{Hash, Hasher};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// A user-defined value carried by [`ErrorContext::Custom`](crate::types::ErrorContext::Custom).
+///
+/// Payloads must also be `Clone + PartialEq`, which provides [`ClonePayload`].
+pub trait ContextPayload: Display + Debug + Send + Sync + ClonePayload {
+    /// Returns a stable name for the kind of payload, such as `"sql_query"`.
+    ///
+    /// It is serialized with the payload and hashed into the fingerprint, so
+    /// it should not change between releases.
+    fn kind(&self) -> &str;
+
+    /// Returns a structured representation of the payload for serialization.
+    ///
+    /// Defaults to `None`, in which case only the kind and the rendered
+    /// message are serialized.
+    #[inline]
+    fn value(&self) -> Option<MetadataValue> {
+        None
+    }
+
+    /// Feeds the parts of the payload that identify the error into `hasher`.
+    ///
+    /// The kind is always hashed; this defaults to adding nothing else, so
+    /// payloads of the same kind do not split fingerprints.
+    #[inline]
+    fn fingerprint(&self, hasher: &mut dyn Hasher) {
+        let _ = hasher;
+    }
+}
+
+/// Object-safe cloning, comparison and downcasting of a [`ContextPayload`].
+///
+/// Implemented for every payload that is `Clone + PartialEq`.
+pub trait ClonePayload: Any {
+    /// Clones the payload into a new box.
+    fn clone_payload(&self) -> Box<dyn ContextPayload>;
+
+    /// Returns `true` if `other` is of the same type and equal to this payload.
+    fn eq_payload(&self, other: &dyn ContextPayload) -> bool;
+
+    /// Returns the payload as `Any`, for downcasting.
+    fn as_any(&self) -> &dyn Any;
+}
+
+impl<T: ContextPayload + Clone + PartialEq> ClonePayload for T {
+    #[inline]
+    fn clone_payload(&self) -> Box<dyn ContextPayload> {
+        Box::new(self.clone())
+    }
+
+    #[inline]
+    fn eq_payload(&self, other: &dyn ContextPayload) -> bool {
+        other.downcast_ref::<T>().is_some_and(|other| self == other)
+    }
+
+    #[inline]
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+impl dyn ContextPayload {
+    /// Returns the payload if it is of type `T`.
+    #[inline]
+    pub fn downcast_ref<T: ContextPayload>(&self) -> Option<&T> {
+        self.as_any().downcast_ref::<T>()
+    }
+
+    /// Returns `true` if the payload is of type `T`.
+    #[inline]
+    pub fn is<T: ContextPayload>(&self) -> bool {
+        self.as_any().is::<T>()
+    }
+}
+
+impl Clone for Box<dyn ContextPayload> {
+    #[inline]
+    fn clone(&self) -> Self {
+        self.clone_payload()
+    }
+}
+
+impl PartialEq for dyn ContextPayload {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.eq_payload(other)
+    }
+}
+
+impl Eq for dyn ContextPayload {}
+
+impl Hash for dyn ContextPayload {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.kind().hash(state);
+        self.fingerprint(state);
+    }
+}
+
+/// A custom payload read back from its serialized form.
+///
+/// Holds the kind, rendered message and value of the original payload, and
+/// renders as that message. Its fingerprint only covers the kind.
+#[cfg(feature = "serde")]
+#[derive(Debug, Clone, PartialEq)]
+pub struct SerializedPayload {
+    kind: String,
+    message: String,
+    value: Option<MetadataValue>,
+}
+
+#[cfg(feature = "serde")]
+impl SerializedPayload {
+    /// Returns the rendered message of the original payload.
+    #[inline]
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    /// Returns the structured value of the original payload, if it had one.
+    #[inline]
+    pub fn value_ref(&self) -> Option<&MetadataValue> {
+        self.value.as_ref()
+    }
+}
+
+#[cfg(feature = "serde")]
+impl Display for SerializedPayload {
+    #[inline]
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl ContextPayload for SerializedPayload {
+    #[inline]
+    fn kind(&self) -> &str {
+        &self.kind
+    }
+
+    #[inline]
+    fn value(&self) -> Option<MetadataValue> {
+        self.value.clone()
+    }
+}
+
+#[cfg(feature = "serde")]
+impl Serialize for dyn ContextPayload {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        #[derive(Serialize)]
+        #[serde(rename = "ContextPayload")]
+        struct PayloadRef<'a> {
+            kind: &'a str,
+            message: Rendered<'a>,
+            #[serde(skip_serializing_if = "Option::is_none")]
+            value: Option<MetadataValue>,
+        }
+
+        struct Rendered<'a>(&'a dyn ContextPayload);
+
+        impl Serialize for Rendered<'_> {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serializer.collect_str(self.0)
+            }
+        }
+
+        PayloadRef { kind: self.kind(), message: Rendered(self), value: self.value() }
+            .serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for Box<dyn ContextPayload> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(rename = "ContextPayload")]
+        struct PayloadRepr {
+            kind: String,
+            #[serde(default)]
+            message: String,
+            #[serde(default)]
+            value: Option<MetadataValue>,
+        }
+
+        let PayloadRepr { kind, message, value } = PayloadRepr::deserialize(deserializer)?;
+        Ok(Box::new(SerializedPayload { kind, message, value }))
+    }
+}
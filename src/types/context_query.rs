@@ -0,0 +1,81 @@
+//! Non-allocating queries over a stack of contexts.
+//!
+//! The query methods of [`ComposableError`](crate::types::ComposableError),
+//! [`ErrorPipeline`](crate::types::ErrorPipeline) and
+//! [`Validation`](crate::validation::Validation) share these helpers. Every
+//! helper takes contexts most recent first and borrows from them.
+
+use crate::types::{ErrorContext, Location, MetadataValue};
+
+/// Returns `true` if any context carries `tag`.
+#[inline]
+pub(crate) fn has_tag<'a>(mut contexts: impl Iterator<Item = &'a ErrorContext>, tag: &str) -> bool {
+    contexts.any(|ctx| {
+        ctx.as_group()
+            .is_some_and(|g| g.tags.iter().any(|t| t == tag))
+    })
+}
+
+/// Iterates over the tags of every context.
+#[inline]
+pub(crate) fn tags<'a>(
+    contexts: impl Iterator<Item = &'a ErrorContext> + 'a,
+) -> impl Iterator<Item = &'a str> + 'a {
+    contexts
+        .filter_map(ErrorContext::as_group)
+        .flat_map(|g| g.tags.iter().map(|tag| tag.as_ref()))
+}
+
+/// Returns the first value stored under metadata `key` if it is text.
+///
+/// The first entry wins whatever its type, so an older text value never
+/// shadows a newer value of another type.
+#[inline]
+pub(crate) fn metadata<'a>(
+    contexts: impl Iterator<Item = &'a ErrorContext>,
+    key: &str,
+) -> Option<&'a str> {
+    metadata_any(contexts, key)?.as_str()
+}
+
+/// Returns the first value stored under metadata `key`, whatever its type.
+#[inline]
+pub(crate) fn metadata_any<'a>(
+    contexts: impl Iterator<Item = &'a ErrorContext>,
+    key: &str,
+) -> Option<&'a MetadataValue> {
+    metadata_all(contexts, key).next()
+}
+
+/// Iterates over the values stored under metadata `key`.
+#[inline]
+pub(crate) fn metadata_all<'a: 'k, 'k>(
+    contexts: impl Iterator<Item = &'a ErrorContext> + 'k,
+    key: &'k str,
+) -> impl Iterator<Item = &'a MetadataValue> + 'k {
+    contexts
+        .filter_map(ErrorContext::as_group)
+        .flat_map(|g| g.metadata.iter())
+        .filter(move |(k, _)| k == key)
+        .map(|(_, value)| value)
+}
+
+/// Iterates over the source locations of every context that has one.
+#[inline]
+pub(crate) fn locations<'a>(
+    contexts: impl Iterator<Item = &'a ErrorContext> + 'a,
+) -> impl Iterator<Item = &'a Location> + 'a {
+    contexts.filter_map(|ctx| ctx.as_group()?.location.as_ref())
+}
+
+/// Iterates over the plain messages of every context that has one.
+#[inline]
+pub(crate) fn messages<'a>(
+    contexts: impl Iterator<Item = &'a ErrorContext> + 'a,
+) -> impl Iterator<Item = &'a str> + 'a {
+    contexts.filter_map(|ctx| match ctx {
+        ErrorContext::Simple(message) => Some(message.as_ref()),
+        ErrorContext::Group(g) => g.message.as_deref(),
+        ErrorContext::Custom(_) => None,
+    })
+}
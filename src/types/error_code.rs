@@ -0,0 +1,295 @@
+//! Pluggable error codes.
+//!
+//! [`ComposableError::set_code`](crate::types::ComposableError::set_code) accepts any
+//! [`ErrorCode`], not just `u32`. Plain numbers stay on the fast path and keep
+//! working exactly as before, while richer codes (string identifiers, enum
+//! variants or hierarchical [`HierarchicalCode`]s) are stored alongside them and
+//! used for formatting, serde and fingerprinting.
+//!
+//! # Examples
+//!
+//! ```
+//! use error_rail::ComposableError;
+//! use error_rail::types::{ErrorCode, HierarchicalCode};
+//!
+//! let err = ComposableError::new("connection refused")
+//!     .set_code(HierarchicalCode::new("DB", "CONN", 42));
+//!
+//! assert_eq!(err.error_code(), Some(42));
+//! assert_eq!(err.code().unwrap().domain(), Some("DB"));
+//! assert_eq!(err.to_string(), "connection refused (code: DB-CONN-0042)");
+//! ```
+
+use crate::types::alloc_type::{Arc, Cow, String};
+#[cfg(not(feature = "std"))]
+use alloc::string::ToString;
+use core::any::Any;
+use core::fmt::{Debug, Display};
+use core::hash::{Hash, Hasher};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// An error code that can be attached to a [`ComposableError`](crate::types::ComposableError).
+///
+/// `Display` provides the rendered form used in messages and fingerprints.
+/// Hierarchical codes can expose their parts through [`domain`](Self::domain),
+/// [`category`](Self::category) and [`number`](Self::number); the number is
+/// what [`error_code`](crate::types::ComposableError::error_code) returns.
+///
+/// # Examples
+///
+/// ```
+/// use error_rail::ComposableError;
+/// use error_rail::types::ErrorCode;
+/// use std::fmt;
+///
+/// #[derive(Debug)]
+/// enum ApiCode {
+///     RateLimited,
+/// }
+///
+/// impl fmt::Display for ApiCode {
+///     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+///         f.write_str("API-RATE-LIMITED")
+///     }
+/// }
+///
+/// impl ErrorCode for ApiCode {
+///     fn domain(&self) -> Option<&str> {
+///         Some("API")
+///     }
+///
+///     fn number(&self) -> Option<u32> {
+///         Some(429)
+///     }
+/// }
+///
+/// let err = ComposableError::new("too many requests").set_code(ApiCode::RateLimited);
+///
+/// assert_eq!(err.error_code(), Some(429));
+/// assert!(matches!(err.code_as::<ApiCode>(), Some(ApiCode::RateLimited)));
+/// ```
+pub trait ErrorCode: Display + Debug + Send + Sync + 'static {
+    /// Returns the top-level domain of the code (e.g. `"DB"`), if any.
+    #[inline]
+    fn domain(&self) -> Option<&str> {
+        None
+    }
+
+    /// Returns the category within the domain (e.g. `"CONN"`), if any.
+    #[inline]
+    fn category(&self) -> Option<&str> {
+        None
+    }
+
+    /// Returns the numeric part of the code, if any.
+    #[inline]
+    fn number(&self) -> Option<u32> {
+        None
+    }
+}
+
+impl ErrorCode for u32 {
+    #[inline]
+    fn number(&self) -> Option<u32> {
+        Some(*self)
+    }
+}
+
+impl ErrorCode for &'static str {}
+
+impl ErrorCode for String {}
+
+/// A `DOMAIN-CATEGORY-NUMBER` code such as `DB-CONN-0042`.
+///
+/// # Examples
+///
+/// ```
+/// use error_rail::types::HierarchicalCode;
+///
+/// let code = HierarchicalCode::new("DB", "CONN", 42);
+/// assert_eq!(code.to_string(), "DB-CONN-0042");
+/// ```
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct HierarchicalCode {
+    /// Top-level domain, e.g. `"DB"`
+    pub domain: Cow<'static, str>,
+    /// Category within the domain, e.g. `"CONN"`
+    pub category: Cow<'static, str>,
+    /// Number within the category
+    pub number: u32,
+}
+
+impl HierarchicalCode {
+    /// Creates a hierarchical code from its parts.
+    #[inline]
+    pub fn new<D, C>(domain: D, category: C, number: u32) -> Self
+    where
+        D: Into<Cow<'static, str>>,
+        C: Into<Cow<'static, str>>,
+    {
+        Self { domain: domain.into(), category: category.into(), number }
+    }
+}
+
+impl Display for HierarchicalCode {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{}-{}-{:04}", self.domain, self.category, self.number)
+    }
+}
+
+impl ErrorCode for HierarchicalCode {
+    #[inline]
+    fn domain(&self) -> Option<&str> {
+        Some(&self.domain)
+    }
+
+    #[inline]
+    fn category(&self) -> Option<&str> {
+        Some(&self.category)
+    }
+
+    #[inline]
+    fn number(&self) -> Option<u32> {
+        Some(self.number)
+    }
+}
+
+/// An owned snapshot of any [`ErrorCode`].
+///
+/// This is the serialized form of non-numeric codes, and the type they come
+/// back as after deserialization.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CodeInfo {
+    /// Rendered code, as produced by `Display`
+    pub code: String,
+    /// Top-level domain, if any
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
+    pub domain: Option<String>,
+    /// Category within the domain, if any
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
+    pub category: Option<String>,
+    /// Numeric part, if any
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
+    pub number: Option<u32>,
+}
+
+impl CodeInfo {
+    /// Captures the rendered form and parts of a code.
+    pub fn of(code: &dyn ErrorCode) -> Self {
+        Self {
+            code: code.to_string(),
+            domain: code.domain().map(Into::into),
+            category: code.category().map(Into::into),
+            number: code.number(),
+        }
+    }
+}
+
+impl Display for CodeInfo {
+    #[inline]
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(&self.code)
+    }
+}
+
+impl ErrorCode for CodeInfo {
+    #[inline]
+    fn domain(&self) -> Option<&str> {
+        self.domain.as_deref()
+    }
+
+    #[inline]
+    fn category(&self) -> Option<&str> {
+        self.category.as_deref()
+    }
+
+    #[inline]
+    fn number(&self) -> Option<u32> {
+        self.number
+    }
+}
+
+/// Views a type-erased code value as the concrete `ErrorCode` it was created from.
+type ViewFn = fn(&(dyn Any + Send + Sync)) -> &dyn ErrorCode;
+
+/// Shared storage for a non-numeric [`ErrorCode`].
+///
+/// Codes are compared and hashed by their parts and rendered form.
+#[derive(Clone)]
+pub(crate) struct CodeSlot {
+    value: Arc<dyn Any + Send + Sync>,
+    view: ViewFn,
+}
+
+impl CodeSlot {
+    /// Stores `code`, or returns `None` for plain `u32` codes that need no slot.
+    #[inline]
+    pub(crate) fn new<C: ErrorCode>(code: C) -> Option<Self> {
+        if (&code as &dyn Any).is::<u32>() {
+            return None;
+        }
+        Some(Self { value: Arc::new(code), view: view::<C> })
+    }
+
+    #[inline]
+    pub(crate) fn get(&self) -> &dyn ErrorCode {
+        (self.view)(&*self.value)
+    }
+
+    #[inline]
+    pub(crate) fn downcast_ref<C: ErrorCode>(&self) -> Option<&C> {
+        let value: &dyn Any = &*self.value;
+        value.downcast_ref::<C>()
+    }
+}
+
+fn view<C: ErrorCode>(value: &(dyn Any + Send + Sync)) -> &dyn ErrorCode {
+    value
+        .downcast_ref::<C>()
+        .expect("code slots are only created with their own view function")
+}
+
+impl Debug for CodeSlot {
+    #[inline]
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        Debug::fmt(self.get(), f)
+    }
+}
+
+impl PartialEq for CodeSlot {
+    fn eq(&self, other: &Self) -> bool {
+        let (a, b) = (self.get(), other.get());
+        a.number() == b.number()
+            && a.domain() == b.domain()
+            && a.category() == b.category()
+            && a.to_string() == b.to_string()
+    }
+}
+
+impl Eq for CodeSlot {}
+
+impl Hash for CodeSlot {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        let code = self.get();
+        code.number().hash(state);
+        code.to_string().hash(state);
+    }
+}
+
+#[cfg(feature = "serde")]
+impl Serialize for CodeSlot {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        CodeInfo::of(self.get()).serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for CodeSlot {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let info = CodeInfo::deserialize(deserializer)?;
+        Ok(Self { value: Arc::new(info), view: view::<CodeInfo> })
+    }
+}
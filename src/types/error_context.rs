@@ -8,6 +8,7 @@
 //!
 //! - **`Simple`**: Free-form text describing what was happening when the error occurred.
 //! - **`Group`**: A rich context containing location, tags, metadata, and an optional message.
+//! - **`Custom`**: A user-defined [`ContextPayload`], see [`context_payload`](crate::types::context_payload).
 //!
 //! # Usage
 //!
@@ -31,11 +32,16 @@
 //! assert!(ctx.message().contains("[db]"));
 //! ```
 use crate::types::alloc_type::{Box, Cow};
+use crate::types::compaction::{ContextRepeat, RepeatView};
+use crate::types::context_payload::ContextPayload;
+use crate::types::i18n::{self, Localizer, MessageCatalog, Template};
+use crate::types::redaction::{self, Unredacted, REDACTED};
+use crate::types::{ContextKey, FromMetadata, MetadataValue, Severity, Timestamp, Visibility};
 #[cfg(not(feature = "std"))]
 use alloc::string::ToString;
-use core::fmt::Display;
+use core::fmt::{Debug, Display};
 #[cfg(feature = "serde")]
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Serialize, Serializer};
 
 use smallvec::SmallVec;
 
@@ -49,6 +55,7 @@ use smallvec::SmallVec;
 ///
 /// - `Simple(Cow<'static, str>)`: A plain text message describing the error context.
 /// - `Group(GroupContext)`: A rich context containing multiple pieces of information.
+/// - `Custom(Box<dyn ContextPayload>)`: A user-defined payload with its own rendering.
 ///
 /// # Examples
 ///
@@ -63,28 +70,160 @@ use smallvec::SmallVec;
 ///     .metadata("retry_count", "3")
 ///     .build();
 /// ```
-#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", derive(Deserialize))]
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum ErrorContext {
     Simple(Cow<'static, str>),
     Group(Box<GroupContext>),
+    Custom(Box<dyn ContextPayload>),
 }
 
 /// A rich context containing multiple pieces of information.
-#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
-#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+///
+/// `Debug`, `Display` and `Serialize` replace sensitive parts with
+/// [`REDACTED`]; see [`redaction`].
+#[cfg_attr(feature = "serde", derive(Deserialize))]
+#[derive(Clone, PartialEq, Eq, Hash, Default)]
 pub struct GroupContext {
     /// Optional message describing this context
     pub message: Option<Cow<'static, str>>,
+    /// Optional catalog key of a localized message; see [`i18n`]
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub message_key: Option<Cow<'static, str>>,
     /// Optional source location where the error occurred
     pub location: Option<Location>,
     /// Tags for categorizing and filtering errors
     pub tags: SmallVec<[Cow<'static, str>; 2]>,
-    /// Arbitrary key-value metadata pairs
-    pub metadata: SmallVec<[(Cow<'static, str>, Cow<'static, str>); 2]>,
+    /// Key-value metadata pairs with typed values
+    pub metadata: SmallVec<[(Cow<'static, str>, MetadataValue); 2]>,
+    /// Optional severity of this context
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub severity: Option<Severity>,
+    /// Optional time at which this context was attached
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub timestamp: Option<Timestamp>,
+    /// Whether the message is sensitive and rendered as [`REDACTED`]
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub secret_message: bool,
+    /// Metadata keys whose values are sensitive and rendered as [`REDACTED`]
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub secret_keys: SmallVec<[Cow<'static, str>; 1]>,
+    /// Whether this context may be shown to end users; see [`visibility`](crate::types::visibility)
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub visibility: Visibility,
+    /// How often this context repeated, when consecutive contexts were folded
+    /// into it; see [`compaction`](crate::types::compaction)
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub repeat: Option<ContextRepeat>,
+}
+
+impl GroupContext {
+    /// Returns `true` if the value of metadata `key` is redacted, either because
+    /// it was added as secret or because the key is on the global denylist.
+    #[inline]
+    pub fn is_secret_key(&self, key: &str) -> bool {
+        self.secret_keys.iter().any(|secret| secret == key) || redaction::is_redacted_key(key)
+    }
+
+    /// Returns the message, or [`REDACTED`] if it is sensitive.
+    #[inline]
+    pub fn redacted_message(&self) -> Option<&str> {
+        self.message
+            .as_deref()
+            .map(|message| if self.secret_message { REDACTED } else { message })
+    }
+
+    /// Iterates over the metadata with sensitive values replaced by [`REDACTED`].
+    pub fn redacted_metadata(&self) -> impl Iterator<Item = (&str, &MetadataValue)> + '_ {
+        self.metadata.iter().map(move |(key, value)| {
+            let value = if self.is_secret_key(key) { &REDACTED_VALUE } else { value };
+            (key.as_ref(), value)
+        })
+    }
+
+    /// Returns the first value of a folded counter, or [`REDACTED`] if it is sensitive.
+    fn redacted_range(&self, reveal: bool) -> Option<(&str, &MetadataValue)> {
+        let range = self.repeat.as_ref()?.range.as_ref()?;
+        let first =
+            if !reveal && self.is_secret_key(&range.key) { &REDACTED_VALUE } else { &range.first };
+        Some((range.key.as_ref(), first))
+    }
+
+    /// Returns the value of the first metadata entry stored under `key`.
+    #[inline]
+    pub fn value(&self, key: &str) -> Option<&MetadataValue> {
+        self.metadata
+            .iter()
+            .find_map(|(k, value)| (k == key).then_some(value))
+    }
+
+    /// Reads the value of a typed key, or `None` if it is missing or has another type.
+    ///
+    /// # Examples
+    /// ```
+    /// use error_rail::types::ContextKey;
+    /// use error_rail::ErrorContext;
+    ///
+    /// const RETRIES: ContextKey<u32> = ContextKey::new("retries");
+    ///
+    /// let ctx = ErrorContext::builder().set(RETRIES, 3).build();
+    /// let ErrorContext::Group(group) = &ctx else { unreachable!() };
+    /// assert_eq!(group.get(RETRIES), Some(3));
+    /// ```
+    #[inline]
+    pub fn get<T: FromMetadata>(&self, key: ContextKey<T>) -> Option<T> {
+        self.value(key.name()).and_then(T::from_metadata)
+    }
+}
+
+/// Stand-in for redacted metadata values.
+static REDACTED_VALUE: MetadataValue = MetadataValue::Str(Cow::Borrowed(REDACTED));
+
+impl Debug for GroupContext {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        struct Metadata<'a>(&'a GroupContext);
+
+        impl Debug for Metadata<'_> {
+            fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                f.debug_list().entries(self.0.redacted_metadata()).finish()
+            }
+        }
+
+        struct Repeat<'a>(&'a ContextRepeat, Option<(&'a str, &'a MetadataValue)>);
+
+        impl Debug for Repeat<'_> {
+            fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                f.debug_struct("ContextRepeat")
+                    .field("count", &self.0.count)
+                    .field("range", &self.1)
+                    .finish()
+            }
+        }
+
+        f.debug_struct("GroupContext")
+            .field("message", &self.redacted_message())
+            .field("message_key", &self.message_key)
+            .field("location", &self.location)
+            .field("tags", &self.tags)
+            .field("metadata", &Metadata(self))
+            .field("severity", &self.severity)
+            .field("timestamp", &self.timestamp)
+            .field("visibility", &self.visibility)
+            .field(
+                "repeat",
+                &self
+                    .repeat
+                    .as_ref()
+                    .map(|repeat| Repeat(repeat, self.redacted_range(false))),
+            )
+            .finish()
+    }
 }
 
 /// Source file and line number where the error occurred.
+///
+/// Displayed as `file:line`, followed by `:column` and ` in module::path` when
+/// those are known.
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct Location {
@@ -92,6 +231,71 @@ pub struct Location {
     pub file: Cow<'static, str>,
     /// Line number in the source file
     pub line: u32,
+    /// Optional column in the source line
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
+    pub column: Option<u32>,
+    /// Optional path of the enclosing module; automatic capture with
+    /// `#[track_caller]` cannot fill it in
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
+    pub module_path: Option<Cow<'static, str>>,
+}
+
+impl Location {
+    /// Creates a location from a file and line.
+    #[inline]
+    pub fn new<S: Into<Cow<'static, str>>>(file: S, line: u32) -> Self {
+        Self { file: file.into(), line, column: None, module_path: None }
+    }
+
+    /// Returns the location of the caller, including its column.
+    ///
+    /// # Examples
+    /// ```
+    /// use error_rail::types::Location;
+    ///
+    /// let (here, line) = (Location::caller(), line!());
+    /// assert_eq!(here.line, line);
+    /// assert!(here.column.is_some());
+    /// ```
+    #[inline]
+    #[track_caller]
+    pub fn caller() -> Self {
+        core::panic::Location::caller().into()
+    }
+
+    /// Sets the column.
+    #[inline]
+    pub fn with_column(mut self, column: u32) -> Self {
+        self.column = Some(column);
+        self
+    }
+
+    /// Sets the module path, usually from `module_path!()`.
+    #[inline]
+    pub fn with_module_path<S: Into<Cow<'static, str>>>(mut self, module_path: S) -> Self {
+        self.module_path = Some(module_path.into());
+        self
+    }
+}
+
+impl From<&'static core::panic::Location<'static>> for Location {
+    #[inline]
+    fn from(location: &'static core::panic::Location<'static>) -> Self {
+        Self::new(location.file(), location.line()).with_column(location.column())
+    }
+}
+
+impl Display for Location {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{}:{}", self.file, self.line)?;
+        if let Some(column) = self.column {
+            write!(f, ":{}", column)?;
+        }
+        if let Some(module_path) = &self.module_path {
+            write!(f, " in {}", module_path)?;
+        }
+        Ok(())
+    }
 }
 
 impl ErrorContext {
@@ -115,6 +319,12 @@ impl ErrorContext {
         Self::Simple(message.into())
     }
 
+    /// Wraps a user-defined payload; see [`context_payload`](crate::types::context_payload).
+    #[inline]
+    pub fn custom<P: ContextPayload>(payload: P) -> Self {
+        Self::Custom(Box::new(payload))
+    }
+
     /// Captures the file/line pair where an error occurred.
     ///
     /// Typically used with the `file!()` and `line!()` macros to automatically
@@ -134,7 +344,7 @@ impl ErrorContext {
     #[inline]
     pub fn location<S: Into<Cow<'static, str>>>(file: S, line: u32) -> Self {
         Self::Group(Box::new(GroupContext {
-            location: Some(Location { file: file.into(), line }),
+            location: Some(Location::new(file, line)),
             ..Default::default()
         }))
     }
@@ -180,10 +390,7 @@ impl ErrorContext {
     /// assert_eq!(ctx.message(), "(user_id=42)");
     /// ```
     #[inline]
-    pub fn metadata<K: Into<Cow<'static, str>>, V: Into<Cow<'static, str>>>(
-        key: K,
-        value: V,
-    ) -> Self {
+    pub fn metadata<K: Into<Cow<'static, str>>, V: Into<MetadataValue>>(key: K, value: V) -> Self {
         Self::Group(Box::new(GroupContext {
             metadata: smallvec::smallvec![(key.into(), value.into())],
             ..Default::default()
@@ -195,6 +402,7 @@ impl ErrorContext {
     /// Each variant is formatted differently:
     /// - `Simple`: Returns the message as-is.
     /// - `Group`: Combines all available fields into one cohesive unit.
+    /// - `Custom`: Renders the payload with its `Display` implementation.
     ///
     /// # Examples
     ///
@@ -224,9 +432,242 @@ impl ErrorContext {
     pub fn message(&self) -> Cow<'_, str> {
         match self {
             Self::Simple(s) => Cow::Borrowed(s.as_ref()),
-            Self::Group(g) => Cow::Owned(ContextRenderer::new(g).to_string()),
+            Self::Group(g) => Cow::Owned(ContextRenderer::new(g, false, None).to_string()),
+            Self::Custom(payload) => Cow::Owned(payload.to_string()),
         }
     }
+
+    /// Creates a localized context from a catalog key and named arguments.
+    ///
+    /// The arguments are stored as metadata and substituted into the template
+    /// the [`MessageCatalog`] returns for the key at render time. Without a
+    /// catalog, the key is rendered followed by the arguments.
+    ///
+    /// # Examples
+    /// ```
+    /// use error_rail::types::MemoryCatalog;
+    /// use error_rail::ErrorContext;
+    ///
+    /// let ctx = ErrorContext::i18n("order.not_found", [("id", 42)]);
+    /// assert_eq!(ctx.message(), "order.not_found (id=42)");
+    ///
+    /// let catalog = MemoryCatalog::new().with_message("de", "order.not_found", "Bestellung {id} fehlt");
+    /// assert_eq!(ctx.localize(&catalog, "de-CH"), "Bestellung 42 fehlt");
+    /// ```
+    #[inline]
+    pub fn i18n<K, I, N, V>(key: K, args: I) -> Self
+    where
+        K: Into<Cow<'static, str>>,
+        I: IntoIterator<Item = (N, V)>,
+        N: Into<Cow<'static, str>>,
+        V: Into<MetadataValue>,
+    {
+        Self::Group(Box::new(GroupContext {
+            message_key: Some(key.into()),
+            metadata: args
+                .into_iter()
+                .map(|(name, value)| (name.into(), value.into()))
+                .collect(),
+            ..Default::default()
+        }))
+    }
+
+    /// Returns the catalog key of a localized context, if it has one.
+    #[inline]
+    pub fn message_key(&self) -> Option<&str> {
+        match self {
+            Self::Simple(_) | Self::Custom(_) => None,
+            Self::Group(g) => g.message_key.as_deref(),
+        }
+    }
+
+    /// Renders the context like [`message`](Self::message), resolving its message
+    /// key in `catalog` for `locale` and its fallback chain.
+    #[inline]
+    pub fn localize(&self, catalog: &dyn MessageCatalog, locale: &str) -> Cow<'_, str> {
+        match self {
+            Self::Simple(s) => Cow::Borrowed(s.as_ref()),
+            Self::Group(g) => Cow::Owned(
+                ContextRenderer::new(g, false, Some(Localizer { catalog, locale })).to_string(),
+            ),
+            Self::Custom(payload) => Cow::Owned(payload.to_string()),
+        }
+    }
+
+    /// Creates a context whose message is sensitive and rendered as [`REDACTED`].
+    ///
+    /// # Examples
+    /// ```
+    /// use error_rail::ErrorContext;
+    ///
+    /// let ctx = ErrorContext::secret("card 4111-1111-1111-1111 declined");
+    /// assert_eq!(ctx.message(), "[REDACTED]");
+    /// assert_eq!(ctx.unredacted().to_string(), "card 4111-1111-1111-1111 declined");
+    /// ```
+    #[inline]
+    pub fn secret<S: Into<Cow<'static, str>>>(message: S) -> Self {
+        ErrorContextBuilder::new().secret_message(message).build()
+    }
+
+    /// Returns a privileged view that renders and serializes sensitive parts in clear text.
+    #[inline]
+    pub fn unredacted(&self) -> Unredacted<'_, Self> {
+        Unredacted(self)
+    }
+
+    /// Creates a message context that is safe to show to end users.
+    ///
+    /// Other contexts are internal and left out of
+    /// [`ComposableError::public_view`](crate::types::ComposableError::public_view).
+    ///
+    /// # Examples
+    /// ```
+    /// use error_rail::ErrorContext;
+    ///
+    /// let ctx = ErrorContext::public("saving your profile");
+    /// assert!(ctx.is_public());
+    /// assert!(!ErrorContext::new("UPDATE users SET ...").is_public());
+    /// ```
+    #[inline]
+    pub fn public<S: Into<Cow<'static, str>>>(message: S) -> Self {
+        ErrorContextBuilder::new().message(message).public().build()
+    }
+
+    /// Returns who may see this context. `Simple` and `Custom` contexts are always internal.
+    #[inline]
+    pub fn visibility(&self) -> Visibility {
+        match self {
+            Self::Simple(_) | Self::Custom(_) => Visibility::Internal,
+            Self::Group(g) => g.visibility,
+        }
+    }
+
+    /// Returns `true` if this context may be shown to end users.
+    #[inline]
+    pub fn is_public(&self) -> bool {
+        self.visibility().is_public()
+    }
+
+    #[inline]
+    pub(crate) fn render(
+        &self,
+        f: &mut core::fmt::Formatter<'_>,
+        reveal: bool,
+        localizer: Option<Localizer<'_>>,
+    ) -> core::fmt::Result {
+        self.render_repeated(f, reveal, localizer, None)
+    }
+
+    /// Renders the context as the most recent of a run of folded contexts.
+    ///
+    /// Without `repeat`, a group renders its own stored repeat.
+    pub(crate) fn render_repeated(
+        &self,
+        f: &mut core::fmt::Formatter<'_>,
+        reveal: bool,
+        localizer: Option<Localizer<'_>>,
+        repeat: Option<RepeatView<'_>>,
+    ) -> core::fmt::Result {
+        let plain = match self {
+            Self::Simple(s) => f.write_str(s),
+            Self::Custom(payload) => Display::fmt(payload, f),
+            Self::Group(g) => {
+                let mut renderer = ContextRenderer::new(g, reveal, localizer);
+                renderer.repeat = repeat.or(renderer.repeat);
+                return renderer.render(f);
+            },
+        };
+        plain?;
+        match repeat {
+            Some(RepeatView { count, .. }) if count > 1 => write!(f, " (x{})", count),
+            _ => Ok(()),
+        }
+    }
+
+    /// Returns the group behind this context, or `None` for other kinds.
+    #[inline]
+    pub fn as_group(&self) -> Option<&GroupContext> {
+        match self {
+            Self::Group(g) => Some(g),
+            Self::Simple(_) | Self::Custom(_) => None,
+        }
+    }
+
+    /// Returns the user-defined payload behind this context, or `None` for other kinds.
+    #[inline]
+    pub fn as_custom(&self) -> Option<&dyn ContextPayload> {
+        match self {
+            Self::Custom(payload) => Some(&**payload),
+            Self::Simple(_) | Self::Group(_) => None,
+        }
+    }
+
+    /// Returns the severity of a group context, if one was set.
+    ///
+    /// # Examples
+    /// ```
+    /// use error_rail::ErrorContext;
+    /// use error_rail::types::Severity;
+    ///
+    /// let ctx = ErrorContext::builder().tag("db").severity(Severity::Critical).build();
+    /// assert_eq!(ctx.severity(), Some(Severity::Critical));
+    /// assert_eq!(ErrorContext::new("plain").severity(), None);
+    /// ```
+    #[inline]
+    pub fn severity(&self) -> Option<Severity> {
+        match self {
+            Self::Simple(_) | Self::Custom(_) => None,
+            Self::Group(g) => g.severity,
+        }
+    }
+
+    /// Returns the time at which a group context was attached, if it was recorded.
+    #[inline]
+    pub fn timestamp(&self) -> Option<Timestamp> {
+        match self {
+            Self::Simple(_) | Self::Custom(_) => None,
+            Self::Group(g) => g.timestamp,
+        }
+    }
+
+    /// Reads the value of a typed key from a group context's metadata.
+    ///
+    /// Returns `None` for other contexts and for missing or differently typed values.
+    #[inline]
+    pub fn metadata_value<T: FromMetadata>(&self, key: ContextKey<T>) -> Option<T> {
+        match self {
+            Self::Simple(_) | Self::Custom(_) => None,
+            Self::Group(g) => g.get(key),
+        }
+    }
+
+    /// Records the time at which this context was attached.
+    ///
+    /// A `Simple` context becomes a `Group` with the same message, so its
+    /// rendering does not change. A `Custom` context has no room for a
+    /// timestamp and is returned unchanged.
+    ///
+    /// # Examples
+    /// ```
+    /// use error_rail::ErrorContext;
+    /// use error_rail::types::Timestamp;
+    ///
+    /// let ctx = ErrorContext::new("retrying").with_timestamp(Timestamp::from_unix_millis(5));
+    /// assert_eq!(ctx.message(), "retrying");
+    /// assert_eq!(ctx.timestamp(), Some(Timestamp::from_unix_millis(5)));
+    /// ```
+    #[inline]
+    pub fn with_timestamp(self, timestamp: Timestamp) -> Self {
+        let mut group = match self {
+            Self::Simple(message) => {
+                Box::new(GroupContext { message: Some(message), ..Default::default() })
+            },
+            Self::Group(group) => group,
+            custom @ Self::Custom(_) => return custom,
+        };
+        group.timestamp = Some(timestamp);
+        Self::Group(group)
+    }
 }
 
 /// Private helper for unified context rendering.
@@ -235,11 +676,18 @@ impl ErrorContext {
 /// across both `message()` and `Display::fmt`.
 struct ContextRenderer<'a> {
     group: &'a GroupContext,
+    /// Whether sensitive parts are rendered in clear text.
+    reveal: bool,
+    /// Catalog and locale used to resolve the message key.
+    localizer: Option<Localizer<'a>>,
+    /// Repeat count and counter range of the run this context stands for.
+    repeat: Option<RepeatView<'a>>,
 }
 
 impl<'a> ContextRenderer<'a> {
-    fn new(group: &'a GroupContext) -> Self {
-        Self { group }
+    fn new(group: &'a GroupContext, reveal: bool, localizer: Option<Localizer<'a>>) -> Self {
+        let repeat = group.repeat.as_ref().map(ContextRepeat::view);
+        Self { group, reveal, localizer, repeat }
     }
 
     fn render(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
@@ -261,12 +709,17 @@ impl<'a> ContextRenderer<'a> {
             if has_content {
                 write!(f, " ")?;
             }
-            write!(f, "at {}:{}", loc.file.as_ref(), loc.line)?;
+            write!(f, "at {}", loc)?;
             has_content = true;
         }
 
-        // 3. Message
-        if let Some(msg) = &g.message {
+        // 3. Message, localized when the catalog knows its key
+        let template = match (&g.message_key, self.localizer) {
+            (Some(key), Some(localizer)) => localizer.resolve(key),
+            _ => None,
+        };
+        let message = g.message.as_deref().or(g.message_key.as_deref());
+        if template.is_some() || message.is_some() {
             if has_content {
                 if g.location.is_some() {
                     write!(f, ": ")?;
@@ -274,24 +727,72 @@ impl<'a> ContextRenderer<'a> {
                     write!(f, " ")?;
                 }
             }
-            write!(f, "{}", msg.as_ref())?;
+            match (&template, message) {
+                _ if g.secret_message && !self.reveal => f.write_str(REDACTED)?,
+                (Some(template), _) => Template {
+                    template,
+                    arg: |name: &str| g.value(name).map(|value| self.value(name, value)),
+                }
+                .fmt(f)?,
+                (None, Some(message)) => f.write_str(message)?,
+                (None, None) => {},
+            }
             has_content = true;
         }
 
-        // 4. Metadata
-        if let Some((first, rest)) = g.metadata.split_first() {
+        // 4. Metadata, minus the arguments already used by the template
+        let mut metadata = g
+            .metadata
+            .iter()
+            .filter(|(k, _)| !template.as_deref().is_some_and(|t| i18n::uses_arg(t, k)));
+        if let Some((k, v)) = metadata.next() {
             if has_content {
                 write!(f, " ")?;
             }
-            write!(f, "({}={}", first.0, first.1)?;
-            for (k, v) in rest {
-                write!(f, ", {}={}", k, v)?;
+            write!(f, "(")?;
+            self.entry(f, k, v)?;
+            for (k, v) in metadata {
+                write!(f, ", ")?;
+                self.entry(f, k, v)?;
             }
             write!(f, ")")?;
+            has_content = true;
+        }
+
+        // 5. Repeat count, unless a counter range already shows it
+        if let Some(RepeatView { count, range: None }) = self.repeat.filter(|r| r.count > 1) {
+            if has_content {
+                write!(f, " ")?;
+            }
+            write!(f, "(x{})", count)?;
         }
 
         Ok(())
     }
+
+    /// Writes one metadata entry, as a range when it is the folded counter.
+    fn entry(
+        &self,
+        f: &mut core::fmt::Formatter<'_>,
+        key: &str,
+        value: &MetadataValue,
+    ) -> core::fmt::Result {
+        match self.repeat.and_then(|r| r.range).filter(|&(k, _)| k == key) {
+            Some((_, first)) => {
+                write!(f, "{}={}..{}", key, self.value(key, first), self.value(key, value))
+            },
+            None => write!(f, "{}={}", key, self.value(key, value)),
+        }
+    }
+
+    #[inline]
+    fn value<'v>(&self, key: &str, value: &'v MetadataValue) -> &'v MetadataValue {
+        if !self.reveal && self.group.is_secret_key(key) {
+            &REDACTED_VALUE
+        } else {
+            value
+        }
+    }
 }
 
 impl core::fmt::Display for ContextRenderer<'_> {
@@ -302,15 +803,159 @@ impl core::fmt::Display for ContextRenderer<'_> {
 
 impl Display for ErrorContext {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
-        match self {
-            Self::Simple(s) => write!(f, "{}", s.as_ref()),
-            Self::Group(g) => ContextRenderer::new(g).render(f),
-        }
+        self.render(f, false, None)
     }
 }
 
 impl core::error::Error for ErrorContext {}
 
+#[cfg(feature = "serde")]
+impl Serialize for ErrorContext {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        ContextSer { context: self, reveal: false }.serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl Serialize for GroupContext {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        GroupSer { group: self, reveal: false }.serialize(serializer)
+    }
+}
+
+/// Serializes a context, redacting sensitive parts unless `reveal` is set.
+#[cfg(feature = "serde")]
+pub(crate) struct ContextSer<'a> {
+    pub(crate) context: &'a ErrorContext,
+    pub(crate) reveal: bool,
+}
+
+#[cfg(feature = "serde")]
+impl Serialize for ContextSer<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self.context {
+            ErrorContext::Simple(s) => {
+                serializer.serialize_newtype_variant("ErrorContext", 0, "Simple", s)
+            },
+            ErrorContext::Group(g) => serializer.serialize_newtype_variant(
+                "ErrorContext",
+                1,
+                "Group",
+                &GroupSer { group: g, reveal: self.reveal },
+            ),
+            ErrorContext::Custom(payload) => {
+                serializer.serialize_newtype_variant("ErrorContext", 2, "Custom", &**payload)
+            },
+        }
+    }
+}
+
+/// Serializes a context stack, redacting sensitive parts unless `reveal` is set.
+#[cfg(feature = "serde")]
+pub(crate) struct ContextsSer<'a> {
+    pub(crate) contexts: &'a [ErrorContext],
+    pub(crate) reveal: bool,
+}
+
+#[cfg(feature = "serde")]
+impl Serialize for ContextsSer<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(
+            self.contexts
+                .iter()
+                .map(|context| ContextSer { context, reveal: self.reveal }),
+        )
+    }
+}
+
+#[cfg(feature = "serde")]
+struct GroupSer<'a> {
+    group: &'a GroupContext,
+    reveal: bool,
+}
+
+#[cfg(feature = "serde")]
+impl Serialize for GroupSer<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        #[derive(Serialize)]
+        #[serde(rename = "GroupContext")]
+        struct GroupContextRef<'a> {
+            message: Option<&'a str>,
+            #[serde(skip_serializing_if = "Option::is_none")]
+            message_key: Option<&'a str>,
+            location: &'a Option<Location>,
+            tags: &'a [Cow<'static, str>],
+            metadata: MetadataSer<'a>,
+            #[serde(skip_serializing_if = "Option::is_none")]
+            severity: Option<Severity>,
+            #[serde(skip_serializing_if = "Option::is_none")]
+            timestamp: Option<Timestamp>,
+            #[serde(skip_serializing_if = "core::ops::Not::not")]
+            secret_message: bool,
+            #[serde(skip_serializing_if = "<[_]>::is_empty")]
+            secret_keys: &'a [Cow<'static, str>],
+            #[serde(skip_serializing_if = "is_internal")]
+            visibility: Visibility,
+            #[serde(skip_serializing_if = "Option::is_none")]
+            repeat: Option<RepeatRef<'a>>,
+        }
+
+        #[derive(Serialize)]
+        #[serde(rename = "ContextRepeat")]
+        struct RepeatRef<'a> {
+            count: usize,
+            #[serde(skip_serializing_if = "Option::is_none")]
+            range: Option<RangeRef<'a>>,
+        }
+
+        #[derive(Serialize)]
+        #[serde(rename = "CounterRange")]
+        struct RangeRef<'a> {
+            key: &'a str,
+            first: &'a MetadataValue,
+        }
+
+        #[inline]
+        fn is_internal(visibility: &Visibility) -> bool {
+            !visibility.is_public()
+        }
+
+        struct MetadataSer<'a>(&'a GroupSer<'a>);
+
+        impl Serialize for MetadataSer<'_> {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                let GroupSer { group, reveal } = *self.0;
+                if reveal {
+                    serializer.collect_seq(&group.metadata)
+                } else {
+                    serializer.collect_seq(group.redacted_metadata())
+                }
+            }
+        }
+
+        let g = self.group;
+        GroupContextRef {
+            message: if self.reveal { g.message.as_deref() } else { g.redacted_message() },
+            message_key: g.message_key.as_deref(),
+            location: &g.location,
+            tags: &g.tags,
+            metadata: MetadataSer(self),
+            severity: g.severity,
+            timestamp: g.timestamp,
+            secret_message: g.secret_message,
+            secret_keys: &g.secret_keys,
+            visibility: g.visibility,
+            repeat: g.repeat.as_ref().map(|repeat| RepeatRef {
+                count: repeat.count,
+                range: g
+                    .redacted_range(self.reveal)
+                    .map(|(key, first)| RangeRef { key, first }),
+            }),
+        }
+        .serialize(serializer)
+    }
+}
+
 /// Builder for creating complex [`ErrorContext::Group`] entries.
 ///
 /// # Examples
@@ -367,6 +1012,29 @@ impl ErrorContextBuilder {
         self
     }
 
+    /// Sets the catalog key of a localized message; see [`ErrorContext::i18n`].
+    ///
+    /// A plain [`message`](Self::message) set as well is used when no catalog
+    /// is given or the key cannot be resolved.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use error_rail::ErrorContext;
+    ///
+    /// let ctx = ErrorContext::builder()
+    ///     .message_key("cart.empty")
+    ///     .message("cart is empty")
+    ///     .tag("checkout")
+    ///     .build();
+    /// assert_eq!(ctx.message(), "[checkout] cart is empty");
+    /// ```
+    #[inline]
+    pub fn message_key<S: Into<Cow<'static, str>>>(mut self, key: S) -> Self {
+        self.context.message_key = Some(key.into());
+        self
+    }
+
     /// Sets the source location for this context.
     ///
     /// Typically used with the `file!()` and `line!()` macros to automatically
@@ -388,7 +1056,30 @@ impl ErrorContextBuilder {
     /// ```
     #[inline]
     pub fn location<S: Into<Cow<'static, str>>>(mut self, file: S, line: u32) -> Self {
-        self.context.location = Some(Location { file: file.into(), line });
+        self.context.location = Some(Location::new(file, line));
+        self
+    }
+
+    /// Sets the source location to the caller of this method, including its column.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use error_rail::ErrorContext;
+    ///
+    /// let ctx = ErrorContext::builder().caller_location().message("parsing").build();
+    /// assert!(ctx.message().contains(file!()));
+    /// ```
+    #[inline]
+    #[track_caller]
+    pub fn caller_location(self) -> Self {
+        self.at(Location::caller())
+    }
+
+    /// Sets the source location from a [`Location`].
+    #[inline]
+    pub fn at(mut self, location: Location) -> Self {
+        self.context.location = Some(location);
         self
     }
 
@@ -421,7 +1112,8 @@ impl ErrorContextBuilder {
     ///
     /// Metadata provides structured information that can be parsed by log
     /// aggregators or monitoring tools. Multiple metadata pairs can be added
-    /// by calling this method multiple times.
+    /// by calling this method multiple times. Values keep their type; see
+    /// [`MetadataValue`].
     ///
     /// # Arguments
     ///
@@ -434,12 +1126,14 @@ impl ErrorContextBuilder {
     /// use error_rail::ErrorContext;
     ///
     /// let ctx = ErrorContext::builder()
-    ///     .metadata("user_id", "12345")
+    ///     .metadata("user_id", 12345)
     ///     .metadata("request_id", "abc-def")
+    ///     .metadata("cached", false)
     ///     .build();
+    /// assert_eq!(ctx.message(), "(user_id=12345, request_id=abc-def, cached=false)");
     /// ```
     #[inline]
-    pub fn metadata<K: Into<Cow<'static, str>>, V: Into<Cow<'static, str>>>(
+    pub fn metadata<K: Into<Cow<'static, str>>, V: Into<MetadataValue>>(
         mut self,
         key: K,
         value: V,
@@ -448,6 +1142,148 @@ impl ErrorContextBuilder {
         self
     }
 
+    /// Adds a metadata entry through a typed [`ContextKey`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use error_rail::types::ContextKey;
+    /// use error_rail::ErrorContext;
+    ///
+    /// const USER_ID: ContextKey<u64> = ContextKey::new("user_id");
+    ///
+    /// let ctx = ErrorContext::builder().set(USER_ID, 42).build();
+    /// assert_eq!(ctx.metadata_value(USER_ID), Some(42));
+    /// ```
+    #[inline]
+    pub fn set<T: Into<MetadataValue>>(self, key: ContextKey<T>, value: T) -> Self {
+        self.metadata(key.name(), value)
+    }
+
+    /// Adds a metadata pair whose value is sensitive.
+    ///
+    /// The value is rendered, serialized and fingerprinted as `[REDACTED]`
+    /// unless an [`Unredacted`] view is used.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use error_rail::ErrorContext;
+    ///
+    /// let ctx = ErrorContext::builder()
+    ///     .metadata("user_id", "42")
+    ///     .secret_metadata("token", "s3cr3t")
+    ///     .build();
+    /// assert_eq!(ctx.message(), "(user_id=42, token=[REDACTED])");
+    /// ```
+    #[inline]
+    pub fn secret_metadata<K: Into<Cow<'static, str>>, V: Into<MetadataValue>>(
+        mut self,
+        key: K,
+        value: V,
+    ) -> Self {
+        let key = key.into();
+        if !self.context.secret_keys.contains(&key) {
+            self.context.secret_keys.push(key.clone());
+        }
+        self.context.metadata.push((key, value.into()));
+        self
+    }
+
+    /// Sets a message that is sensitive and rendered as `[REDACTED]`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use error_rail::ErrorContext;
+    ///
+    /// let ctx = ErrorContext::builder()
+    ///     .tag("billing")
+    ///     .secret_message("account DE89 3704 0044 0532 0130 00")
+    ///     .build();
+    /// assert_eq!(ctx.message(), "[billing] [REDACTED]");
+    /// ```
+    #[inline]
+    pub fn secret_message<S: Into<Cow<'static, str>>>(mut self, msg: S) -> Self {
+        self.context.message = Some(msg.into());
+        self.context.secret_message = true;
+        self
+    }
+
+    /// Sets the severity of this context.
+    ///
+    /// Context severities are not rendered, but take part in
+    /// [`ComposableError::max_severity`](crate::types::ComposableError::max_severity).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use error_rail::ErrorContext;
+    /// use error_rail::types::Severity;
+    ///
+    /// let ctx = ErrorContext::builder()
+    ///     .tag("payments")
+    ///     .severity(Severity::Critical)
+    ///     .build();
+    /// ```
+    #[inline]
+    pub fn severity(mut self, severity: Severity) -> Self {
+        self.context.severity = Some(severity);
+        self
+    }
+
+    /// Sets the time at which this context was attached.
+    ///
+    /// Timestamps are only rendered when
+    /// [`show_timestamps`](crate::types::error_formatter::ErrorFormatBuilder::show_timestamps)
+    /// is enabled.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use error_rail::ErrorContext;
+    /// use error_rail::types::{Clock, ManualClock, Timestamp};
+    ///
+    /// let clock = ManualClock::new(Timestamp::from_unix_millis(1_000));
+    /// let ctx = ErrorContext::builder()
+    ///     .message("attempt 2")
+    ///     .timestamp(clock.now())
+    ///     .build();
+    /// assert_eq!(ctx.timestamp(), Some(Timestamp::from_unix_millis(1_000)));
+    /// ```
+    #[inline]
+    pub fn timestamp(mut self, timestamp: Timestamp) -> Self {
+        self.context.timestamp = Some(timestamp);
+        self
+    }
+
+    /// Marks this context as safe to show to end users; see
+    /// [`visibility`](crate::types::visibility).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use error_rail::ErrorContext;
+    ///
+    /// let ctx = ErrorContext::builder()
+    ///     .message("checking out")
+    ///     .metadata("items", 3)
+    ///     .public()
+    ///     .build();
+    /// assert!(ctx.is_public());
+    /// ```
+    #[inline]
+    pub fn public(self) -> Self {
+        self.visibility(Visibility::Public)
+    }
+
+    /// Sets who may see this context (default: [`Visibility::Internal`]).
+    #[inline]
+    pub fn visibility(mut self, visibility: Visibility) -> Self {
+        self.context.visibility = visibility;
+        self
+    }
+
     /// Builds and returns the final [`ErrorContext`].
     ///
     /// Consumes the builder and produces an [`ErrorContext::Group`] variant
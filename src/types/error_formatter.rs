@@ -1,7 +1,13 @@
 //! Error chain formatting utilities.
 
 use crate::types::alloc_type;
-use crate::types::ComposableError;
+use crate::types::compaction::RepeatView;
+use crate::types::context_limit::Elided;
+use crate::types::i18n::{Localizer, MessageCatalog};
+use crate::types::{
+    ComposableError, ContextCompaction, ErrorBacktrace, ErrorCause, ErrorCode, ErrorContext,
+    Location, Severity, Timestamp, TraceIds,
+};
 use core::fmt::Display;
 
 #[cfg(not(feature = "std"))]
@@ -40,6 +46,23 @@ pub struct ErrorFormatConfig {
     pub indent: String,
     pub show_code: bool,
     pub cascade: bool,
+    /// Prefix written before the first item of each nested cause layer.
+    pub cause_prefix: String,
+    /// Whether printable attachments are rendered after the core error.
+    pub show_attachments: bool,
+    /// Whether a layer's severity is shown as a prefix (e.g. `"warning: "`).
+    pub show_severity: bool,
+    /// Whether recorded timestamps are shown as a prefix of each item.
+    pub show_timestamps: bool,
+    /// Whether trace, span and correlation IDs are shown after a layer's core error.
+    pub show_trace_ids: bool,
+    /// Whether captured source locations are shown after each context and a
+    /// layer's core error.
+    pub show_locations: bool,
+    /// Whether attached backtraces are rendered, one item per frame, after a layer's core error.
+    pub show_backtrace: bool,
+    /// Folds runs of repeated contexts when rendering; see [`compaction`](crate::types::compaction).
+    pub compaction: Option<ContextCompaction>,
 }
 
 impl Default for ErrorFormatConfig {
@@ -55,6 +78,14 @@ impl Default for ErrorFormatConfig {
             indent: "  ".into(),
             show_code: true,
             cascade: false,
+            cause_prefix: "caused by: ".into(),
+            show_attachments: false,
+            show_severity: true,
+            show_timestamps: false,
+            show_trace_ids: true,
+            show_locations: false,
+            show_backtrace: false,
+            compaction: None,
         }
     }
 }
@@ -72,6 +103,19 @@ impl ErrorFormatConfig {
         }
     }
 
+    /// Tree-style formatting that also shows attachments, timestamps, locations
+    /// and backtraces.
+    #[inline]
+    pub fn verbose() -> Self {
+        Self {
+            show_attachments: true,
+            show_timestamps: true,
+            show_locations: true,
+            show_backtrace: true,
+            ..Self::pretty()
+        }
+    }
+
     /// Indented cascade formatting.
     #[inline]
     pub fn cascaded() -> Self {
@@ -110,7 +154,29 @@ impl ErrorFormatConfig {
         }
     }
 
-    fn format_multiline_tree(&self, items: &[&dyn Display]) -> String {
+    /// Formats pre-built chain items, honoring their tree depth in multiline modes.
+    pub(crate) fn format_items(&self, items: &[ChainItem<'_>]) -> String {
+        if items.is_empty() {
+            return String::new();
+        }
+
+        if self.multiline && self.context_prefix.is_some() {
+            self.format_multiline_tree(items)
+        } else if self.cascade {
+            self.format_cascade(items)
+        } else {
+            self.format_linear(items)
+        }
+    }
+
+    #[inline]
+    fn push_indent(&self, result: &mut String, levels: usize) {
+        for _ in 0..levels {
+            result.push_str(&self.indent);
+        }
+    }
+
+    fn format_multiline_tree(&self, items: &[ChainItem<'_>]) -> String {
         let len = items.len();
         let mut result = String::with_capacity(len * 40);
 
@@ -119,6 +185,7 @@ impl ErrorFormatConfig {
 
         for (i, item) in items.iter().enumerate().skip(1) {
             result.push_str(&self.separator);
+            self.push_indent(&mut result, item.depth);
             let is_last = i == len - 1;
             if let Some(p) = self.get_prefix(is_last) {
                 result.push_str(p);
@@ -129,16 +196,14 @@ impl ErrorFormatConfig {
         result
     }
 
-    fn format_cascade(&self, items: &[&dyn Display]) -> String {
+    fn format_cascade(&self, items: &[ChainItem<'_>]) -> String {
         let len = items.len();
         let mut result = String::with_capacity(len * 32);
 
         for (i, item) in items.iter().enumerate() {
             if i > 0 {
                 result.push_str(&self.separator);
-                for _ in 0..i {
-                    result.push_str(&self.indent);
-                }
+                self.push_indent(&mut result, item.level);
             }
             result.push_str(&item.to_string());
         }
@@ -146,7 +211,7 @@ impl ErrorFormatConfig {
         result
     }
 
-    fn format_linear(&self, items: &[&dyn Display]) -> String {
+    fn format_linear(&self, items: &[ChainItem<'_>]) -> String {
         let len = items.len();
         let last_idx = len - 1;
         let mut result = String::with_capacity(len * 32);
@@ -206,18 +271,11 @@ impl ErrorFormatter for ErrorFormatConfig {
     }
 
     fn format_chain<'a>(&self, chain: impl Iterator<Item = &'a dyn Display>) -> String {
-        let items: Vec<_> = chain.collect();
-        if items.is_empty() {
-            return String::new();
-        }
-
-        if self.multiline && self.context_prefix.is_some() {
-            self.format_multiline_tree(&items)
-        } else if self.cascade {
-            self.format_cascade(&items)
-        } else {
-            self.format_linear(&items)
-        }
+        let items: Vec<_> = chain
+            .enumerate()
+            .map(|(i, item)| ChainItem::plain(item, i))
+            .collect();
+        self.format_items(&items)
     }
 }
 
@@ -226,12 +284,20 @@ pub struct ErrorFormatBuilder<'a, E> {
     pub(crate) error: &'a ComposableError<E>,
     pub(crate) config: ErrorFormatConfig,
     pub(crate) reverse_context: bool,
+    pub(crate) unredacted: bool,
+    pub(crate) localizer: Option<Localizer<'a>>,
 }
 
 impl<'a, E> ErrorFormatBuilder<'a, E> {
     #[inline]
     pub fn new(error: &'a ComposableError<E>) -> Self {
-        Self { error, config: ErrorFormatConfig::default(), reverse_context: false }
+        Self {
+            error,
+            config: ErrorFormatConfig::default(),
+            reverse_context: false,
+            unredacted: false,
+            localizer: None,
+        }
     }
 
     #[inline]
@@ -264,6 +330,13 @@ impl<'a, E> ErrorFormatBuilder<'a, E> {
         self
     }
 
+    /// Uses [`ErrorFormatConfig::verbose`], which also renders backtraces.
+    #[inline]
+    pub fn verbose(mut self) -> Self {
+        self.config = ErrorFormatConfig::verbose();
+        self
+    }
+
     #[inline]
     pub fn cascade(mut self, enabled: bool) -> Self {
         self.config.cascade = enabled;
@@ -281,6 +354,479 @@ impl<'a, E> ErrorFormatBuilder<'a, E> {
         self.config = ErrorFormatConfig::cascaded();
         self
     }
+
+    /// Sets the prefix written before each nested cause layer (default: `"caused by: "`).
+    ///
+    /// Suppressed layers are always prefixed with `"suppressed: "`; see
+    /// [`ComposableError::merge`].
+    #[inline]
+    pub fn cause_prefix(mut self, prefix: impl Into<alloc_type::String>) -> Self {
+        self.config.cause_prefix = prefix.into();
+        self
+    }
+
+    /// Renders attachments added with `attach_display`/`attach_debug` (default: false).
+    #[inline]
+    pub fn show_attachments(mut self, show: bool) -> Self {
+        self.config.show_attachments = show;
+        self
+    }
+
+    /// Shows each layer's severity as a prefix (default: true).
+    #[inline]
+    pub fn show_severity(mut self, show: bool) -> Self {
+        self.config.show_severity = show;
+        self
+    }
+
+    /// Shows the recorded timestamp of each context and layer as a prefix (default: false).
+    #[inline]
+    pub fn show_timestamps(mut self, show: bool) -> Self {
+        self.config.show_timestamps = show;
+        self
+    }
+
+    /// Shows trace, span and correlation IDs after each layer's core error (default: true).
+    #[inline]
+    pub fn show_trace_ids(mut self, show: bool) -> Self {
+        self.config.show_trace_ids = show;
+        self
+    }
+
+    /// Shows where each layer entered the rail, after its core error, and where
+    /// each context was attached, after the context (default: false).
+    ///
+    /// Locations are captured with the `caller-location` feature; see
+    /// [`caller_location`](crate::types::caller_location).
+    #[inline]
+    pub fn show_locations(mut self, show: bool) -> Self {
+        self.config.show_locations = show;
+        self
+    }
+
+    /// Renders attached backtraces after each layer's core error (default: false).
+    #[inline]
+    pub fn show_backtrace(mut self, show: bool) -> Self {
+        self.config.show_backtrace = show;
+        self
+    }
+
+    /// Folds runs of repeated contexts into one item when rendering (default: none).
+    ///
+    /// The error's stored contexts are left as they are; see
+    /// [`compaction`](crate::types::compaction).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use error_rail::{ComposableError, ErrorContext};
+    /// use error_rail::types::ContextCompaction;
+    ///
+    /// let err = ComposableError::new("timeout")
+    ///     .with_contexts((1..=3).map(|attempt| ErrorContext::metadata("retry_attempt", attempt)));
+    ///
+    /// let compaction = ContextCompaction::new().counter_key("retry_attempt");
+    /// assert_eq!(
+    ///     err.fmt().compaction(compaction).to_string(),
+    ///     "(retry_attempt=1..3) -> timeout"
+    /// );
+    /// assert_eq!(err.context().len(), 3);
+    /// ```
+    #[inline]
+    pub fn compaction(mut self, compaction: ContextCompaction) -> Self {
+        self.config.compaction = Some(compaction);
+        self
+    }
+
+    /// Renders sensitive context messages and metadata values in clear text (default: false).
+    ///
+    /// This is a privileged mode for sinks that may see secrets; see
+    /// [`redaction`](crate::types::redaction). Unlike the layout presets, it is
+    /// kept when switching to [`pretty`](Self::pretty) or [`cascaded`](Self::cascaded).
+    #[inline]
+    pub fn unredacted(mut self, unredacted: bool) -> Self {
+        self.unredacted = unredacted;
+        self
+    }
+
+    /// Renders localized contexts in `locale`, resolving their message keys in `catalog`.
+    ///
+    /// Keys missing from every locale of the fallback chain render as they do
+    /// without a catalog; see [`i18n`](crate::types::i18n).
+    #[inline]
+    pub fn locale(mut self, catalog: &'a dyn MessageCatalog, locale: &'a str) -> Self {
+        self.localizer = Some(Localizer { catalog, locale });
+        self
+    }
+}
+
+/// A single rendered entry of an error chain: a context or a layer's core error.
+pub(crate) struct ChainItem<'a> {
+    entry: Entry<'a>,
+    code: Option<&'a dyn ErrorCode>,
+    trace: Option<&'a TraceIds>,
+    location: Option<&'a Location>,
+    prefix: Option<&'a str>,
+    severity: Option<Severity>,
+    timestamp: Option<Timestamp>,
+    /// Depth of the cause layer this item belongs to (0 for the outer error).
+    depth: usize,
+    /// Indentation level used by cascaded output.
+    level: usize,
+}
+
+impl<'a> ChainItem<'a> {
+    #[inline]
+    fn plain(item: &'a dyn Display, level: usize) -> Self {
+        Self {
+            entry: Entry::Item(item),
+            code: None,
+            trace: None,
+            location: None,
+            prefix: None,
+            severity: None,
+            timestamp: None,
+            depth: 0,
+            level,
+        }
+    }
+}
+
+impl Display for ChainItem<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        if let Some(prefix) = self.prefix {
+            f.write_str(prefix)?;
+        }
+        if let Some(timestamp) = self.timestamp {
+            write!(f, "{} ", timestamp)?;
+        }
+        if let Some(severity) = self.severity {
+            write!(f, "{}: ", severity)?;
+        }
+        match self.entry {
+            Entry::Item(item) => Display::fmt(item, f)?,
+            Entry::Context { context, reveal, localizer, repeat } => {
+                context.render_repeated(f, reveal, localizer, repeat)?
+            },
+            Entry::Elided(count) => Display::fmt(&Elided(count), f)?,
+        }
+        if let Some(code) = self.code {
+            write!(f, " (code: {})", code)?;
+        }
+        if let Some(trace) = self.trace {
+            write!(f, " ({})", trace)?;
+        }
+        if let Some(location) = self.location {
+            write!(f, " (at {})", location)?;
+        }
+        Ok(())
+    }
+}
+
+/// What a [`ChainItem`] renders.
+#[derive(Clone, Copy)]
+enum Entry<'a> {
+    /// A core error, cause message or attachment.
+    Item(&'a dyn Display),
+    /// A context, with its sensitive parts shown when `reveal` is set, standing
+    /// for a run of `repeat` folded contexts when compacted at render time.
+    Context {
+        context: &'a ErrorContext,
+        reveal: bool,
+        localizer: Option<Localizer<'a>>,
+        repeat: Option<RepeatView<'a>>,
+    },
+    /// Marker standing in for contexts dropped by a context limit.
+    Elided(usize),
+}
+
+/// Flattens an error and its cause tree into displayable items, outermost layer first.
+///
+/// When nothing follows the outer core error, its own code and trace IDs are left
+/// for the caller to append so single-layer output stays unchanged (see
+/// [`has_trailing_items`]). Cause layers always carry them inline.
+pub(crate) fn chain_items<'a, E: Display>(
+    error: &'a ComposableError<E>,
+    options: ChainOptions<'a>,
+) -> Vec<ChainItem<'a>> {
+    let mut builder = ChainBuilder { items: Vec::with_capacity(error.context.len() + 1), options };
+
+    let trailing = has_trailing_items(error, &options);
+    let outer_code = if options.show_code && trailing { error.code() } else { None };
+    let core_level = builder.push_layer(
+        (&error.context, error.sites(), error.elision()),
+        &error.core_error,
+        outer_code,
+        None,
+        0,
+        0,
+    );
+    if options.show_trace_ids && trailing {
+        builder.mark_trace(error.trace_ids());
+    }
+    if trailing {
+        builder.mark_location(error.location());
+    }
+    builder.mark_timestamp(error.timestamp());
+    builder.mark_severity(0, error.severity());
+
+    if options.show_attachments {
+        for attachment in error.attachment_list().iter().filter(|a| a.is_printable()) {
+            builder.items.push(ChainItem {
+                entry: Entry::Item(attachment),
+                code: None,
+                trace: None,
+                location: None,
+                prefix: Some("attachment: "),
+                severity: None,
+                timestamp: None,
+                depth: 0,
+                level: core_level + 1,
+            });
+        }
+    }
+    builder.push_backtrace(error.backtrace(), 0, core_level + 1);
+    builder.push_causes(error.children(), 1, core_level + 1);
+
+    builder.items
+}
+
+/// Returns `true` if any item is rendered after the outer core error.
+pub(crate) fn has_trailing_items<E>(
+    error: &ComposableError<E>,
+    options: &ChainOptions<'_>,
+) -> bool {
+    !error.children().is_empty()
+        || (options.show_attachments && error.attachment_list().iter().any(|a| a.is_printable()))
+        || (options.show_backtrace && error.backtrace().is_some())
+}
+
+/// Options controlling which parts of an error end up in its chain items.
+#[derive(Clone, Copy)]
+pub(crate) struct ChainOptions<'a> {
+    pub(crate) reverse_context: bool,
+    pub(crate) show_code: bool,
+    pub(crate) show_attachments: bool,
+    pub(crate) show_severity: bool,
+    pub(crate) show_timestamps: bool,
+    pub(crate) show_trace_ids: bool,
+    pub(crate) show_locations: bool,
+    pub(crate) show_backtrace: bool,
+    pub(crate) unredacted: bool,
+    pub(crate) localizer: Option<Localizer<'a>>,
+    pub(crate) cause_prefix: &'a str,
+    pub(crate) compaction: Option<&'a ContextCompaction>,
+}
+
+impl ChainOptions<'static> {
+    /// Contexts, core errors and cause prefixes only, as used by custom formatters.
+    pub(crate) const PLAIN: Self = Self {
+        reverse_context: false,
+        show_code: false,
+        show_attachments: false,
+        show_severity: false,
+        show_timestamps: false,
+        show_trace_ids: false,
+        show_locations: false,
+        show_backtrace: false,
+        unredacted: false,
+        localizer: None,
+        cause_prefix: "caused by: ",
+        compaction: None,
+    };
+}
+
+/// Contexts of a layer in push order, where each was attached, and the
+/// elision of its dropped contexts.
+type LayerContexts<'a> = (&'a [ErrorContext], &'a [Option<Location>], Option<(usize, usize)>);
+
+struct ChainBuilder<'a> {
+    items: Vec<ChainItem<'a>>,
+    options: ChainOptions<'a>,
+}
+
+impl<'a> ChainBuilder<'a> {
+    /// Pushes one layer and returns the cascade level of its core error.
+    ///
+    /// `sites` are where the contexts were attached, and `elision` is where
+    /// dropped contexts were removed, in push order, and how many.
+    fn push_layer(
+        &mut self,
+        (contexts, sites, elision): LayerContexts<'a>,
+        core: &'a dyn Display,
+        code: Option<&'a dyn ErrorCode>,
+        mut prefix: Option<&'a str>,
+        depth: usize,
+        mut level: usize,
+    ) -> usize {
+        let reveal = self.options.unredacted;
+        let localizer = self.options.localizer;
+        let show_timestamps = self.options.show_timestamps;
+        let sites = if self.options.show_locations { sites } else { &[] };
+        let site = |i: usize| sites.get(i).and_then(Option::as_ref);
+        let mut push = |entry: Entry<'a>, timestamp: Option<Timestamp>, location| {
+            self.items.push(ChainItem {
+                entry,
+                code: None,
+                trace: None,
+                location,
+                prefix: prefix.take(),
+                severity: None,
+                timestamp: if show_timestamps { timestamp } else { None },
+                depth,
+                level,
+            });
+            level += 1;
+        };
+        // Runs are folded on each side of the elision marker, never across it.
+        let (contexts, elision) = match self.options.compaction {
+            None => (
+                contexts
+                    .iter()
+                    .enumerate()
+                    .map(|(i, ctx)| (i, ctx, None))
+                    .collect::<Vec<_>>(),
+                elision,
+            ),
+            Some(compaction) => {
+                let slot = elision.map_or(contexts.len(), |(slot, _)| slot);
+                let (older, newer) = contexts.split_at(slot);
+                let mut runs = compaction.runs(older).collect::<Vec<_>>();
+                let elision = elision.map(|(_, count)| (runs.len(), count));
+                runs.extend(
+                    compaction
+                        .runs(newer)
+                        .map(|(i, ctx, repeat)| (slot + i, ctx, repeat)),
+                );
+                (runs, elision)
+            },
+        };
+        let marker_at = |at: usize| {
+            elision
+                .filter(|&(slot, _)| slot == at)
+                .map(|(_, count)| Entry::Elided(count))
+        };
+        // The marker sits where the dropped contexts were, in push order.
+        if self.options.reverse_context {
+            for (i, &(at, ctx, repeat)) in contexts.iter().enumerate() {
+                if let Some(marker) = marker_at(i) {
+                    push(marker, None, None);
+                }
+                let entry = Entry::Context { context: ctx, reveal, localizer, repeat };
+                push(entry, ctx.timestamp(), site(at));
+            }
+            if let Some(marker) = marker_at(contexts.len()) {
+                push(marker, None, None);
+            }
+        } else {
+            for (i, &(at, ctx, repeat)) in contexts.iter().enumerate().rev() {
+                if let Some(marker) = marker_at(i + 1) {
+                    push(marker, None, None);
+                }
+                let entry = Entry::Context { context: ctx, reveal, localizer, repeat };
+                push(entry, ctx.timestamp(), site(at));
+            }
+            if let Some(marker) = marker_at(0) {
+                push(marker, None, None);
+            }
+        }
+        self.items.push(ChainItem {
+            entry: Entry::Item(core),
+            code,
+            trace: None,
+            location: None,
+            prefix,
+            severity: None,
+            timestamp: None,
+            depth,
+            level,
+        });
+        level
+    }
+
+    /// Shows `trace` on the most recently pushed item, a layer's core error.
+    #[inline]
+    fn mark_trace(&mut self, trace: Option<&'a TraceIds>) {
+        if let Some(item) = self.items.last_mut() {
+            item.trace = trace;
+        }
+    }
+
+    /// Shows `location` on the most recently pushed item, a layer's core error.
+    #[inline]
+    fn mark_location(&mut self, location: Option<&'a Location>) {
+        if self.options.show_locations {
+            if let Some(item) = self.items.last_mut() {
+                item.location = location;
+            }
+        }
+    }
+
+    /// Shows `timestamp` on the most recently pushed item, a layer's core error.
+    #[inline]
+    fn mark_timestamp(&mut self, timestamp: Option<Timestamp>) {
+        if self.options.show_timestamps {
+            if let Some(item) = self.items.last_mut() {
+                item.timestamp = timestamp;
+            }
+        }
+    }
+
+    /// Shows `severity` on the item at `index`, the first item of a layer.
+    #[inline]
+    fn mark_severity(&mut self, index: usize, severity: Option<Severity>) {
+        if self.options.show_severity {
+            self.items[index].severity = severity;
+        }
+    }
+
+    /// Pushes one item per backtrace frame, below a layer's core error.
+    fn push_backtrace(
+        &mut self,
+        backtrace: Option<&'a ErrorBacktrace>,
+        depth: usize,
+        level: usize,
+    ) {
+        let Some(backtrace) = backtrace.filter(|_| self.options.show_backtrace) else {
+            return;
+        };
+        for (i, frame) in backtrace.frames().iter().enumerate() {
+            self.items.push(ChainItem {
+                entry: Entry::Item(frame),
+                code: None,
+                trace: None,
+                location: None,
+                prefix: (i == 0).then_some("backtrace: "),
+                severity: None,
+                timestamp: None,
+                depth,
+                level,
+            });
+        }
+    }
+
+    fn push_causes(&mut self, causes: &'a [ErrorCause], depth: usize, level: usize) {
+        for cause in causes {
+            let code = if self.options.show_code { cause.code() } else { None };
+            let start = self.items.len();
+            let core_level = self.push_layer(
+                (&cause.context, cause.context_sites.of(cause.context.len()), cause.elision()),
+                &cause.message,
+                code,
+                Some(if cause.suppressed { "suppressed: " } else { self.options.cause_prefix }),
+                depth,
+                level,
+            );
+            if self.options.show_trace_ids {
+                self.mark_trace(cause.trace_ids.as_ref());
+            }
+            self.mark_location(cause.location.as_ref());
+            self.mark_timestamp(cause.timestamp);
+            self.mark_severity(start, cause.severity);
+            self.push_backtrace(cause.backtrace.as_ref(), depth, core_level + 1);
+            self.push_causes(&cause.causes, depth + 1, core_level + 1);
+        }
+    }
 }
 
 impl<'a, E> Display for ErrorFormatBuilder<'a, E>
@@ -288,28 +834,39 @@ where
     E: Display,
 {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
-        let ctx_count = self.error.context_iter().count();
-        let mut items: Vec<&dyn Display> = Vec::with_capacity(ctx_count + 1);
-
-        if self.reverse_context {
-            items.extend(
-                self.error
-                    .context_iter()
-                    .rev()
-                    .map(|ctx| ctx as &dyn Display),
-            );
-        } else {
-            items.extend(self.error.context_iter().map(|ctx| ctx as &dyn Display));
-        }
-        items.push(self.error.core_error());
-
-        let formatted = self.config.format_chain(items.iter().copied());
+        let options = ChainOptions {
+            reverse_context: self.reverse_context,
+            show_code: self.config.show_code,
+            show_attachments: self.config.show_attachments,
+            show_severity: self.config.show_severity,
+            show_timestamps: self.config.show_timestamps,
+            show_trace_ids: self.config.show_trace_ids,
+            show_locations: self.config.show_locations,
+            show_backtrace: self.config.show_backtrace,
+            unredacted: self.unredacted,
+            localizer: self.localizer,
+            cause_prefix: &self.config.cause_prefix,
+            compaction: self.config.compaction.as_ref(),
+        };
+        let items = chain_items(self.error, options);
+
+        let formatted = self.config.format_items(&items);
         f.write_str(&formatted)?;
 
-        if self.config.show_code {
-            if let Some(code) = self.error.error_code() {
+        if !has_trailing_items(self.error, &options) {
+            if let Some(code) = self.error.code().filter(|_| self.config.show_code) {
                 write!(f, " (code: {})", code)?;
             }
+            if let Some(trace) = self
+                .error
+                .trace_ids()
+                .filter(|_| self.config.show_trace_ids)
+            {
+                write!(f, " ({})", trace)?;
+            }
+            if let Some(location) = self.error.location().filter(|_| self.config.show_locations) {
+                write!(f, " (at {})", location)?;
+            }
         }
 
         Ok(())
@@ -1,9 +1,14 @@
 use crate::traits::TransientError;
-use crate::types::accumulator::Accumulator;
-use crate::types::alloc_type::Box;
-use crate::types::composable_error::ComposableError;
+use crate::types::alloc_type::{Box, Vec};
+use crate::types::backtrace::ErrorBacktrace;
+use crate::types::caller_location::{self, ContextSites};
+use crate::types::compaction::ContextCompaction;
+use crate::types::composable_error::{Attachment, ComposableError};
+use crate::types::context_limit::{self, ContextLimit};
+use crate::types::context_query;
 use crate::types::lazy_context::LazyGroupContext;
 use crate::types::marked_error::MarkedError;
+use crate::types::{ErrorVec, Location, MetadataValue};
 use crate::{ComposableResult, ErrorContext, IntoErrorContext};
 
 use crate::types::utils::u32_to_cow;
@@ -37,7 +42,93 @@ use crate::types::utils::u32_to_cow;
 #[must_use]
 pub struct ErrorPipeline<T, E> {
     result: Result<T, E>,
-    pending_contexts: Accumulator<ErrorContext>,
+    pending: Pending,
+}
+
+/// Everything the pipeline gathers for the error it builds on `Err`.
+struct Pending {
+    /// Contexts in push order, already folded and bounded.
+    contexts: ErrorVec<ErrorContext>,
+    /// Call sites at which the contexts were added.
+    sites: ContextSites,
+    attachments: Vec<Attachment>,
+    context_limit: Option<ContextLimit>,
+    /// Number of contexts dropped because of `context_limit`.
+    dropped_contexts: usize,
+    compaction: Option<ContextCompaction>,
+    /// The first call site that saw the error, recorded as its location.
+    caller: Option<&'static core::panic::Location<'static>>,
+    backtrace: Option<ErrorBacktrace>,
+}
+
+impl Pending {
+    #[inline]
+    fn new(caller: Option<&'static core::panic::Location<'static>>) -> Self {
+        Self {
+            contexts: ErrorVec::new(),
+            sites: ContextSites::default(),
+            attachments: Vec::new(),
+            context_limit: None,
+            dropped_contexts: 0,
+            compaction: None,
+            caller,
+            backtrace: None,
+        }
+    }
+
+    /// Pushes a context added at `caller` the way [`ComposableError`] does,
+    /// folding it and applying the context limit right away.
+    #[inline]
+    fn push(&mut self, ctx: ErrorContext, caller: &'static core::panic::Location<'static>) {
+        self.dropped_contexts += context_limit::push_bounded(
+            (&mut self.contexts, &mut self.sites),
+            (ctx, caller_location::capture(caller)),
+            self.context_limit,
+            self.compaction.as_ref(),
+            self.dropped_contexts,
+        );
+    }
+
+    /// Records `caller` as the error's location unless a site is already known.
+    #[inline]
+    fn record(&mut self, caller: &'static core::panic::Location<'static>) {
+        self.caller.get_or_insert(caller);
+    }
+
+    /// Drops what was gathered for a resolved error, keeping the settings.
+    #[inline]
+    fn clear(&mut self) {
+        self.contexts = ErrorVec::new();
+        self.sites = ContextSites::default();
+        self.dropped_contexts = 0;
+        self.attachments = Vec::new();
+        self.caller = None;
+        self.backtrace = None;
+    }
+
+    /// Builds the final error from `error` and the gathered state.
+    ///
+    /// `caller` is used as the location when no earlier site saw the error.
+    #[inline]
+    fn into_error<E>(
+        self,
+        error: E,
+        caller: &'static core::panic::Location<'static>,
+    ) -> ComposableError<E> {
+        let mut error = ComposableError::new_at(error, self.caller.unwrap_or(caller));
+        error.context = self.contexts;
+        if self.context_limit.is_some() || self.compaction.is_some() || !self.sites.is_empty() {
+            let extras = error.extras_mut();
+            extras.sites = self.sites;
+            extras.context_limit = self.context_limit;
+            extras.dropped_contexts = self.dropped_contexts;
+            extras.compaction = self.compaction;
+        }
+        if let Some(backtrace) = self.backtrace {
+            error = error.with_backtrace(backtrace);
+        }
+        error.with_attachments(self.attachments)
+    }
 }
 
 impl<T, E> ErrorPipeline<T, E> {
@@ -64,8 +155,11 @@ impl<T, E> ErrorPipeline<T, E> {
     /// assert!(err.error_chain().contains("calling flaky"));
     /// ```
     #[inline]
+    #[cfg_attr(feature = "caller-location", track_caller)]
     pub fn new(result: Result<T, E>) -> Self {
-        Self { result, pending_contexts: Accumulator::new() }
+        let caller = core::panic::Location::caller();
+        let pending = Pending::new(result.is_err().then_some(caller));
+        Self { result, pending }
     }
 
     /// Adds a context entry to the pending context stack.
@@ -73,6 +167,10 @@ impl<T, E> ErrorPipeline<T, E> {
     /// If the current result is `Ok`, this is a no-op. Otherwise, the context
     /// is queued to be attached when `finish` is called.
     ///
+    /// With the `caller-location` feature, the first pipeline call that sees
+    /// the error (`new`, a context builder or `finish`) records its site as
+    /// the error's [`location`](ComposableError::location).
+    ///
     /// # Arguments
     ///
     /// * `context` - Context to add
@@ -86,12 +184,15 @@ impl<T, E> ErrorPipeline<T, E> {
     ///     .with_context(ErrorContext::tag("db"));
     /// ```
     #[inline]
+    #[cfg_attr(feature = "caller-location", track_caller)]
     pub fn with_context<C>(mut self, context: C) -> Self
     where
         C: IntoErrorContext,
     {
         if self.result.is_err() {
-            self.pending_contexts.push(context.into_error_context());
+            let caller = core::panic::Location::caller();
+            self.pending.record(caller);
+            self.pending.push(context.into_error_context(), caller);
         }
         self
     }
@@ -100,6 +201,7 @@ impl<T, E> ErrorPipeline<T, E> {
     ///
     /// Adds a context entry to the pending context stack.
     #[inline]
+    #[cfg_attr(feature = "caller-location", track_caller)]
     pub fn context<C>(self, context: C) -> Self
     where
         C: IntoErrorContext,
@@ -107,6 +209,155 @@ impl<T, E> ErrorPipeline<T, E> {
         self.with_context(context)
     }
 
+    /// Captures a backtrace to be attached to the error when the pipeline finishes.
+    ///
+    /// If the current result is `Ok`, this is a no-op, so nothing is captured on
+    /// the success path. Capturing follows `RUST_LIB_BACKTRACE`/`RUST_BACKTRACE`;
+    /// see [`ErrorBacktrace::capture`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use error_rail::ErrorPipeline;
+    ///
+    /// let err = ErrorPipeline::<(), &str>::new(Err("boom"))
+    ///     .with_backtrace()
+    ///     .finish()
+    ///     .unwrap_err();
+    ///
+    /// // Without RUST_BACKTRACE nothing is captured.
+    /// if std::env::var_os("RUST_BACKTRACE").is_none() && std::env::var_os("RUST_LIB_BACKTRACE").is_none() {
+    ///     assert!(err.backtrace().is_none());
+    /// }
+    /// ```
+    #[cfg(feature = "std")]
+    #[inline]
+    pub fn with_backtrace(mut self) -> Self {
+        if self.result.is_err() {
+            self.pending.backtrace = Some(ErrorBacktrace::capture());
+        }
+        self
+    }
+
+    /// Queues a typed attachment to be added to the error when the pipeline finishes.
+    ///
+    /// If the current result is `Ok`, this is a no-op. See
+    /// [`ComposableError::attach`] for how attachments are read back.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use error_rail::ErrorPipeline;
+    /// use std::time::Duration;
+    ///
+    /// let err = ErrorPipeline::<(), &str>::new(Err("slow"))
+    ///     .attach(Duration::from_secs(3))
+    ///     .finish_boxed()
+    ///     .unwrap_err();
+    ///
+    /// assert_eq!(err.request_ref::<Duration>(), Some(&Duration::from_secs(3)));
+    /// ```
+    #[inline]
+    pub fn attach<A>(self, value: A) -> Self
+    where
+        A: core::any::Any + Send + Sync,
+    {
+        self.push_attachment(|| Attachment::new(value))
+    }
+
+    /// Queues an attachment rendered with its `Display` implementation.
+    ///
+    /// See [`ComposableError::attach_display`].
+    #[inline]
+    pub fn attach_display<A>(self, value: A) -> Self
+    where
+        A: core::fmt::Display + core::any::Any + Send + Sync,
+    {
+        self.push_attachment(|| Attachment::display(value))
+    }
+
+    /// Queues an attachment rendered with its `Debug` implementation.
+    ///
+    /// See [`ComposableError::attach_debug`].
+    #[inline]
+    pub fn attach_debug<A>(self, value: A) -> Self
+    where
+        A: core::fmt::Debug + core::any::Any + Send + Sync,
+    {
+        self.push_attachment(|| Attachment::debug(value))
+    }
+
+    #[inline]
+    fn push_attachment(mut self, f: impl FnOnce() -> Attachment) -> Self {
+        if self.result.is_err() {
+            self.pending.attachments.push(f());
+        }
+        self
+    }
+
+    /// Caps the number of contexts kept on the error produced by this pipeline.
+    ///
+    /// The limit is applied to the pending contexts right away and on every
+    /// later push, and stays on the resulting [`ComposableError`]. See
+    /// [`ContextLimit`] for the available policies.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use error_rail::ErrorPipeline;
+    /// use error_rail::types::ContextLimit;
+    ///
+    /// let mut pipeline = ErrorPipeline::<(), &str>::new(Err("timeout"))
+    ///     .with_context_limit(ContextLimit::keep_last(2));
+    /// for attempt in 1..=4 {
+    ///     pipeline = pipeline.with_retry_context(attempt);
+    /// }
+    /// let err = pipeline.finish().unwrap_err();
+    ///
+    /// assert_eq!(err.context().len(), 2);
+    /// assert_eq!(err.dropped_contexts(), 2);
+    /// ```
+    #[inline]
+    pub fn with_context_limit(mut self, limit: ContextLimit) -> Self {
+        self.pending.context_limit = Some(limit);
+        self.pending.sites.truncate(limit);
+        self.pending.dropped_contexts += limit.truncate(&mut self.pending.contexts);
+        self
+    }
+
+    /// Folds repeated pending contexts now and whenever more are added, and
+    /// keeps the rules on the error built by `finish`; see
+    /// [`ComposableError::with_compaction`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use error_rail::ErrorPipeline;
+    /// use error_rail::types::ContextCompaction;
+    ///
+    /// let mut pipeline = ErrorPipeline::<(), &str>::new(Err("timeout"))
+    ///     .with_compaction(ContextCompaction::new().counter_key("retry_attempt"));
+    /// for attempt in 1..=4 {
+    ///     pipeline = pipeline.with_retry_context(attempt);
+    /// }
+    /// let err = pipeline.finish().unwrap_err();
+    ///
+    /// assert_eq!(err.context().len(), 1);
+    /// assert_eq!(err.error_chain(), "(retry_attempt=1..4) -> timeout");
+    /// ```
+    #[inline]
+    pub fn with_compaction(mut self, compaction: ContextCompaction) -> Self {
+        let pending = &mut self.pending;
+        context_limit::compact_bounded(
+            (&mut pending.contexts, &mut pending.sites),
+            &compaction,
+            pending.context_limit,
+            pending.dropped_contexts,
+        );
+        pending.compaction = Some(compaction);
+        self
+    }
+
     /// Creates a retry operations builder for this pipeline.
     ///
     /// Returns a `RetryHints` wrapper that provides fluent methods for attaching
@@ -165,7 +416,7 @@ impl<T, E> ErrorPipeline<T, E> {
             result: self
                 .result
                 .map_err(|e| MarkedError { inner: e, classifier }),
-            pending_contexts: self.pending_contexts,
+            pending: self.pending,
         }
     }
 
@@ -203,7 +454,7 @@ impl<T, E> ErrorPipeline<T, E> {
     where
         F: FnOnce(E) -> NewE,
     {
-        ErrorPipeline { result: self.result.map_err(f), pending_contexts: self.pending_contexts }
+        ErrorPipeline { result: self.result.map_err(f), pending: self.pending }
     }
 
     /// Attempts to recover from an error using a fallback function.
@@ -212,14 +463,12 @@ impl<T, E> ErrorPipeline<T, E> {
     where
         F: FnOnce(E) -> Result<T, E>,
     {
-        let Self { result, pending_contexts } = self;
-        match result {
-            Ok(v) => Self { result: Ok(v), pending_contexts },
-            Err(e) => match recovery(e) {
-                Ok(v) => Self { result: Ok(v), pending_contexts: Accumulator::new() },
-                Err(e) => Self { result: Err(e), pending_contexts },
-            },
-        }
+        let Self { result, mut pending } = self;
+        let result = match result {
+            Ok(v) => Ok(v),
+            Err(e) => recovery(e).inspect(|_| pending.clear()),
+        };
+        Self { result, pending }
     }
 
     /// Recovers from an error using a default value.
@@ -244,11 +493,11 @@ impl<T, E> ErrorPipeline<T, E> {
     /// ```
     #[inline]
     pub fn fallback(self, value: T) -> Self {
-        let Self { result, pending_contexts } = self;
-        match result {
-            Ok(v) => Self { result: Ok(v), pending_contexts },
-            Err(_) => Self { result: Ok(value), pending_contexts: Accumulator::new() },
+        let Self { result, mut pending } = self;
+        if result.is_err() {
+            pending.clear();
         }
+        Self { result: result.or(Ok(value)), pending }
     }
 
     /// Recovers from an error using a safe function that always returns a value.
@@ -273,11 +522,15 @@ impl<T, E> ErrorPipeline<T, E> {
     where
         F: FnOnce(E) -> T,
     {
-        let Self { result, pending_contexts } = self;
-        match result {
-            Ok(v) => Self { result: Ok(v), pending_contexts },
-            Err(e) => Self { result: Ok(f(e)), pending_contexts: Accumulator::new() },
-        }
+        let Self { result, mut pending } = self;
+        let result = match result {
+            Ok(v) => v,
+            Err(e) => {
+                pending.clear();
+                f(e)
+            },
+        };
+        Self { result: Ok(result), pending }
     }
 
     /// Chains a fallible operation on the success value.
@@ -302,7 +555,7 @@ impl<T, E> ErrorPipeline<T, E> {
     where
         F: FnOnce(T) -> Result<U, E>,
     {
-        ErrorPipeline { result: self.result.and_then(f), pending_contexts: self.pending_contexts }
+        ErrorPipeline { result: self.result.and_then(f), pending: self.pending }
     }
 
     /// Transforms the success value using a mapping function.
@@ -327,7 +580,7 @@ impl<T, E> ErrorPipeline<T, E> {
     where
         F: FnOnce(T) -> U,
     {
-        ErrorPipeline { result: self.result.map(f), pending_contexts: self.pending_contexts }
+        ErrorPipeline { result: self.result.map(f), pending: self.pending }
     }
 
     /// Finalizes the pipeline into a boxed [`ComposableResult`].
@@ -349,10 +602,11 @@ impl<T, E> ErrorPipeline<T, E> {
     ///     .finish_boxed();
     /// ```
     #[inline]
+    #[cfg_attr(feature = "caller-location", track_caller)]
     pub fn finish_boxed(self) -> crate::types::BoxedComposableResult<T, E> {
         match self.result {
             Ok(v) => Ok(v),
-            Err(e) => Err(Box::new(ComposableError::new(e).with_contexts(self.pending_contexts))),
+            Err(e) => Err(Box::new(self.pending.into_error(e, core::panic::Location::caller()))),
         }
     }
 
@@ -375,10 +629,11 @@ impl<T, E> ErrorPipeline<T, E> {
     /// ```
     #[inline]
     #[allow(clippy::result_large_err)]
+    #[cfg_attr(feature = "caller-location", track_caller)]
     pub fn finish(self) -> ComposableResult<T, E> {
         match self.result {
             Ok(v) => Ok(v),
-            Err(e) => Err(ComposableError::new(e).with_contexts(self.pending_contexts)),
+            Err(e) => Err(self.pending.into_error(e, core::panic::Location::caller())),
         }
     }
 
@@ -464,15 +719,12 @@ impl<T, E> ErrorPipeline<T, E> {
         E: TransientError,
         F: FnOnce(E) -> Result<T, E>,
     {
-        let Self { result, pending_contexts } = self;
-        match result {
-            Ok(v) => Self { result: Ok(v), pending_contexts },
-            Err(e) if e.is_transient() => match recovery(e) {
-                Ok(v) => Self { result: Ok(v), pending_contexts: Accumulator::new() },
-                Err(e) => Self { result: Err(e), pending_contexts },
-            },
-            Err(e) => Self { result: Err(e), pending_contexts },
-        }
+        let Self { result, mut pending } = self;
+        let result = match result {
+            Err(e) if e.is_transient() => recovery(e).inspect(|_| pending.clear()),
+            other => other,
+        };
+        Self { result, pending }
     }
 
     /// Prepares the error for external retry libraries by classifying it.
@@ -577,6 +829,7 @@ impl<T, E> ErrorPipeline<T, E> {
     /// }
     /// ```
     #[inline]
+    #[cfg_attr(feature = "caller-location", track_caller)]
     pub fn with_retry_context(self, attempt: u32) -> Self {
         if self.result.is_err() {
             // Use lookup table for small numbers to avoid heap allocation
@@ -588,4 +841,81 @@ impl<T, E> ErrorPipeline<T, E> {
             self
         }
     }
+
+    /// Returns `true` if any pending context carries `tag`.
+    ///
+    /// The query methods look at the contexts added to the pipeline so far,
+    /// most recent first, and do not allocate. They see nothing on `Ok`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use error_rail::{ErrorContext, ErrorPipeline};
+    ///
+    /// let pipeline = ErrorPipeline::<(), &str>::new(Err("timeout"))
+    ///     .with_context(ErrorContext::tag("db"))
+    ///     .with_context(ErrorContext::metadata("table", "orders"));
+    ///
+    /// assert!(pipeline.has_tag("db"));
+    /// assert_eq!(pipeline.metadata("table"), Some("orders"));
+    /// ```
+    #[inline]
+    pub fn has_tag(&self, tag: &str) -> bool {
+        context_query::has_tag(self.pending(), tag)
+    }
+
+    /// Returns the most recent value stored under metadata `key` as text, or
+    /// `None` if that value is not text; read other types with
+    /// [`metadata_any`](Self::metadata_any).
+    #[inline]
+    pub fn metadata(&self, key: &str) -> Option<&str> {
+        context_query::metadata(self.pending(), key)
+    }
+
+    /// Returns the most recent value stored under metadata `key`, whatever its type.
+    #[inline]
+    pub fn metadata_any(&self, key: &str) -> Option<&MetadataValue> {
+        context_query::metadata_any(self.pending(), key)
+    }
+
+    /// Iterates over every value stored under metadata `key`, most recent first.
+    #[inline]
+    pub fn metadata_all<'a>(
+        &'a self,
+        key: &'a str,
+    ) -> impl Iterator<Item = &'a MetadataValue> + 'a {
+        context_query::metadata_all(self.pending(), key)
+    }
+
+    /// Iterates over the tags of the pending contexts, most recent first.
+    #[inline]
+    pub fn tags(&self) -> impl Iterator<Item = &str> + '_ {
+        context_query::tags(self.pending())
+    }
+
+    /// Iterates over the source locations of the pending contexts, most recent first.
+    #[inline]
+    pub fn locations(&self) -> impl Iterator<Item = &Location> + '_ {
+        context_query::locations(self.pending())
+    }
+
+    /// Iterates over the plain messages of the pending contexts, most recent first.
+    #[inline]
+    pub fn messages(&self) -> impl Iterator<Item = &str> + '_ {
+        context_query::messages(self.pending())
+    }
+
+    /// Returns the most recent pending context matching `predicate`.
+    #[inline]
+    pub fn find_context<P>(&self, mut predicate: P) -> Option<&ErrorContext>
+    where
+        P: FnMut(&ErrorContext) -> bool,
+    {
+        self.pending().find(|ctx| predicate(ctx))
+    }
+
+    #[inline]
+    fn pending(&self) -> core::iter::Rev<core::slice::Iter<'_, ErrorContext>> {
+        self.pending.contexts.iter().rev()
+    }
 }
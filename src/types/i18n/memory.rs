@@ -0,0 +1,341 @@
+//! Built-in, in-memory [`MessageCatalog`].
+
+use super::MessageCatalog;
+use crate::types::alloc_type::{Cow, String};
+#[cfg(not(feature = "std"))]
+use alloc::{borrow::ToOwned, collections::BTreeMap};
+use core::fmt::{Display, Formatter};
+#[cfg(feature = "std")]
+use std::collections::BTreeMap;
+
+/// A [`MessageCatalog`] holding templates in memory, per locale.
+///
+/// Templates are added with [`with_message`](Self::with_message) or
+/// [`insert`](Self::insert), or loaded from text with
+/// [`load_properties`](Self::load_properties) and [`load_json`](Self::load_json).
+///
+/// # Examples
+///
+/// ```
+/// use error_rail::types::{MemoryCatalog, MessageCatalog};
+///
+/// let mut catalog = MemoryCatalog::new().with_fallback("en");
+/// catalog
+///     .load_properties("en", "# orders\norder.not_found = order {id} was not found")
+///     .unwrap();
+/// catalog
+///     .load_json("de", r#"{ "order": { "not_found": "Bestellung {id} wurde nicht gefunden" } }"#)
+///     .unwrap();
+///
+/// assert_eq!(
+///     catalog.resolve("de-AT", "order.not_found").as_deref(),
+///     Some("Bestellung {id} wurde nicht gefunden")
+/// );
+/// assert_eq!(
+///     catalog.resolve("it", "order.not_found").as_deref(),
+///     Some("order {id} was not found")
+/// );
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct MemoryCatalog {
+    locales: BTreeMap<String, BTreeMap<String, String>>,
+    fallback: Option<String>,
+}
+
+impl MemoryCatalog {
+    /// Creates an empty catalog without a fallback locale.
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the locale tried when neither the requested locale nor its parents
+    /// have a key.
+    #[inline]
+    pub fn with_fallback(mut self, locale: impl Into<String>) -> Self {
+        self.fallback = Some(locale.into());
+        self
+    }
+
+    /// Adds the template for `key` in `locale`.
+    #[inline]
+    pub fn with_message(
+        mut self,
+        locale: impl Into<String>,
+        key: impl Into<String>,
+        template: impl Into<String>,
+    ) -> Self {
+        self.insert(locale, key, template);
+        self
+    }
+
+    /// Adds or replaces the template for `key` in `locale`.
+    pub fn insert(
+        &mut self,
+        locale: impl Into<String>,
+        key: impl Into<String>,
+        template: impl Into<String>,
+    ) -> &mut Self {
+        self.locales
+            .entry(locale.into())
+            .or_default()
+            .insert(key.into(), template.into());
+        self
+    }
+
+    /// Loads `key = value` lines into `locale`.
+    ///
+    /// Blank lines and lines starting with `#` or `!` are skipped. Keys and
+    /// values are trimmed, and the first `=` separates them.
+    pub fn load_properties(&mut self, locale: &str, source: &str) -> Result<(), CatalogError> {
+        for (index, line) in source.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') || line.starts_with('!') {
+                continue;
+            }
+            let (key, value) = line
+                .split_once('=')
+                .ok_or(CatalogError::syntax(index + 1, "expected `key = value`"))?;
+            let key = key.trim();
+            if key.is_empty() {
+                return Err(CatalogError::syntax(index + 1, "empty key"));
+            }
+            self.insert(locale, key, value.trim());
+        }
+        Ok(())
+    }
+
+    /// Loads a JSON object into `locale`.
+    ///
+    /// Values must be strings or nested objects, whose keys are joined with `.`
+    /// (`{"order": {"not_found": "..."}}` defines `order.not_found`).
+    pub fn load_json(&mut self, locale: &str, source: &str) -> Result<(), CatalogError> {
+        let mut parser = JsonParser {
+            source,
+            pos: 0,
+            entries: self.locales.entry(locale.to_owned()).or_default(),
+        };
+        parser.skip_whitespace();
+        parser.object(&mut String::new())?;
+        parser.skip_whitespace();
+        if parser.pos < source.len() {
+            return Err(parser.error("unexpected trailing characters"));
+        }
+        Ok(())
+    }
+
+    /// Loads a file into `locale`: `.json` files with [`load_json`](Self::load_json),
+    /// anything else with [`load_properties`](Self::load_properties).
+    #[cfg(feature = "std")]
+    pub fn load_file(
+        &mut self,
+        locale: &str,
+        path: impl AsRef<std::path::Path>,
+    ) -> Result<(), CatalogError> {
+        let path = path.as_ref();
+        let source = std::fs::read_to_string(path).map_err(CatalogError::Io)?;
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some(ext) if ext.eq_ignore_ascii_case("json") => self.load_json(locale, &source),
+            _ => self.load_properties(locale, &source),
+        }
+    }
+}
+
+impl MessageCatalog for MemoryCatalog {
+    #[inline]
+    fn lookup(&self, locale: &str, key: &str) -> Option<Cow<'_, str>> {
+        self.locales
+            .get(locale)?
+            .get(key)
+            .map(|template| Cow::Borrowed(template.as_str()))
+    }
+
+    #[inline]
+    fn fallback_locale(&self) -> Option<&str> {
+        self.fallback.as_deref()
+    }
+}
+
+/// Error returned when a catalog source cannot be loaded.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum CatalogError {
+    /// The source is malformed at the given 1-based line.
+    Syntax {
+        /// Line on which the problem was found
+        line: usize,
+        /// What was expected or found
+        message: &'static str,
+    },
+    /// The source file could not be read.
+    #[cfg(feature = "std")]
+    Io(std::io::Error),
+}
+
+impl CatalogError {
+    #[inline]
+    fn syntax(line: usize, message: &'static str) -> Self {
+        Self::Syntax { line, message }
+    }
+}
+
+impl Display for CatalogError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Syntax { line, message } => {
+                write!(f, "invalid catalog at line {}: {}", line, message)
+            },
+            #[cfg(feature = "std")]
+            Self::Io(err) => write!(f, "failed to read catalog: {}", err),
+        }
+    }
+}
+
+impl core::error::Error for CatalogError {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            #[cfg(feature = "std")]
+            Self::Io(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+/// Minimal parser for JSON objects of strings and nested objects.
+struct JsonParser<'s, 'm> {
+    source: &'s str,
+    pos: usize,
+    entries: &'m mut BTreeMap<String, String>,
+}
+
+impl JsonParser<'_, '_> {
+    fn error(&self, message: &'static str) -> CatalogError {
+        let line = self.source[..self.pos].matches('\n').count() + 1;
+        CatalogError::syntax(line, message)
+    }
+
+    #[inline]
+    fn peek(&self) -> Option<u8> {
+        self.source.as_bytes().get(self.pos).copied()
+    }
+
+    fn skip_whitespace(&mut self) {
+        while matches!(self.peek(), Some(b' ' | b'\t' | b'\n' | b'\r')) {
+            self.pos += 1;
+        }
+    }
+
+    fn expect(&mut self, byte: u8, message: &'static str) -> Result<(), CatalogError> {
+        self.skip_whitespace();
+        if self.peek() != Some(byte) {
+            return Err(self.error(message));
+        }
+        self.pos += 1;
+        Ok(())
+    }
+
+    /// Parses an object, storing its strings under `prefix`.
+    fn object(&mut self, prefix: &mut String) -> Result<(), CatalogError> {
+        self.expect(b'{', "expected `{`")?;
+        self.skip_whitespace();
+        if self.peek() == Some(b'}') {
+            self.pos += 1;
+            return Ok(());
+        }
+        loop {
+            self.skip_whitespace();
+            let key = self.string()?;
+            self.expect(b':', "expected `:`")?;
+            self.skip_whitespace();
+
+            let len = prefix.len();
+            if !prefix.is_empty() {
+                prefix.push('.');
+            }
+            prefix.push_str(&key);
+            match self.peek() {
+                Some(b'"') => {
+                    let value = self.string()?;
+                    self.entries.insert(prefix.clone(), value);
+                },
+                Some(b'{') => self.object(prefix)?,
+                _ => return Err(self.error("expected a string or an object")),
+            }
+            prefix.truncate(len);
+
+            self.skip_whitespace();
+            match self.peek() {
+                Some(b',') => self.pos += 1,
+                Some(b'}') => {
+                    self.pos += 1;
+                    return Ok(());
+                },
+                _ => return Err(self.error("expected `,` or `}`")),
+            }
+        }
+    }
+
+    fn string(&mut self) -> Result<String, CatalogError> {
+        if self.peek() != Some(b'"') {
+            return Err(self.error("expected a string"));
+        }
+        self.pos += 1;
+        let mut out = String::new();
+        loop {
+            let rest = &self.source[self.pos..];
+            let Some(end) = rest.find(['"', '\\']) else {
+                return Err(self.error("unterminated string"));
+            };
+            out.push_str(&rest[..end]);
+            self.pos += end;
+            if self.peek() == Some(b'"') {
+                self.pos += 1;
+                return Ok(out);
+            }
+            self.pos += 1;
+            let escaped = match self.peek() {
+                Some(b'"') => '"',
+                Some(b'\\') => '\\',
+                Some(b'/') => '/',
+                Some(b'b') => '\u{8}',
+                Some(b'f') => '\u{c}',
+                Some(b'n') => '\n',
+                Some(b'r') => '\r',
+                Some(b't') => '\t',
+                Some(b'u') => {
+                    self.pos += 1;
+                    let unit = self.hex4()?;
+                    let code = if (0xD800..0xDC00).contains(&unit) {
+                        if !self.source[self.pos..].starts_with("\\u") {
+                            return Err(self.error("unpaired surrogate"));
+                        }
+                        self.pos += 2;
+                        let low = self.hex4()?;
+                        if !(0xDC00..0xE000).contains(&low) {
+                            return Err(self.error("unpaired surrogate"));
+                        }
+                        0x10000 + ((unit - 0xD800) << 10) + (low - 0xDC00)
+                    } else {
+                        unit
+                    };
+                    out.push(char::from_u32(code).ok_or(self.error("invalid unicode escape"))?);
+                    continue;
+                },
+                _ => return Err(self.error("invalid escape")),
+            };
+            out.push(escaped);
+            self.pos += 1;
+        }
+    }
+
+    fn hex4(&mut self) -> Result<u32, CatalogError> {
+        let digits = self
+            .source
+            .get(self.pos..self.pos + 4)
+            .ok_or(self.error("invalid unicode escape"))?;
+        let value =
+            u32::from_str_radix(digits, 16).map_err(|_| self.error("invalid unicode escape"))?;
+        self.pos += 4;
+        Ok(value)
+    }
+}
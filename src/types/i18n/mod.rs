@@ -0,0 +1,206 @@
+//! Localized context messages resolved from message catalogs.
+//!
+//! A context created with [`ErrorContext::i18n`](crate::ErrorContext::i18n)
+//! stores a message key and named arguments instead of a finished English
+//! sentence. The arguments live in the context's metadata, so they stay
+//! structured, typed and redactable. The key is only turned into text at
+//! render time, by a [`MessageCatalog`] for the requested locale:
+//!
+//! - [`ErrorFormatBuilder::locale`](crate::types::error_formatter::ErrorFormatBuilder::locale)
+//!   renders a whole error chain in one locale.
+//! - [`ErrorContext::localize`](crate::ErrorContext::localize) renders a single context.
+//!
+//! Templates name their arguments in braces (`"Bestellung {id} nicht gefunden"`);
+//! `{{` and `}}` produce literal braces. Arguments used by the template are not
+//! repeated as metadata. Lookups walk a fallback chain: `de-DE`, then `de`, then
+//! the catalog's [`fallback_locale`](MessageCatalog::fallback_locale) and its
+//! parents. Without a catalog, or when no locale has the key, the context's
+//! plain message is used, or the key itself if it has none.
+//!
+//! [`MemoryCatalog`] is a built-in catalog that can be filled in code or loaded
+//! from `key = value` and JSON sources.
+//!
+//! # Examples
+//!
+//! ```
+//! use error_rail::types::MemoryCatalog;
+//! use error_rail::{ComposableError, ErrorContext};
+//!
+//! let catalog = MemoryCatalog::new()
+//!     .with_message("en", "order.not_found", "order {id} was not found")
+//!     .with_message("de", "order.not_found", "Bestellung {id} wurde nicht gefunden")
+//!     .with_fallback("en");
+//!
+//! let err = ComposableError::new("lookup failed")
+//!     .with_context(ErrorContext::i18n("order.not_found", [("id", 42)]));
+//!
+//! assert_eq!(err.to_string(), "order.not_found (id=42) -> lookup failed");
+//! assert_eq!(
+//!     err.fmt().locale(&catalog, "de-DE").to_string(),
+//!     "Bestellung 42 wurde nicht gefunden -> lookup failed"
+//! );
+//! assert_eq!(
+//!     err.fmt().locale(&catalog, "fr").to_string(),
+//!     "order 42 was not found -> lookup failed"
+//! );
+//! ```
+
+mod memory;
+
+pub use memory::{CatalogError, MemoryCatalog};
+
+use crate::types::alloc_type::{Box, Cow};
+use crate::types::MetadataValue;
+use core::fmt::{Display, Formatter};
+
+/// Source of localized message templates.
+///
+/// Implementors only provide exact lookups; [`resolve`](Self::resolve) walks the
+/// locale fallback chain on top of them.
+pub trait MessageCatalog {
+    /// Returns the template for `key` in exactly `locale`, if the catalog has one.
+    fn lookup(&self, locale: &str, key: &str) -> Option<Cow<'_, str>>;
+
+    /// Returns the locale tried after the requested one and its parents.
+    #[inline]
+    fn fallback_locale(&self) -> Option<&str> {
+        None
+    }
+
+    /// Returns the template for `key`, trying `locale`, its parents, and then the
+    /// [`fallback_locale`](Self::fallback_locale) and its parents.
+    fn resolve(&self, locale: &str, key: &str) -> Option<Cow<'_, str>> {
+        locale_chain(locale)
+            .chain(self.fallback_locale().into_iter().flat_map(locale_chain))
+            .find_map(|locale| self.lookup(locale, key))
+    }
+}
+
+impl<C: MessageCatalog + ?Sized> MessageCatalog for &C {
+    #[inline]
+    fn lookup(&self, locale: &str, key: &str) -> Option<Cow<'_, str>> {
+        (**self).lookup(locale, key)
+    }
+
+    #[inline]
+    fn fallback_locale(&self) -> Option<&str> {
+        (**self).fallback_locale()
+    }
+}
+
+impl<C: MessageCatalog + ?Sized> MessageCatalog for Box<C> {
+    #[inline]
+    fn lookup(&self, locale: &str, key: &str) -> Option<Cow<'_, str>> {
+        (**self).lookup(locale, key)
+    }
+
+    #[inline]
+    fn fallback_locale(&self) -> Option<&str> {
+        (**self).fallback_locale()
+    }
+}
+
+/// Returns `locale` followed by its parents, e.g. `zh-Hant-TW`, `zh-Hant`, `zh`.
+///
+/// Both `-` and `_` separate subtags.
+///
+/// # Examples
+///
+/// ```
+/// use error_rail::types::i18n::locale_chain;
+///
+/// assert_eq!(locale_chain("de-DE").collect::<Vec<_>>(), ["de-DE", "de"]);
+/// assert_eq!(locale_chain("pt_BR").collect::<Vec<_>>(), ["pt_BR", "pt"]);
+/// ```
+pub fn locale_chain(locale: &str) -> impl Iterator<Item = &str> {
+    core::iter::successors(Some(locale), |locale| {
+        locale.rfind(['-', '_']).map(|end| &locale[..end])
+    })
+    .filter(|locale| !locale.is_empty())
+}
+
+/// A catalog and the locale contexts are rendered in.
+#[derive(Clone, Copy)]
+pub(crate) struct Localizer<'a> {
+    pub(crate) catalog: &'a dyn MessageCatalog,
+    pub(crate) locale: &'a str,
+}
+
+impl<'a> Localizer<'a> {
+    #[inline]
+    pub(crate) fn resolve(&self, key: &str) -> Option<Cow<'a, str>> {
+        self.catalog.resolve(self.locale, key)
+    }
+}
+
+/// Renders a template, substituting `{name}` with the argument of that name.
+///
+/// Unknown placeholders and unmatched braces are written as they are.
+pub(crate) struct Template<'t, F> {
+    pub(crate) template: &'t str,
+    pub(crate) arg: F,
+}
+
+impl<'v, F> Display for Template<'_, F>
+where
+    F: Fn(&str) -> Option<&'v MetadataValue>,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        let mut rest = self.template;
+        while let Some(start) = rest.find(['{', '}']) {
+            f.write_str(&rest[..start])?;
+            let tail = &rest[start..];
+            if tail.starts_with("{{") || tail.starts_with("}}") {
+                f.write_str(&tail[..1])?;
+                rest = &tail[2..];
+                continue;
+            }
+            match placeholder(tail) {
+                Some((name, len)) => {
+                    match (self.arg)(name) {
+                        Some(value) => Display::fmt(value, f)?,
+                        None => f.write_str(&tail[..len])?,
+                    }
+                    rest = &tail[len..];
+                },
+                None => {
+                    f.write_str(&tail[..1])?;
+                    rest = &tail[1..];
+                },
+            }
+        }
+        f.write_str(rest)
+    }
+}
+
+/// Returns `true` if `template` has a `{name}` placeholder.
+pub(crate) fn uses_arg(template: &str, name: &str) -> bool {
+    let mut rest = template;
+    while let Some(start) = rest.find(['{', '}']) {
+        let tail = &rest[start..];
+        if tail.starts_with("{{") || tail.starts_with("}}") {
+            rest = &tail[2..];
+            continue;
+        }
+        match placeholder(tail) {
+            Some((found, _)) if found == name => return true,
+            Some((_, len)) => rest = &tail[len..],
+            None => rest = &tail[1..],
+        }
+    }
+    false
+}
+
+/// Parses `{name}` at the start of `text`, returning the name and the length
+/// of the whole placeholder.
+fn placeholder(text: &str) -> Option<(&str, usize)> {
+    let inner = text.strip_prefix('{')?;
+    let end = inner.find(['{', '}'])?;
+    let name = &inner[..end];
+    let valid = !name.is_empty()
+        && inner[end..].starts_with('}')
+        && name
+            .chars()
+            .all(|c| c.is_alphanumeric() || c == '_' || c == '.' || c == '-');
+    valid.then_some((name, end + 2))
+}
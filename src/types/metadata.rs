@@ -0,0 +1,478 @@
+//! Typed metadata values and typed context keys.
+//!
+//! Metadata on a [`GroupContext`](crate::types::GroupContext) is stored as
+//! [`MetadataValue`]s, so numbers, booleans, durations, lists and maps keep
+//! their type instead of being stringified. They render as plain text in
+//! `Display` output and serialize as native values with the `serde` feature.
+//!
+//! A [`ContextKey`] pairs a metadata key with the type of its value. Declaring
+//! keys as constants keeps producers and readers in sync and turns typos into
+//! compile errors.
+//!
+//! # Examples
+//!
+//! ```
+//! use error_rail::types::ContextKey;
+//! use error_rail::{ComposableError, ErrorContext};
+//! use std::time::Duration;
+//!
+//! const USER_ID: ContextKey<u64> = ContextKey::new("user_id");
+//! const ELAPSED: ContextKey<Duration> = ContextKey::new("elapsed");
+//!
+//! let err = ComposableError::new("lookup failed").with_context(
+//!     ErrorContext::builder()
+//!         .set(USER_ID, 42)
+//!         .set(ELAPSED, Duration::from_millis(250))
+//!         .metadata("retry_count", 3)
+//!         .build(),
+//! );
+//!
+//! assert_eq!(err.metadata_value(USER_ID), Some(42));
+//! assert_eq!(err.metadata_value(ELAPSED), Some(Duration::from_millis(250)));
+//! assert_eq!(err.error_chain(), "(user_id=42, elapsed=250ms, retry_count=3) -> lookup failed");
+//! ```
+
+use crate::types::alloc_type::{Cow, String, Vec};
+use core::fmt::{Debug, Display, Formatter};
+use core::hash::{Hash, Hasher};
+use core::marker::PhantomData;
+use core::time::Duration;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// The value of a metadata entry.
+///
+/// Signed and unsigned integers holding the same number compare and hash as
+/// equal, so values survive formats that do not keep the signedness. Floats
+/// compare by their bit pattern.
+///
+/// With the `serde` feature, values serialize as the matching native type.
+/// Durations use serde's `{ "secs", "nanos" }` representation and maps with
+/// exactly those two keys deserialize back into a duration.
+#[derive(Debug, Clone)]
+pub enum MetadataValue {
+    /// Text.
+    Str(Cow<'static, str>),
+    /// Signed integer.
+    I64(i64),
+    /// Unsigned integer.
+    U64(u64),
+    /// Floating-point number.
+    F64(f64),
+    /// Boolean flag.
+    Bool(bool),
+    /// Time span, rendered like `250ms` or `1.5s`.
+    Duration(Duration),
+    /// Ordered list of values.
+    List(Vec<MetadataValue>),
+    /// Ordered key/value pairs.
+    Map(Vec<(Cow<'static, str>, MetadataValue)>),
+}
+
+impl MetadataValue {
+    /// Builds a [`List`](Self::List) from any values convertible into metadata.
+    pub fn list<I>(values: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: Into<MetadataValue>,
+    {
+        Self::List(values.into_iter().map(Into::into).collect())
+    }
+
+    /// Builds a [`Map`](Self::Map) from key/value pairs, keeping their order.
+    pub fn map<I, K, V>(entries: I) -> Self
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: Into<Cow<'static, str>>,
+        V: Into<MetadataValue>,
+    {
+        Self::Map(
+            entries
+                .into_iter()
+                .map(|(key, value)| (key.into(), value.into()))
+                .collect(),
+        )
+    }
+
+    /// Returns the text if this is a [`Str`](Self::Str) value.
+    #[inline]
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Self::Str(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Returns the value as an `i128` if it is an integer.
+    #[inline]
+    fn as_integer(&self) -> Option<i128> {
+        match *self {
+            Self::I64(value) => Some(value.into()),
+            Self::U64(value) => Some(value.into()),
+            _ => None,
+        }
+    }
+}
+
+impl PartialEq for MetadataValue {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::Str(a), Self::Str(b)) => a == b,
+            (Self::F64(a), Self::F64(b)) => a.to_bits() == b.to_bits(),
+            (Self::Bool(a), Self::Bool(b)) => a == b,
+            (Self::Duration(a), Self::Duration(b)) => a == b,
+            (Self::List(a), Self::List(b)) => a == b,
+            (Self::Map(a), Self::Map(b)) => a == b,
+            (a, b) => a.as_integer().is_some() && a.as_integer() == b.as_integer(),
+        }
+    }
+}
+
+impl Eq for MetadataValue {}
+
+impl Hash for MetadataValue {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        match self {
+            Self::Str(value) => (0u8, value).hash(state),
+            Self::I64(_) | Self::U64(_) => (1u8, self.as_integer()).hash(state),
+            Self::F64(value) => (2u8, value.to_bits()).hash(state),
+            Self::Bool(value) => (3u8, value).hash(state),
+            Self::Duration(value) => (4u8, value).hash(state),
+            Self::List(values) => (5u8, values).hash(state),
+            Self::Map(entries) => (6u8, entries).hash(state),
+        }
+    }
+}
+
+impl PartialEq<str> for MetadataValue {
+    #[inline]
+    fn eq(&self, other: &str) -> bool {
+        self.as_str() == Some(other)
+    }
+}
+
+impl PartialEq<&str> for MetadataValue {
+    #[inline]
+    fn eq(&self, other: &&str) -> bool {
+        self.as_str() == Some(*other)
+    }
+}
+
+impl Display for MetadataValue {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Str(value) => f.write_str(value),
+            Self::I64(value) => Display::fmt(value, f),
+            Self::U64(value) => Display::fmt(value, f),
+            Self::F64(value) => Display::fmt(value, f),
+            Self::Bool(value) => Display::fmt(value, f),
+            Self::Duration(value) => Debug::fmt(value, f),
+            Self::List(values) => {
+                f.write_str("[")?;
+                for (i, value) in values.iter().enumerate() {
+                    if i > 0 {
+                        f.write_str(", ")?;
+                    }
+                    Display::fmt(value, f)?;
+                }
+                f.write_str("]")
+            },
+            Self::Map(entries) => {
+                f.write_str("{")?;
+                for (i, (key, value)) in entries.iter().enumerate() {
+                    if i > 0 {
+                        f.write_str(", ")?;
+                    }
+                    write!(f, "{}={}", key, value)?;
+                }
+                f.write_str("}")
+            },
+        }
+    }
+}
+
+macro_rules! impl_from {
+    ($variant:ident($target:ty): $($source:ty),*) => {
+        $(
+            impl From<$source> for MetadataValue {
+                #[inline]
+                fn from(value: $source) -> Self {
+                    Self::$variant(value as $target)
+                }
+            }
+        )*
+    };
+}
+
+impl_from!(I64(i64): i8, i16, i32, i64, isize);
+impl_from!(U64(u64): u8, u16, u32, u64, usize);
+impl_from!(F64(f64): f32, f64);
+
+impl From<&'static str> for MetadataValue {
+    #[inline]
+    fn from(value: &'static str) -> Self {
+        Self::Str(Cow::Borrowed(value))
+    }
+}
+
+impl From<String> for MetadataValue {
+    #[inline]
+    fn from(value: String) -> Self {
+        Self::Str(Cow::Owned(value))
+    }
+}
+
+impl From<Cow<'static, str>> for MetadataValue {
+    #[inline]
+    fn from(value: Cow<'static, str>) -> Self {
+        Self::Str(value)
+    }
+}
+
+impl From<bool> for MetadataValue {
+    #[inline]
+    fn from(value: bool) -> Self {
+        Self::Bool(value)
+    }
+}
+
+impl From<Duration> for MetadataValue {
+    #[inline]
+    fn from(value: Duration) -> Self {
+        Self::Duration(value)
+    }
+}
+
+impl From<Vec<MetadataValue>> for MetadataValue {
+    #[inline]
+    fn from(values: Vec<MetadataValue>) -> Self {
+        Self::List(values)
+    }
+}
+
+/// Types that can be read back from a [`MetadataValue`].
+///
+/// Integers convert between signed and unsigned values when they fit, and
+/// numbers and booleans are also parsed from text, so values recorded as
+/// strings can still be read through a typed [`ContextKey`].
+pub trait FromMetadata: Sized {
+    /// Converts the value, or returns `None` if it has a different type.
+    fn from_metadata(value: &MetadataValue) -> Option<Self>;
+}
+
+macro_rules! impl_from_metadata_int {
+    ($($ty:ty),*) => {
+        $(
+            impl FromMetadata for $ty {
+                #[inline]
+                fn from_metadata(value: &MetadataValue) -> Option<Self> {
+                    match value {
+                        MetadataValue::Str(text) => text.parse().ok(),
+                        other => other.as_integer().and_then(|n| n.try_into().ok()),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_from_metadata_int!(i8, i16, i32, i64, isize, u8, u16, u32, u64, usize);
+
+impl FromMetadata for f64 {
+    #[inline]
+    fn from_metadata(value: &MetadataValue) -> Option<Self> {
+        match *value {
+            MetadataValue::F64(n) => Some(n),
+            MetadataValue::I64(n) => Some(n as f64),
+            MetadataValue::U64(n) => Some(n as f64),
+            MetadataValue::Str(ref text) => text.parse().ok(),
+            _ => None,
+        }
+    }
+}
+
+impl FromMetadata for bool {
+    #[inline]
+    fn from_metadata(value: &MetadataValue) -> Option<Self> {
+        match value {
+            MetadataValue::Bool(flag) => Some(*flag),
+            MetadataValue::Str(text) => text.parse().ok(),
+            _ => None,
+        }
+    }
+}
+
+impl FromMetadata for Duration {
+    #[inline]
+    fn from_metadata(value: &MetadataValue) -> Option<Self> {
+        match value {
+            MetadataValue::Duration(duration) => Some(*duration),
+            _ => None,
+        }
+    }
+}
+
+impl FromMetadata for String {
+    #[inline]
+    fn from_metadata(value: &MetadataValue) -> Option<Self> {
+        value.as_str().map(String::from)
+    }
+}
+
+impl FromMetadata for Cow<'static, str> {
+    #[inline]
+    fn from_metadata(value: &MetadataValue) -> Option<Self> {
+        match value {
+            MetadataValue::Str(text) => Some(text.clone()),
+            _ => None,
+        }
+    }
+}
+
+impl<T: FromMetadata> FromMetadata for Vec<T> {
+    fn from_metadata(value: &MetadataValue) -> Option<Self> {
+        match value {
+            MetadataValue::List(values) => values.iter().map(T::from_metadata).collect(),
+            _ => None,
+        }
+    }
+}
+
+impl FromMetadata for MetadataValue {
+    #[inline]
+    fn from_metadata(value: &MetadataValue) -> Option<Self> {
+        Some(value.clone())
+    }
+}
+
+/// A metadata key tied to the type of its value.
+///
+/// Keys are usually declared as constants and used with
+/// [`ErrorContextBuilder::set`](crate::types::ErrorContextBuilder::set) to write a
+/// value and [`ComposableError::metadata_value`](crate::types::ComposableError::metadata_value)
+/// to read it back.
+pub struct ContextKey<T> {
+    name: &'static str,
+    marker: PhantomData<fn() -> T>,
+}
+
+impl<T> ContextKey<T> {
+    /// Creates a key stored under `name`.
+    #[inline]
+    pub const fn new(name: &'static str) -> Self {
+        Self { name, marker: PhantomData }
+    }
+
+    /// Returns the metadata key.
+    #[inline]
+    pub const fn name(&self) -> &'static str {
+        self.name
+    }
+}
+
+impl<T> Clone for ContextKey<T> {
+    #[inline]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for ContextKey<T> {}
+
+impl<T> Debug for ContextKey<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.debug_tuple("ContextKey").field(&self.name).finish()
+    }
+}
+
+#[cfg(feature = "serde")]
+impl Serialize for MetadataValue {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Self::Str(value) => serializer.serialize_str(value),
+            Self::I64(value) => serializer.serialize_i64(*value),
+            Self::U64(value) => serializer.serialize_u64(*value),
+            Self::F64(value) => serializer.serialize_f64(*value),
+            Self::Bool(value) => serializer.serialize_bool(*value),
+            Self::Duration(value) => value.serialize(serializer),
+            Self::List(values) => serializer.collect_seq(values),
+            Self::Map(entries) => serializer.collect_map(entries.iter().map(|(k, v)| (k, v))),
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for MetadataValue {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        use serde::de::{MapAccess, SeqAccess, Visitor};
+
+        struct ValueVisitor;
+
+        impl<'de> Visitor<'de> for ValueVisitor {
+            type Value = MetadataValue;
+
+            fn expecting(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+                f.write_str("a string, number, boolean, list or map")
+            }
+
+            fn visit_bool<E>(self, value: bool) -> Result<Self::Value, E> {
+                Ok(MetadataValue::Bool(value))
+            }
+
+            fn visit_i64<E>(self, value: i64) -> Result<Self::Value, E> {
+                Ok(MetadataValue::I64(value))
+            }
+
+            fn visit_u64<E>(self, value: u64) -> Result<Self::Value, E> {
+                Ok(MetadataValue::U64(value))
+            }
+
+            fn visit_f64<E>(self, value: f64) -> Result<Self::Value, E> {
+                Ok(MetadataValue::F64(value))
+            }
+
+            fn visit_str<E>(self, value: &str) -> Result<Self::Value, E> {
+                Ok(MetadataValue::Str(Cow::Owned(value.into())))
+            }
+
+            fn visit_string<E>(self, value: String) -> Result<Self::Value, E> {
+                Ok(MetadataValue::Str(Cow::Owned(value)))
+            }
+
+            fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+                let mut values = Vec::with_capacity(seq.size_hint().unwrap_or(0));
+                while let Some(value) = seq.next_element()? {
+                    values.push(value);
+                }
+                Ok(MetadataValue::List(values))
+            }
+
+            fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+                let mut entries: Vec<(Cow<'static, str>, MetadataValue)> =
+                    Vec::with_capacity(map.size_hint().unwrap_or(0));
+                while let Some((key, value)) = map.next_entry::<String, MetadataValue>()? {
+                    entries.push((Cow::Owned(key), value));
+                }
+                Ok(as_duration(&entries)
+                    .map_or(MetadataValue::Map(entries), MetadataValue::Duration))
+            }
+        }
+
+        deserializer.deserialize_any(ValueVisitor)
+    }
+}
+
+/// Recognizes serde's `{ "secs", "nanos" }` representation of a [`Duration`].
+#[cfg(feature = "serde")]
+fn as_duration(entries: &[(Cow<'static, str>, MetadataValue)]) -> Option<Duration> {
+    let field = |name: &str| {
+        entries
+            .iter()
+            .find_map(|(key, value)| (key == name).then_some(value))
+    };
+    if entries.len() != 2 {
+        return None;
+    }
+    let nanos = u32::from_metadata(field("nanos")?).filter(|&n| n < 1_000_000_000)?;
+    Some(Duration::new(u64::from_metadata(field("secs")?)?, nanos))
+}
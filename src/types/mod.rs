@@ -6,6 +6,7 @@
 //! # Examples
 //!
 //! ```
+//! # #[cfg(feature = "alloc")] {
 //! use error_rail::{ComposableError, ErrorContext};
 //!
 //! let err = ComposableError::new("database connection failed")
@@ -14,32 +15,122 @@
 //!
 //! assert!(err.to_string().contains("database connection failed"));
 //! assert_eq!(err.error_code(), Some(500));
+//! # }
 //! ```
+#[cfg(feature = "alloc")]
 use smallvec::SmallVec;
 
+#[cfg(feature = "alloc")]
 pub mod accumulator;
+#[cfg(feature = "alloc")]
 pub mod alloc_type;
+#[cfg(feature = "alloc")]
+pub mod backtrace;
+#[cfg(feature = "alloc")]
+pub mod caller_location;
+#[cfg(feature = "alloc")]
+pub mod clock;
+#[cfg(feature = "alloc")]
+pub mod compaction;
+#[cfg(feature = "alloc")]
 pub mod composable_error;
+#[cfg(feature = "alloc")]
+pub mod context_limit;
+#[cfg(feature = "alloc")]
+pub mod context_payload;
+#[cfg(feature = "alloc")]
+pub(crate) mod context_query;
+#[cfg(feature = "alloc")]
+pub mod error_code;
+#[cfg(feature = "alloc")]
 pub mod error_context;
+#[cfg(feature = "alloc")]
 pub mod error_formatter;
+#[cfg(feature = "alloc")]
 pub mod error_pipeline;
+#[cfg(feature = "alloc")]
+pub mod i18n;
+#[cfg(feature = "alloc")]
 pub mod lazy_context;
+#[cfg(feature = "alloc")]
 pub mod marked_error;
+#[cfg(feature = "alloc")]
+pub mod metadata;
+#[cfg(feature = "alloc")]
+pub mod rail_error;
+#[cfg(feature = "alloc")]
+pub mod redaction;
+#[cfg(feature = "alloc")]
 pub mod retry;
+pub mod severity;
+#[cfg(feature = "alloc")]
+pub mod shared_error;
+#[cfg(feature = "alloc")]
+pub mod thin_error;
+#[cfg(feature = "alloc")]
+pub mod trace;
+#[cfg(feature = "alloc")]
 pub(crate) mod utils;
+#[cfg(feature = "alloc")]
+pub mod visibility;
 
+#[cfg(feature = "alloc")]
 pub use alloc_type::*;
-pub use composable_error::{ComposableError, FingerprintConfig};
+#[cfg(feature = "alloc")]
+pub use backtrace::{BacktraceFilter, BacktraceFrame, ErrorBacktrace};
+#[cfg(all(feature = "alloc", target_has_atomic = "64"))]
+pub use clock::ManualClock;
+#[cfg(all(feature = "alloc", feature = "std"))]
+pub use clock::SystemClock;
+#[cfg(feature = "alloc")]
+pub use clock::{Clock, Timestamp};
+#[cfg(feature = "alloc")]
+pub use compaction::{ContextCompaction, ContextRepeat, CounterRange};
+#[cfg(feature = "alloc")]
+pub use composable_error::{
+    Causes, CodeRule, ComposableError, ErrorCause, FingerprintConfig, MergeOptions, MergeStrategy,
+};
+#[cfg(feature = "alloc")]
+pub use context_limit::{ContextLimit, OverflowPolicy};
+#[cfg(feature = "serde")]
+pub use context_payload::SerializedPayload;
+#[cfg(feature = "alloc")]
+pub use context_payload::{ClonePayload, ContextPayload};
+#[cfg(feature = "alloc")]
+pub use error_code::{CodeInfo, ErrorCode, HierarchicalCode};
+#[cfg(feature = "alloc")]
 pub use error_context::*;
+#[cfg(feature = "alloc")]
 pub use error_pipeline::*;
+#[cfg(feature = "alloc")]
+pub use i18n::{CatalogError, MemoryCatalog, MessageCatalog};
+#[cfg(feature = "alloc")]
 pub use lazy_context::*;
+#[cfg(feature = "alloc")]
 pub use marked_error::MarkedError;
+#[cfg(feature = "alloc")]
+pub use metadata::{ContextKey, FromMetadata, MetadataValue};
+#[cfg(feature = "alloc")]
+pub use rail_error::{DynError, RailError, RailResult, RailResultExt};
+#[cfg(feature = "alloc")]
+pub use redaction::Unredacted;
+#[cfg(feature = "alloc")]
 pub use retry::RetryOps;
+pub use severity::Severity;
+#[cfg(feature = "alloc")]
+pub use shared_error::SharedComposableError;
+#[cfg(feature = "alloc")]
+pub use thin_error::ThinComposableError;
+#[cfg(feature = "alloc")]
+pub use trace::TraceIds;
+#[cfg(feature = "alloc")]
+pub use visibility::{PublicView, Visibility};
 
 /// SmallVec-backed collection used for accumulating contexts/errors.
 ///
 /// Uses inline storage for up to 2 elements to avoid heap allocations
 /// in common cases where only a few contexts are attached.
+#[cfg(feature = "alloc")]
 pub type ErrorVec<E> = SmallVec<[E; 2]>;
 
 /// Result alias that wraps failures in [`ComposableError`].
@@ -48,6 +139,7 @@ pub type ErrorVec<E> = SmallVec<[E; 2]>;
 ///
 /// * `T` - The success value type
 /// * `E` - The core error type
+#[cfg(feature = "alloc")]
 pub type ComposableResult<T, E> = Result<T, ComposableError<E>>;
 
 /// Boxed [`ComposableError`] for reduced stack size.
@@ -55,6 +147,7 @@ pub type ComposableResult<T, E> = Result<T, ComposableError<E>>;
 /// # Type Parameters
 ///
 /// * `E` - The core error type
+#[cfg(feature = "alloc")]
 pub type BoxedComposableError<E> = alloc_type::Box<ComposableError<E>>;
 
 /// Result alias with boxed [`ComposableError`] for reduced stack size.
@@ -70,4 +163,5 @@ pub type BoxedComposableError<E> = alloc_type::Box<ComposableError<E>>;
 /// # See Also
 ///
 /// * [`crate::prelude::BoxedResult`] - Shorter alias (recommended)
+#[cfg(feature = "alloc")]
 pub type BoxedComposableResult<T, E> = Result<T, BoxedComposableError<E>>;
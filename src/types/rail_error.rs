@@ -0,0 +1,327 @@
+//! Type-erased composable error for application code.
+//!
+//! [`RailError`] plays the role of `anyhow::Error` on top of error-rail: a single
+//! error type that any [`ComposableError<E>`] converts into without losing its
+//! contexts, code or causes, and that can be downcast back to the original core
+//! error later.
+
+use crate::traits::{BoxedResultExt, IntoErrorContext};
+use crate::types::alloc_type::{Box, String};
+use crate::types::composable_error::{Attachment, ComposableError, ErrorCause};
+use crate::types::{ErrorCode, LazyContext, Severity};
+use core::error::Error;
+use core::fmt::{Debug, Display};
+use core::ops::{Deref, DerefMut};
+
+/// Boxed, type-erased core error stored inside a [`RailError`].
+pub type DynError = Box<dyn Error + Send + Sync + 'static>;
+
+/// Result alias for functions returning a [`RailError`].
+pub type RailResult<T> = Result<T, RailError>;
+
+/// Type-erased [`ComposableError`] with downcasting support.
+///
+/// The error is stored behind a single pointer, and dereferences to
+/// `ComposableError<DynError>` so contexts, codes, causes, formatting and
+/// fingerprints are available as usual.
+///
+/// Results wrapped with [`ResultExt::ctx`](crate::ResultExt::ctx) convert back
+/// with `?`: when the core error is already a `RailError` the new context is
+/// merged into it instead of nesting another layer. Use
+/// [`RailResultExt::ctx_rail`] to add context without changing the type.
+///
+/// # Examples
+///
+/// ```
+/// use error_rail::types::{RailError, RailResult};
+/// use error_rail::types::RailResultExt;
+/// use error_rail::ResultExt;
+/// use std::io;
+///
+/// fn load() -> RailResult<String> {
+///     Err(io::Error::other("disk full")).ctx("loading config")?
+/// }
+///
+/// let err = load().ctx_rail("starting app").unwrap_err();
+///
+/// assert_eq!(err.context_iter().count(), 2);
+/// assert_eq!(err.downcast_ref::<io::Error>().unwrap().to_string(), "disk full");
+/// ```
+pub struct RailError(Box<ComposableError<DynError>>);
+
+impl RailError {
+    /// Wraps any error without context or code.
+    #[inline]
+    #[cfg_attr(feature = "caller-location", track_caller)]
+    pub fn new<E>(error: E) -> Self
+    where
+        E: Error + Send + Sync + 'static,
+    {
+        Self::from(ComposableError::new(error))
+    }
+
+    /// Adds a context entry.
+    #[inline]
+    pub fn with_context<C: IntoErrorContext>(mut self, ctx: C) -> Self {
+        self.0.with_context_inplace(ctx);
+        self
+    }
+
+    /// Sets the error code; see [`ComposableError::set_code`].
+    #[inline]
+    pub fn set_code<C: ErrorCode>(mut self, code: C) -> Self {
+        self.0.set_code_inplace(code);
+        self
+    }
+
+    /// Sets the severity.
+    #[inline]
+    pub fn set_severity(mut self, severity: Severity) -> Self {
+        self.0.extras_mut().severity = Some(severity);
+        self
+    }
+
+    /// Attaches a lower-level error as a cause.
+    #[inline]
+    pub fn with_cause<C: Into<ErrorCause>>(mut self, cause: C) -> Self {
+        self.0.extras_mut().causes.push(cause.into());
+        self
+    }
+
+    /// Attaches a typed payload; see [`ComposableError::attach`].
+    #[inline]
+    pub fn attach<T>(mut self, value: T) -> Self
+    where
+        T: core::any::Any + Send + Sync,
+    {
+        self.0.extras_mut().attachments.push(Attachment::new(value));
+        self
+    }
+
+    /// Returns `true` if the core error is of type `E`.
+    #[inline]
+    pub fn is<E>(&self) -> bool
+    where
+        E: Error + 'static,
+    {
+        self.0.core_error.is::<E>()
+    }
+
+    /// Returns a reference to the core error if it is of type `E`.
+    #[inline]
+    pub fn downcast_ref<E>(&self) -> Option<&E>
+    where
+        E: Error + 'static,
+    {
+        self.0.core_error.downcast_ref::<E>()
+    }
+
+    /// Returns a mutable reference to the core error if it is of type `E`.
+    #[inline]
+    pub fn downcast_mut<E>(&mut self) -> Option<&mut E>
+    where
+        E: Error + 'static,
+    {
+        self.0.core_error.downcast_mut::<E>()
+    }
+
+    /// Recovers the original `ComposableError<E>`, keeping contexts, code and causes.
+    ///
+    /// Returns the error unchanged if the core error is not of type `E`.
+    pub fn downcast<E>(self) -> Result<ComposableError<E>, Self>
+    where
+        E: Error + 'static,
+    {
+        let ComposableError { core_error, context, error_code, extras } = *self.0;
+        match core_error.downcast::<E>() {
+            Ok(core) => Ok(ComposableError { core_error: *core, context, error_code, extras }),
+            Err(core_error) => {
+                Err(Self(Box::new(ComposableError { core_error, context, error_code, extras })))
+            },
+        }
+    }
+
+    /// Wraps an already type-erased composable error.
+    #[inline]
+    pub fn from_dyn(error: ComposableError<DynError>) -> Self {
+        Self(Box::new(error))
+    }
+
+    /// Returns the underlying type-erased composable error.
+    #[inline]
+    pub fn into_inner(self) -> ComposableError<DynError> {
+        *self.0
+    }
+
+    /// Merges the parts of an outer error that wrapped this one.
+    fn absorb<E>(mut self, mut outer: ComposableError<E>) -> Self {
+        let has_code = outer.code().is_some();
+        let (len, count) = (self.0.context.len(), outer.context.len());
+        let sites = outer
+            .extras
+            .as_deref_mut()
+            .map(|extras| core::mem::take(&mut extras.sites))
+            .unwrap_or_default();
+        if !sites.is_empty() {
+            self.0.extras_mut();
+        }
+        if let Some(extras) = self.0.extras.as_deref_mut() {
+            extras.sites.append(len, sites, count);
+        }
+        self.0.context.extend(outer.context);
+        if has_code {
+            self.0.error_code = outer.error_code;
+        }
+        if let Some(mut extras) = outer.extras {
+            if has_code {
+                self.0.set_code_slot(extras.code.take());
+            }
+            self.0.extras_mut().absorb(*extras);
+        } else if has_code {
+            self.0.set_code_slot(None);
+        }
+        self.0.enforce_context_limit();
+        self
+    }
+}
+
+impl<E> From<ComposableError<E>> for RailError
+where
+    E: Error + Send + Sync + 'static,
+{
+    fn from(error: ComposableError<E>) -> Self {
+        let ComposableError { core_error, context, error_code, extras } = error;
+        let core: DynError = Box::new(core_error);
+
+        match core.downcast::<RailError>() {
+            Ok(inner) => {
+                inner.absorb(ComposableError { core_error: (), context, error_code, extras })
+            },
+            Err(core_error) => {
+                Self(Box::new(ComposableError { core_error, context, error_code, extras }))
+            },
+        }
+    }
+}
+
+impl<E> From<Box<ComposableError<E>>> for RailError
+where
+    E: Error + Send + Sync + 'static,
+{
+    #[inline]
+    fn from(error: Box<ComposableError<E>>) -> Self {
+        Self::from(*error)
+    }
+}
+
+impl Deref for RailError {
+    type Target = ComposableError<DynError>;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl DerefMut for RailError {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl Debug for RailError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        Debug::fmt(&self.0, f)
+    }
+}
+
+impl Display for RailError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        Display::fmt(&self.0, f)
+    }
+}
+
+impl Error for RailError {
+    /// Returns the same chain as [`ComposableError::source`]: the nearest
+    /// attached cause if present, otherwise the core error.
+    #[inline]
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self.0.cause() {
+            Some(cause) => Some(cause),
+            None => Some(&*self.0.core_error),
+        }
+    }
+}
+
+impl<T> BoxedResultExt<T, DynError> for Result<T, RailError> {
+    #[inline]
+    #[cfg_attr(feature = "caller-location", track_caller)]
+    fn ctx_boxed<C: IntoErrorContext>(self, msg: C) -> Self {
+        let caller = core::panic::Location::caller();
+        self.map_err(|mut e| {
+            e.0.capture_location(caller);
+            e.0.push_context_from(msg.into_error_context(), caller);
+            e
+        })
+    }
+
+    #[inline]
+    #[cfg_attr(feature = "caller-location", track_caller)]
+    fn ctx_boxed_with<F>(self, f: F) -> Self
+    where
+        F: FnOnce() -> String,
+    {
+        let caller = core::panic::Location::caller();
+        self.map_err(|mut e| {
+            e.0.capture_location(caller);
+            e.0.push_context_from(LazyContext::new(f).into_error_context(), caller);
+            e
+        })
+    }
+}
+
+/// Context methods for [`RailResult`] that keep the `RailError` type.
+///
+/// [`ResultExt::ctx`](crate::ResultExt::ctx) wraps any error into a new
+/// `Box<ComposableError<E>>`, which for a `RailError` only merges back on `?`.
+/// These methods add the context in place instead, so the result stays a
+/// `RailResult<T>`.
+///
+/// # Examples
+///
+/// ```
+/// use error_rail::types::{RailError, RailResult, RailResultExt};
+/// use std::io;
+///
+/// let res: RailResult<()> = Err(RailError::new(io::Error::other("disk full")));
+/// let res: RailResult<()> = res.ctx_rail("loading config");
+///
+/// assert_eq!(res.unwrap_err().error_chain(), "loading config -> disk full");
+/// ```
+pub trait RailResultExt<T> {
+    /// Adds a context to the error, keeping the `RailError` type.
+    fn ctx_rail<C: IntoErrorContext>(self, msg: C) -> Self;
+
+    /// Adds a lazily formatted context to the error, keeping the `RailError` type.
+    fn ctx_rail_with<F>(self, f: F) -> Self
+    where
+        F: FnOnce() -> String;
+}
+
+impl<T> RailResultExt<T> for RailResult<T> {
+    #[inline]
+    #[cfg_attr(feature = "caller-location", track_caller)]
+    fn ctx_rail<C: IntoErrorContext>(self, msg: C) -> Self {
+        self.ctx_boxed(msg)
+    }
+
+    #[inline]
+    #[cfg_attr(feature = "caller-location", track_caller)]
+    fn ctx_rail_with<F>(self, f: F) -> Self
+    where
+        F: FnOnce() -> String,
+    {
+        self.ctx_boxed_with(f)
+    }
+}
@@ -0,0 +1,139 @@
+//! Redaction of sensitive context messages and metadata values.
+//!
+//! Group contexts can mark their message or individual metadata values as
+//! sensitive with [`ErrorContextBuilder::secret_message`](crate::types::ErrorContextBuilder::secret_message),
+//! [`ErrorContextBuilder::secret_metadata`](crate::types::ErrorContextBuilder::secret_metadata)
+//! or the `secret(...)` field of [`group!`](crate::group!). With the `std`
+//! feature, [`set_redacted_keys`] additionally redacts metadata keys globally.
+//!
+//! Sensitive parts render as [`REDACTED`] in `Display`, `Debug`,
+//! [`error_chain`](crate::types::ComposableError::error_chain), every
+//! [`ErrorFormatBuilder`](crate::types::error_formatter::ErrorFormatBuilder)
+//! layout and serde output, and fingerprints hash the placeholder instead of the
+//! value. The original values are only rendered through [`Unredacted`] or
+//! [`ErrorFormatBuilder::unredacted`](crate::types::error_formatter::ErrorFormatBuilder::unredacted).
+//!
+//! # Examples
+//!
+//! ```
+//! use error_rail::{ComposableError, ErrorContext};
+//!
+//! let err = ComposableError::new("login failed").with_context(
+//!     ErrorContext::builder()
+//!         .tag("auth")
+//!         .secret_metadata("email", "alice@example.com")
+//!         .build(),
+//! );
+//!
+//! assert_eq!(err.error_chain(), "[auth] (email=[REDACTED]) -> login failed");
+//! assert_eq!(
+//!     err.unredacted().to_string(),
+//!     "[auth] (email=alice@example.com) -> login failed"
+//! );
+//! ```
+
+use crate::types::{ComposableError, ErrorContext};
+use core::fmt::{Display, Formatter};
+
+/// Placeholder rendered, serialized and hashed in place of a sensitive value.
+pub const REDACTED: &str = "[REDACTED]";
+
+#[cfg(feature = "std")]
+static REDACTED_KEYS: std::sync::RwLock<&'static [&'static str]> = std::sync::RwLock::new(&[]);
+
+/// Redacts the values of these metadata keys in every context (requires `std`).
+///
+/// Keys are compared ASCII case-insensitively and replace any previously set
+/// list. Pass an empty slice to turn global redaction off again.
+///
+/// # Examples
+///
+/// ```
+/// use error_rail::ErrorContext;
+/// use error_rail::types::redaction;
+///
+/// redaction::set_redacted_keys(&["password", "token"]);
+///
+/// let ctx = ErrorContext::builder().metadata("Token", "abc123").build();
+/// assert_eq!(ctx.to_string(), "(Token=[REDACTED])");
+/// # redaction::set_redacted_keys(&[]);
+/// ```
+#[cfg(feature = "std")]
+pub fn set_redacted_keys(keys: &'static [&'static str]) {
+    match REDACTED_KEYS.write() {
+        Ok(mut guard) => *guard = keys,
+        Err(poisoned) => *poisoned.into_inner() = keys,
+    }
+}
+
+/// Returns the metadata keys currently redacted in every context (requires `std`).
+#[cfg(feature = "std")]
+pub fn redacted_keys() -> &'static [&'static str] {
+    match REDACTED_KEYS.read() {
+        Ok(guard) => *guard,
+        Err(poisoned) => *poisoned.into_inner(),
+    }
+}
+
+/// Returns `true` if `key` is on the global denylist.
+#[inline]
+pub(crate) fn is_redacted_key(key: &str) -> bool {
+    #[cfg(feature = "std")]
+    {
+        redacted_keys()
+            .iter()
+            .any(|denied| denied.eq_ignore_ascii_case(key))
+    }
+    #[cfg(not(feature = "std"))]
+    {
+        let _ = key;
+        false
+    }
+}
+
+/// Privileged view that renders and serializes sensitive values in clear text.
+///
+/// Created with [`ErrorContext::unredacted`] or [`ComposableError::unredacted`].
+/// Only use it for sinks that are allowed to see secrets.
+#[derive(Debug, Clone, Copy)]
+pub struct Unredacted<'a, T: ?Sized>(pub(crate) &'a T);
+
+impl<'a, T: ?Sized> Unredacted<'a, T> {
+    /// Returns the wrapped value.
+    #[inline]
+    pub fn get(&self) -> &'a T {
+        self.0
+    }
+}
+
+impl Display for Unredacted<'_, ErrorContext> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        self.0.render(f, true, None)
+    }
+}
+
+impl<E: Display> Display for Unredacted<'_, ComposableError<E>> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        let builder = self.0.fmt().unredacted(true);
+        if f.alternate() {
+            Display::fmt(&builder.cascaded(), f)
+        } else {
+            Display::fmt(&builder, f)
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for Unredacted<'_, ErrorContext> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        crate::types::error_context::ContextSer { context: self.0, reveal: true }
+            .serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<E: serde::Serialize> serde::Serialize for Unredacted<'_, ComposableError<E>> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.0.serialize_with(serializer, true)
+    }
+}
@@ -0,0 +1,69 @@
+//! Severity levels for errors and contexts.
+//!
+//! A [`Severity`] can be set on a [`ComposableError`](crate::types::ComposableError)
+//! with [`set_severity`](crate::types::ComposableError::set_severity) and on
+//! individual group contexts via [`ErrorContextBuilder::severity`](crate::types::ErrorContextBuilder::severity)
+//! or the `severity(...)` field of [`group!`](crate::group!). Levels are ordered
+//! from least to most severe, so the highest one can be picked with
+//! [`max_severity`](crate::types::ComposableError::max_severity).
+//! [`HeaplessError`](crate::heapless::HeaplessError) uses the same levels.
+//!
+//! # Examples
+//!
+//! ```
+//! # #[cfg(feature = "alloc")] {
+//! use error_rail::{ComposableError, ErrorContext};
+//! use error_rail::types::Severity;
+//!
+//! let err = ComposableError::new("disk almost full")
+//!     .with_context(ErrorContext::builder().tag("fs").severity(Severity::Critical).build())
+//!     .set_severity(Severity::Warning);
+//!
+//! assert_eq!(err.severity(), Some(Severity::Warning));
+//! assert_eq!(err.max_severity(), Some(Severity::Critical));
+//! assert_eq!(err.to_string(), "warning: [fs] -> disk almost full");
+//! # }
+//! ```
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// How severe an error is, ordered from [`Debug`](Severity::Debug) to [`Fatal`](Severity::Fatal).
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Severity {
+    /// Diagnostic information only useful while debugging.
+    Debug,
+    /// Expected failures that are worth recording.
+    Info,
+    /// Degraded behavior that does not need immediate attention.
+    Warning,
+    /// A failed operation.
+    Error,
+    /// A failure that needs immediate attention.
+    Critical,
+    /// A failure the process cannot recover from.
+    Fatal,
+}
+
+impl Severity {
+    /// Returns the lowercase name of the level, as used by `Display` and serde.
+    #[inline]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Debug => "debug",
+            Self::Info => "info",
+            Self::Warning => "warning",
+            Self::Error => "error",
+            Self::Critical => "critical",
+            Self::Fatal => "fatal",
+        }
+    }
+}
+
+impl core::fmt::Display for Severity {
+    #[inline]
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
@@ -0,0 +1,168 @@
+//! Reference-counted [`ComposableError`] that clones in constant time.
+//!
+//! [`SharedComposableError`] is useful when one failure has to reach many
+//! consumers, e.g. every waiter of a shared future or every subscriber of a
+//! broadcast channel. Cloning only bumps a reference count, so it works even
+//! when the core error itself is not `Clone`.
+
+use crate::types::alloc_type::{Arc, Box};
+use crate::types::{ComposableError, ThinComposableError};
+use core::error::Error;
+use core::fmt::{Debug, Display};
+use core::ops::Deref;
+
+/// [`ComposableError`] shared behind an [`Arc`].
+///
+/// The error is immutable once shared; it dereferences to `ComposableError<E>`
+/// for reading contexts, codes, causes and formatting.
+///
+/// # Examples
+///
+/// ```
+/// use error_rail::types::SharedComposableError;
+/// use error_rail::ComposableError;
+///
+/// // Not `Clone`
+/// #[derive(Debug)]
+/// struct ConnectionLost;
+///
+/// let err = SharedComposableError::from(
+///     ComposableError::new(ConnectionLost).with_context("refreshing cache"),
+/// );
+/// let waiters: Vec<_> = (0..3).map(|_| err.clone()).collect();
+///
+/// assert!(waiters.iter().all(|w| w.ptr_eq(&err)));
+/// assert_eq!(err.context_iter().count(), 1);
+/// ```
+pub struct SharedComposableError<E>(Arc<ComposableError<E>>);
+
+impl<E> SharedComposableError<E> {
+    /// Returns `true` if both values share the same error.
+    #[inline]
+    pub fn ptr_eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+
+    /// Returns how many values share this error.
+    #[inline]
+    pub fn share_count(&self) -> usize {
+        Arc::strong_count(&self.0)
+    }
+
+    /// Returns the error if this is its only owner, or `self` otherwise.
+    #[inline]
+    pub fn try_into_inner(self) -> Result<ComposableError<E>, Self> {
+        Arc::try_unwrap(self.0).map_err(Self)
+    }
+
+    /// Returns the error, cloning it if it is still shared.
+    #[inline]
+    pub fn into_inner(self) -> ComposableError<E>
+    where
+        E: Clone,
+    {
+        Arc::unwrap_or_clone(self.0)
+    }
+
+    /// Returns the underlying [`Arc`].
+    #[inline]
+    pub fn into_arc(self) -> Arc<ComposableError<E>> {
+        self.0
+    }
+}
+
+impl<E> Clone for SharedComposableError<E> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self(Arc::clone(&self.0))
+    }
+}
+
+impl<E> From<ComposableError<E>> for SharedComposableError<E> {
+    #[inline]
+    fn from(error: ComposableError<E>) -> Self {
+        Self(Arc::new(error))
+    }
+}
+
+impl<E> From<Box<ComposableError<E>>> for SharedComposableError<E> {
+    #[inline]
+    fn from(error: Box<ComposableError<E>>) -> Self {
+        Self(Arc::from(error))
+    }
+}
+
+impl<E> From<ThinComposableError<E>> for SharedComposableError<E> {
+    #[inline]
+    fn from(error: ThinComposableError<E>) -> Self {
+        Self::from(error.into_boxed())
+    }
+}
+
+impl<E> From<Arc<ComposableError<E>>> for SharedComposableError<E> {
+    #[inline]
+    fn from(error: Arc<ComposableError<E>>) -> Self {
+        Self(error)
+    }
+}
+
+impl<E> Deref for SharedComposableError<E> {
+    type Target = ComposableError<E>;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<E> AsRef<ComposableError<E>> for SharedComposableError<E> {
+    #[inline]
+    fn as_ref(&self) -> &ComposableError<E> {
+        &self.0
+    }
+}
+
+impl<E: PartialEq> PartialEq for SharedComposableError<E> {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.ptr_eq(other) || self.0 == other.0
+    }
+}
+
+impl<E: Eq> Eq for SharedComposableError<E> {}
+
+impl<E: Debug> Debug for SharedComposableError<E> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        Debug::fmt(&self.0, f)
+    }
+}
+
+impl<E: Display> Display for SharedComposableError<E> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        Display::fmt(&self.0, f)
+    }
+}
+
+impl<E> Error for SharedComposableError<E>
+where
+    E: Error + Send + Sync + 'static,
+{
+    #[inline]
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        self.0.source()
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<E: serde::Serialize> serde::Serialize for SharedComposableError<E> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.0.serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, E: serde::Deserialize<'de>> serde::Deserialize<'de> for SharedComposableError<E> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        ComposableError::deserialize(deserializer).map(Self::from)
+    }
+}
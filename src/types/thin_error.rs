@@ -0,0 +1,214 @@
+//! Pointer-sized [`ComposableError`] for compact `Result`s.
+//!
+//! A `ComposableError<E>` stores its core error, an inline context stack and
+//! an error code directly, which makes `Result<T, ComposableError<E>>` several
+//! words larger than `T`. [`ThinComposableError`] keeps the whole error behind
+//! a single pointer, so its results stay as small as `Result<T, Box<_>>` while
+//! still offering the builder methods and `?` conversions of the full type.
+
+use crate::traits::{BoxedResultExt, IntoErrorContext};
+use crate::types::alloc_type::{Box, String};
+use crate::types::composable_error::{Attachment, ComposableError, ErrorCause};
+use crate::types::{ErrorCode, LazyContext, Severity, SharedComposableError};
+use core::error::Error;
+use core::fmt::{Debug, Display};
+use core::ops::{Deref, DerefMut};
+
+/// [`ComposableError`] stored behind a single pointer.
+///
+/// Dereferences to `ComposableError<E>`, so contexts, codes, causes, formatting
+/// and fingerprints are available as usual. Converts from `ComposableError<E>`,
+/// `Box<ComposableError<E>>` and plain `E`, so `?` works on all of them.
+///
+/// # Examples
+///
+/// ```
+/// use error_rail::types::ThinComposableError;
+/// use error_rail::ResultExt;
+///
+/// fn parse(input: &str) -> Result<u32, ThinComposableError<std::num::ParseIntError>> {
+///     Ok(input.parse::<u32>().ctx("parsing port")?)
+/// }
+///
+/// assert_eq!(
+///     core::mem::size_of::<ThinComposableError<std::num::ParseIntError>>(),
+///     core::mem::size_of::<usize>()
+/// );
+/// let err = parse("http").unwrap_err();
+/// assert_eq!(err.context_iter().count(), 1);
+/// ```
+#[derive(Clone, PartialEq, Eq)]
+pub struct ThinComposableError<E>(Box<ComposableError<E>>);
+
+impl<E> ThinComposableError<E> {
+    /// Wraps `error` without context or code.
+    #[inline]
+    #[cfg_attr(feature = "caller-location", track_caller)]
+    pub fn new(error: E) -> Self {
+        Self(Box::new(ComposableError::new(error)))
+    }
+
+    /// Adds a context entry.
+    #[inline]
+    pub fn with_context<C: IntoErrorContext>(mut self, ctx: C) -> Self {
+        self.0.with_context_inplace(ctx);
+        self
+    }
+
+    /// Sets the error code; see [`ComposableError::set_code`].
+    #[inline]
+    pub fn set_code<C: ErrorCode>(mut self, code: C) -> Self {
+        self.0.set_code_inplace(code);
+        self
+    }
+
+    /// Sets the severity.
+    #[inline]
+    pub fn set_severity(mut self, severity: Severity) -> Self {
+        self.0.extras_mut().severity = Some(severity);
+        self
+    }
+
+    /// Attaches a lower-level error as a cause.
+    #[inline]
+    pub fn with_cause<C: Into<ErrorCause>>(mut self, cause: C) -> Self {
+        self.0.extras_mut().causes.push(cause.into());
+        self
+    }
+
+    /// Attaches a typed payload; see [`ComposableError::attach`].
+    #[inline]
+    pub fn attach<T>(mut self, value: T) -> Self
+    where
+        T: core::any::Any + Send + Sync,
+    {
+        self.0.extras_mut().attachments.push(Attachment::new(value));
+        self
+    }
+
+    /// Returns the full composable error.
+    #[inline]
+    pub fn into_inner(self) -> ComposableError<E> {
+        *self.0
+    }
+
+    /// Returns the boxed composable error, without moving it.
+    #[inline]
+    pub fn into_boxed(self) -> Box<ComposableError<E>> {
+        self.0
+    }
+
+    /// Moves the error into a [`SharedComposableError`] that clones cheaply.
+    #[inline]
+    pub fn into_shared(self) -> SharedComposableError<E> {
+        SharedComposableError::from(self.into_inner())
+    }
+}
+
+impl<E> From<ComposableError<E>> for ThinComposableError<E> {
+    #[inline]
+    fn from(error: ComposableError<E>) -> Self {
+        Self(Box::new(error))
+    }
+}
+
+impl<E> From<Box<ComposableError<E>>> for ThinComposableError<E> {
+    #[inline]
+    fn from(error: Box<ComposableError<E>>) -> Self {
+        Self(error)
+    }
+}
+
+impl<E> From<E> for ThinComposableError<E> {
+    #[inline]
+    #[cfg_attr(feature = "caller-location", track_caller)]
+    fn from(error: E) -> Self {
+        Self::new(error)
+    }
+}
+
+impl<E> From<ThinComposableError<E>> for ComposableError<E> {
+    #[inline]
+    fn from(error: ThinComposableError<E>) -> Self {
+        error.into_inner()
+    }
+}
+
+impl<E> Deref for ThinComposableError<E> {
+    type Target = ComposableError<E>;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<E> DerefMut for ThinComposableError<E> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl<E: Debug> Debug for ThinComposableError<E> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        Debug::fmt(&self.0, f)
+    }
+}
+
+impl<E: Display> Display for ThinComposableError<E> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        Display::fmt(&self.0, f)
+    }
+}
+
+impl<E> Error for ThinComposableError<E>
+where
+    E: Error + Send + Sync + 'static,
+{
+    #[inline]
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        self.0.source()
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<E: serde::Serialize> serde::Serialize for ThinComposableError<E> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.0.serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, E: serde::Deserialize<'de>> serde::Deserialize<'de> for ThinComposableError<E> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        ComposableError::deserialize(deserializer).map(Self::from)
+    }
+}
+
+impl<T, E> BoxedResultExt<T, E> for Result<T, ThinComposableError<E>> {
+    #[inline]
+    #[cfg_attr(feature = "caller-location", track_caller)]
+    fn ctx_boxed<C: IntoErrorContext>(self, msg: C) -> Self {
+        let caller = core::panic::Location::caller();
+        self.map_err(|mut e| {
+            e.0.capture_location(caller);
+            e.0.push_context_from(msg.into_error_context(), caller);
+            e
+        })
+    }
+
+    #[inline]
+    #[cfg_attr(feature = "caller-location", track_caller)]
+    fn ctx_boxed_with<F>(self, f: F) -> Self
+    where
+        F: FnOnce() -> String,
+    {
+        let caller = core::panic::Location::caller();
+        self.map_err(|mut e| {
+            e.0.capture_location(caller);
+            e.0.push_context_from(LazyContext::new(f).into_error_context(), caller);
+            e
+        })
+    }
+}
@@ -0,0 +1,171 @@
+//! Trace, span and correlation IDs carried by errors.
+//!
+//! [`TraceIds`] lets logs from several services be joined on the request that
+//! failed. IDs can be set directly, parsed from a W3C `traceparent` header with
+//! [`TraceIds::from_traceparent`], or, with the `tracing` feature, captured
+//! from the active span.
+//!
+//! IDs are rendered by [`ErrorFormatBuilder`](crate::types::error_formatter::ErrorFormatBuilder)
+//! (see [`show_trace_ids`](crate::types::error_formatter::ErrorFormatBuilder::show_trace_ids)),
+//! serialized with serde, and ignored by fingerprints unless
+//! [`include_trace_ids`](crate::types::FingerprintConfig::include_trace_ids) is set.
+//!
+//! # Examples
+//!
+//! ```
+//! use error_rail::ComposableError;
+//!
+//! let err = ComposableError::new("payment declined")
+//!     .with_traceparent("00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01")
+//!     .with_correlation_id("order-1234");
+//!
+//! assert_eq!(err.trace_id(), Some("4bf92f3577b34da6a3ce929d0e0e4736"));
+//! assert_eq!(
+//!     err.to_string(),
+//!     "payment declined (trace_id=4bf92f3577b34da6a3ce929d0e0e4736, \
+//!      span_id=00f067aa0ba902b7, correlation_id=order-1234)"
+//! );
+//! ```
+
+use crate::types::alloc_type::Cow;
+use core::fmt::{Display, Formatter};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Identifiers linking an error to the distributed trace and request it belongs to.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct TraceIds {
+    /// Distributed trace ID, e.g. the 32 hex digits of a W3C trace
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
+    pub trace_id: Option<Cow<'static, str>>,
+    /// ID of the span in which the error occurred
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
+    pub span_id: Option<Cow<'static, str>>,
+    /// Application-level correlation or request ID
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
+    pub correlation_id: Option<Cow<'static, str>>,
+}
+
+impl TraceIds {
+    /// Parses a W3C `traceparent` header into its trace and parent span IDs.
+    ///
+    /// Returns `None` if the header is malformed, uses the reserved version
+    /// `ff`, or carries an all-zero trace or span ID.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use error_rail::types::TraceIds;
+    ///
+    /// let ids = TraceIds::from_traceparent(
+    ///     "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01",
+    /// )
+    /// .unwrap();
+    /// assert_eq!(ids.span_id.as_deref(), Some("00f067aa0ba902b7"));
+    ///
+    /// assert!(TraceIds::from_traceparent("not-a-traceparent").is_none());
+    /// ```
+    pub fn from_traceparent(header: &str) -> Option<Self> {
+        let mut parts = header.trim().split('-');
+        let version = parts.next()?;
+        let trace_id = parts.next()?;
+        let span_id = parts.next()?;
+        let flags = parts.next()?;
+
+        let valid = is_hex(version, 2)
+            && version != "ff"
+            && (version != "00" || parts.next().is_none())
+            && is_hex(trace_id, 32)
+            && is_hex(span_id, 16)
+            && is_hex(flags, 2)
+            && !is_zero(trace_id)
+            && !is_zero(span_id);
+        if !valid {
+            return None;
+        }
+
+        Some(Self {
+            trace_id: Some(Cow::Owned(trace_id.into())),
+            span_id: Some(Cow::Owned(span_id.into())),
+            correlation_id: None,
+        })
+    }
+
+    /// Returns `true` if no ID is set.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.trace_id.is_none() && self.span_id.is_none() && self.correlation_id.is_none()
+    }
+
+    /// Fills every ID that is unset in `self` from `other`.
+    #[inline]
+    pub(crate) fn fill_from(&mut self, other: TraceIds) {
+        self.trace_id = self.trace_id.take().or(other.trace_id);
+        self.span_id = self.span_id.take().or(other.span_id);
+        self.correlation_id = self.correlation_id.take().or(other.correlation_id);
+    }
+
+    #[inline]
+    fn fields(&self) -> [(&'static str, Option<&str>); 3] {
+        [
+            ("trace_id", self.trace_id.as_deref()),
+            ("span_id", self.span_id.as_deref()),
+            ("correlation_id", self.correlation_id.as_deref()),
+        ]
+    }
+}
+
+#[inline]
+fn is_hex(part: &str, len: usize) -> bool {
+    part.len() == len
+        && part
+            .bytes()
+            .all(|b| b.is_ascii_digit() || (b'a'..=b'f').contains(&b))
+}
+
+#[inline]
+fn is_zero(part: &str) -> bool {
+    part.bytes().all(|b| b == b'0')
+}
+
+/// Renders the set IDs as `key=value` pairs, e.g. `trace_id=4bf9..., span_id=00f0...`.
+impl Display for TraceIds {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        let mut first = true;
+        for (key, value) in self.fields() {
+            if let Some(value) = value {
+                if !first {
+                    f.write_str(", ")?;
+                }
+                write!(f, "{}={}", key, value)?;
+                first = false;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "tracing")]
+impl TraceIds {
+    /// Captures the ID of `span` as the span ID, rendered as 16 hex digits.
+    ///
+    /// `tracing` spans have no trace ID of their own; it is left unset.
+    /// Returns `None` for disabled spans, which have no ID.
+    #[inline]
+    pub fn from_span(span: &tracing::Span) -> Option<Self> {
+        use crate::types::alloc_type::String;
+        use core::fmt::Write;
+
+        let id = span.id()?;
+        let mut span_id = String::with_capacity(16);
+        let _ = write!(span_id, "{:016x}", id.into_u64());
+        Some(Self { span_id: Some(Cow::Owned(span_id)), ..Default::default() })
+    }
+
+    /// Captures the ID of the currently active span; see [`from_span`](Self::from_span).
+    #[inline]
+    pub fn current() -> Option<Self> {
+        Self::from_span(&tracing::Span::current())
+    }
+}
@@ -0,0 +1,138 @@
+//! User-facing and internal parts of an error.
+//!
+//! Contexts are [`Internal`](Visibility::Internal) unless marked otherwise, so
+//! SQL statements, hostnames and file paths never reach end users by accident.
+//! Mark a context as safe to show with [`ErrorContext::public`](crate::ErrorContext::public),
+//! [`ErrorContextBuilder::public`](crate::types::ErrorContextBuilder::public) or the
+//! `public()` field of [`group!`](crate::group!), and give the error a user-facing
+//! message and hint with [`with_public_message`](crate::ComposableError::with_public_message)
+//! and [`with_hint`](crate::ComposableError::with_hint).
+//!
+//! [`ComposableError::public_view`](crate::ComposableError::public_view) renders only
+//! those parts plus the error code, while `Display` and
+//! [`error_chain`](crate::ComposableError::error_chain) keep everything for logs.
+//!
+//! # Examples
+//!
+//! ```
+//! use error_rail::{ComposableError, ErrorContext};
+//!
+//! let err = ComposableError::new("connection refused: db-7.internal:5432")
+//!     .with_context("SELECT * FROM orders WHERE id = 42")
+//!     .with_context(ErrorContext::public("loading your order"))
+//!     .with_public_message("the order could not be loaded")
+//!     .with_hint("please try again in a few minutes")
+//!     .set_code(503);
+//!
+//! assert_eq!(
+//!     err.public_view().to_string(),
+//!     "loading your order -> the order could not be loaded (code: 503) \
+//!      (hint: please try again in a few minutes)"
+//! );
+//! assert!(err.error_chain().contains("db-7.internal"));
+//! ```
+
+use crate::types::i18n::{Localizer, MessageCatalog};
+use crate::types::{ComposableError, ErrorCode, ErrorContext};
+use core::fmt::{Display, Formatter};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Who may see a context: end users, or only logs and operators.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Visibility {
+    /// Diagnostic detail kept out of [`PublicView`] (the default).
+    #[default]
+    Internal,
+    /// Safe to show to end users.
+    Public,
+}
+
+impl Visibility {
+    /// Returns `true` for [`Visibility::Public`].
+    #[inline]
+    pub const fn is_public(self) -> bool {
+        matches!(self, Self::Public)
+    }
+}
+
+/// Renders only the user-facing parts of an error.
+///
+/// Created by [`ComposableError::public_view`]. `Display` writes the public
+/// contexts (newest first), the public message, the error code and the hint.
+/// The core error, internal contexts and cause layers are never rendered. When
+/// no public message was set, [`DEFAULT_MESSAGE`](Self::DEFAULT_MESSAGE) or the
+/// message given to [`fallback_message`](Self::fallback_message) is used.
+#[must_use]
+pub struct PublicView<'a, E> {
+    error: &'a ComposableError<E>,
+    fallback: &'a str,
+    localizer: Option<Localizer<'a>>,
+}
+
+impl<'a, E> PublicView<'a, E> {
+    /// Message shown when the error has no public message.
+    pub const DEFAULT_MESSAGE: &'static str = "an internal error occurred";
+
+    #[inline]
+    pub(crate) fn new(error: &'a ComposableError<E>) -> Self {
+        Self { error, fallback: Self::DEFAULT_MESSAGE, localizer: None }
+    }
+
+    /// Sets the message shown when the error has no public message.
+    #[inline]
+    pub fn fallback_message(mut self, message: &'a str) -> Self {
+        self.fallback = message;
+        self
+    }
+
+    /// Renders localized public contexts in `locale`; see [`i18n`](crate::types::i18n).
+    #[inline]
+    pub fn locale(mut self, catalog: &'a dyn MessageCatalog, locale: &'a str) -> Self {
+        self.localizer = Some(Localizer { catalog, locale });
+        self
+    }
+
+    /// Iterates over the public contexts, newest first.
+    #[inline]
+    pub fn contexts(&self) -> impl Iterator<Item = &'a ErrorContext> + 'a {
+        self.error.context_iter().filter(|ctx| ctx.is_public())
+    }
+
+    /// Returns the public message, or the fallback message if none was set.
+    #[inline]
+    pub fn message(&self) -> &'a str {
+        self.error.public_message().unwrap_or(self.fallback)
+    }
+
+    /// Returns the hint, if one was set.
+    #[inline]
+    pub fn hint(&self) -> Option<&'a str> {
+        self.error.hint()
+    }
+
+    /// Returns the error code, if one was set.
+    #[inline]
+    pub fn code(&self) -> Option<&'a dyn ErrorCode> {
+        self.error.code()
+    }
+}
+
+impl<E> Display for PublicView<'_, E> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        for ctx in self.contexts() {
+            ctx.render(f, false, self.localizer)?;
+            f.write_str(" -> ")?;
+        }
+        f.write_str(self.message())?;
+        if let Some(code) = self.code() {
+            write!(f, " (code: {})", code)?;
+        }
+        if let Some(hint) = self.hint() {
+            write!(f, " (hint: {})", hint)?;
+        }
+        Ok(())
+    }
+}
@@ -1,5 +1,7 @@
 use crate::types::accumulator::Accumulator;
-use crate::types::ErrorVec;
+use crate::types::{
+    context_query, ComposableError, ErrorContext, ErrorVec, Location, MetadataValue,
+};
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
@@ -434,3 +436,91 @@ impl<E, A> Validation<E, A> {
         }
     }
 }
+
+impl<E, A> Validation<ComposableError<E>, A> {
+    /// Returns `true` if any context of any accumulated error carries `tag`.
+    ///
+    /// The query methods search the errors in the order they were accumulated
+    /// and each error's contexts most recent first, like the matching methods
+    /// of [`ComposableError`]. They do not allocate and see nothing on `Valid`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use error_rail::validation::Validation;
+    /// use error_rail::{ComposableError, ErrorContext};
+    ///
+    /// let v = Validation::<_, ()>::invalid_many([
+    ///     ComposableError::new("missing").with_context(ErrorContext::metadata("field", "name")),
+    ///     ComposableError::new("too short").with_context(ErrorContext::metadata("field", "password")),
+    /// ]);
+    ///
+    /// assert_eq!(v.metadata("field"), Some("name"));
+    ///
+    /// let fields: Vec<_> = v.metadata_all("field").map(|field| field.to_string()).collect();
+    /// assert_eq!(fields, ["name", "password"]);
+    /// ```
+    #[inline]
+    pub fn has_tag(&self, tag: &str) -> bool {
+        context_query::has_tag(self.contexts(), tag)
+    }
+
+    /// Returns the first value stored under metadata `key` as text, or `None`
+    /// if that value is not text; read other types with
+    /// [`metadata_any`](Self::metadata_any).
+    #[inline]
+    pub fn metadata(&self, key: &str) -> Option<&str> {
+        context_query::metadata(self.contexts(), key)
+    }
+
+    /// Returns the first value stored under metadata `key`, whatever its type.
+    #[inline]
+    pub fn metadata_any(&self, key: &str) -> Option<&MetadataValue> {
+        context_query::metadata_any(self.contexts(), key)
+    }
+
+    /// Iterates over every value stored under metadata `key`.
+    #[inline]
+    pub fn metadata_all<'a>(
+        &'a self,
+        key: &'a str,
+    ) -> impl Iterator<Item = &'a MetadataValue> + 'a {
+        context_query::metadata_all(self.contexts(), key)
+    }
+
+    /// Iterates over the tags of every accumulated error.
+    #[inline]
+    pub fn tags(&self) -> impl Iterator<Item = &str> + '_ {
+        context_query::tags(self.contexts())
+    }
+
+    /// Iterates over the source locations attached as contexts to every accumulated error.
+    #[inline]
+    pub fn locations(&self) -> impl Iterator<Item = &Location> + '_ {
+        context_query::locations(self.contexts())
+    }
+
+    /// Iterates over the plain context messages of every accumulated error.
+    #[inline]
+    pub fn messages(&self) -> impl Iterator<Item = &str> + '_ {
+        context_query::messages(self.contexts())
+    }
+
+    /// Returns the first context matching `predicate`.
+    #[inline]
+    pub fn find_context<P>(&self, mut predicate: P) -> Option<&ErrorContext>
+    where
+        P: FnMut(&ErrorContext) -> bool,
+    {
+        self.contexts().find(|ctx| predicate(ctx))
+    }
+
+    #[inline]
+    fn contexts(&self) -> impl Iterator<Item = &ErrorContext> + '_ {
+        let errors = match self {
+            Self::Valid(_) => Default::default(),
+            Self::Invalid(errors) => errors.iter(),
+        };
+        errors.flat_map(ComposableError::context_iter)
+    }
+}
@@ -45,3 +45,19 @@ async fn test_context_future_pending() {
 
     assert_eq!(wrapped.as_mut().poll(&mut cx), Poll::Pending);
 }
+
+#[tokio::test]
+async fn context_future_applies_context_limit() {
+    use error_rail::types::ContextLimit;
+
+    let err = ContextFuture::new(async { Err::<(), _>("timeout") }, || "fetch")
+        .with_context_limit(ContextLimit::keep_first(1))
+        .await
+        .unwrap_err()
+        .with_context("retry 1")
+        .with_context("retry 2");
+
+    assert_eq!(err.context_limit(), Some(ContextLimit::keep_first(1)));
+    assert_eq!(err.dropped_contexts(), 2);
+    assert_eq!(err.error_chain(), "... 2 more contexts elided ... -> fetch -> timeout");
+}
@@ -136,3 +136,49 @@ async fn test_future_with_span_none() {
 
     assert!(result.is_err());
 }
+
+/// Minimal subscriber that hands out sequential span IDs starting at 42.
+#[derive(Default)]
+struct CountingSubscriber(std::sync::atomic::AtomicU64);
+
+impl tracing::Subscriber for CountingSubscriber {
+    fn enabled(&self, _: &tracing::Metadata<'_>) -> bool {
+        true
+    }
+
+    fn new_span(&self, _: &tracing::span::Attributes<'_>) -> tracing::span::Id {
+        let next = self.0.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+        tracing::span::Id::from_u64(next + 42)
+    }
+
+    fn record(&self, _: &tracing::span::Id, _: &tracing::span::Record<'_>) {}
+
+    fn record_follows_from(&self, _: &tracing::span::Id, _: &tracing::span::Id) {}
+
+    fn event(&self, _: &tracing::Event<'_>) {}
+
+    fn enter(&self, _: &tracing::span::Id) {}
+
+    fn exit(&self, _: &tracing::span::Id) {}
+}
+
+#[test]
+fn span_ids_are_captured_from_spans() {
+    use error_rail::types::TraceIds;
+
+    tracing::subscriber::with_default(CountingSubscriber::default(), || {
+        let span = tracing::info_span!("checkout");
+        assert_eq!(
+            TraceIds::from_span(&span).unwrap().span_id.as_deref(),
+            Some("000000000000002a")
+        );
+
+        let result: Result<(), &str> = Err("declined");
+        let err = result.with_span(&span).unwrap_err();
+        assert_eq!(err.span_id(), Some("000000000000002a"));
+        assert_eq!(err.trace_id(), None);
+    });
+
+    assert!(TraceIds::from_span(&Span::none()).is_none());
+    assert!(instrument_error("no subscriber").trace_ids().is_none());
+}
@@ -0,0 +1,102 @@
+use error_rail::types::{ContextKey, MetadataValue};
+use error_rail::{ComposableError, ErrorContext, IntoErrorContext, ResultExt};
+
+#[derive(IntoErrorContext)]
+struct LoadUser {
+    #[context(message)]
+    action: &'static str,
+    #[context(metadata = "user_id")]
+    id: u64,
+    #[context(tag)]
+    tenant: String,
+    #[context(skip)]
+    #[allow(dead_code)]
+    password: String,
+    region: Option<&'static str>,
+}
+
+#[derive(IntoErrorContext)]
+struct Pair(#[context(tag)] &'static str, u16);
+
+#[derive(IntoErrorContext)]
+struct Unit;
+
+#[derive(IntoErrorContext)]
+struct Generic<T: std::fmt::Display> {
+    #[context(metadata)]
+    value: T,
+}
+
+fn load_user(region: Option<&'static str>) -> LoadUser {
+    LoadUser {
+        action: "loading user",
+        id: 42,
+        tenant: "acme".into(),
+        password: "hunter2".into(),
+        region,
+    }
+}
+
+#[test]
+fn fields_map_to_group_context() {
+    let ctx = load_user(Some("eu")).into_error_context();
+    let ErrorContext::Group(group) = &ctx else {
+        panic!("expected a group context");
+    };
+
+    assert_eq!(group.message.as_deref(), Some("loading user"));
+    assert_eq!(group.tags.as_slice(), ["acme"]);
+    let metadata: Vec<_> = group
+        .metadata
+        .iter()
+        .map(|(k, v)| (k.as_ref(), v))
+        .collect();
+    assert_eq!(
+        metadata,
+        [("user_id", &MetadataValue::U64(42)), ("region", &MetadataValue::from("eu"))]
+    );
+    assert!(!ctx.message().contains("hunter2"));
+}
+
+#[test]
+fn none_options_are_left_out() {
+    let ctx = load_user(None).into_error_context();
+    assert_eq!(ctx.message(), "[acme] loading user (user_id=42)");
+}
+
+#[test]
+fn tuple_unit_and_generic_structs_are_supported() {
+    assert_eq!(Pair("http", 8080).into_error_context().message(), "[http] (1=8080)");
+    assert_eq!(Unit.into_error_context().message(), "");
+    assert_eq!(Generic { value: 1.5 }.into_error_context().message(), "(value=1.5)");
+}
+
+#[test]
+fn derived_contexts_plug_into_ctx_and_with_context() {
+    let err = ComposableError::new("not found").with_context(load_user(None));
+    assert_eq!(err.to_string(), "[acme] loading user (user_id=42) -> not found");
+
+    let result: Result<(), &str> = Err("timeout");
+    let err = result.ctx(Pair("db", 5432)).unwrap_err();
+    assert_eq!(err.error_chain(), "[db] (1=5432) -> timeout");
+}
+
+#[derive(IntoErrorContext)]
+struct Request {
+    attempts: u32,
+    cached: bool,
+    peer: std::net::Ipv4Addr,
+}
+
+#[test]
+fn typed_fields_keep_their_metadata_type() {
+    const ATTEMPTS: ContextKey<u32> = ContextKey::new("attempts");
+    const CACHED: ContextKey<bool> = ContextKey::new("cached");
+
+    let request = Request { attempts: 3, cached: false, peer: [10, 0, 0, 1].into() };
+    let err = ComposableError::new("timeout").with_context(request);
+
+    assert_eq!(err.metadata_value(ATTEMPTS), Some(3));
+    assert_eq!(err.metadata_value(CACHED), Some(false));
+    assert_eq!(err.metadata("peer"), Some("10.0.0.1"));
+}
@@ -0,0 +1,5 @@
+//! Integration tests for the derive macros.
+
+mod into_error_context;
+mod rail_context;
+mod transient_error;
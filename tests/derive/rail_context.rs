@@ -0,0 +1,205 @@
+use error_rail::{
+    rail_context, BoxedResult, ComposableError, ErrorContext, GroupContext, RailResult, ResultExt,
+};
+use std::cell::Cell;
+use std::fmt;
+
+#[rail_context("loading user {id}")]
+fn load_user(id: u64, found: bool) -> Result<&'static str, &'static str> {
+    if found {
+        Ok("alice")
+    } else {
+        Err("not found")
+    }
+}
+
+#[rail_context]
+fn unnamed() -> Result<(), &'static str> {
+    Err("boom")
+}
+
+#[rail_context("parsing {input:?}")]
+fn parse(input: &str) -> Result<u32, std::num::ParseIntError> {
+    let value = input.trim().parse::<u32>()?;
+    Ok(value * 2)
+}
+
+#[rail_context("saving order {order}")]
+fn save_order(order: u32) -> BoxedResult<(), &'static str> {
+    Err("disk full").ctx("writing file")
+}
+
+#[rail_context("opening {name}")]
+fn open(name: &str) -> RailResult<()> {
+    Err(error_rail::RailError::new(std::io::Error::other("denied")))
+}
+
+#[rail_context("inner step")]
+fn inner() -> Result<(), ComposableError<&'static str>> {
+    Err(ComposableError::new("failed"))
+}
+
+struct Counted<'a>(&'a Cell<u32>);
+
+impl fmt::Display for Counted<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.set(self.0.get() + 1);
+        f.write_str("counted")
+    }
+}
+
+#[rail_context("using {value}")]
+fn counted(value: Counted<'_>, fail: bool) -> Result<(), &'static str> {
+    if fail {
+        Err("failed")
+    } else {
+        Ok(())
+    }
+}
+
+struct Repo;
+
+impl Repo {
+    #[rail_context("finding {key} in repo")]
+    fn find(&self, key: &str) -> Result<u32, &'static str> {
+        Err("missing")
+    }
+}
+
+fn group(ctx: &ErrorContext) -> GroupContext {
+    match ctx {
+        ErrorContext::Group(group) => (**group).clone(),
+        _ => panic!("expected a group context"),
+    }
+}
+
+#[test]
+fn ok_passes_through_unchanged() {
+    assert_eq!(load_user(1, true).unwrap(), "alice");
+}
+
+#[test]
+fn err_is_boxed_with_formatted_context() {
+    let err = load_user(42, false).unwrap_err();
+    assert_eq!(*err.core_error(), "not found");
+
+    let contexts = err.context();
+    assert_eq!(contexts.len(), 1);
+    let ctx = group(&contexts[0]);
+    assert_eq!(ctx.message.as_deref(), Some("loading user 42"));
+    assert_eq!(ctx.metadata.as_slice(), &[("fn".into(), "load_user".into())]);
+
+    let location = ctx.location.as_ref().unwrap();
+    assert!(location.file.ends_with("rail_context.rs"));
+    assert!(location.line > 0);
+}
+
+#[test]
+fn without_arguments_records_only_fn_and_location() {
+    let err = unnamed().unwrap_err();
+    let ctx = group(&err.context()[0]);
+    assert_eq!(ctx.message, None);
+    assert_eq!(ctx.metadata.as_slice(), &[("fn".into(), "unnamed".into())]);
+    assert!(ctx.location.is_some());
+}
+
+#[test]
+fn question_mark_errors_are_wrapped() {
+    assert_eq!(parse(" 21 ").unwrap(), 42);
+
+    let err = parse("x").unwrap_err();
+    assert_eq!(group(&err.context()[0]).message.as_deref(), Some("parsing \"x\""));
+}
+
+#[test]
+fn boxed_results_keep_their_context_stack() {
+    let err = save_order(7).unwrap_err();
+    let messages: Vec<_> = err.context().iter().map(|ctx| ctx.to_string()).collect();
+    assert_eq!(messages.len(), 2);
+    assert!(messages[0].ends_with(": saving order 7 (fn=save_order)"));
+    assert_eq!(messages[1], "writing file");
+}
+
+#[test]
+fn rail_results_and_composable_errors_are_not_rewrapped() {
+    let err = open("secret.txt").unwrap_err();
+    assert_eq!(err.context().len(), 1);
+    assert!(err.to_string().contains("opening secret.txt"));
+
+    let err: ComposableError<&'static str> = inner().unwrap_err();
+    assert_eq!(*err.core_error(), "failed");
+    assert_eq!(group(&err.context()[0]).message.as_deref(), Some("inner step"));
+}
+
+#[test]
+fn context_is_only_formatted_on_error() {
+    let formats = Cell::new(0);
+    counted(Counted(&formats), false).unwrap();
+    assert_eq!(formats.get(), 0);
+
+    let err = counted(Counted(&formats), true).unwrap_err();
+    assert_eq!(formats.get(), 1);
+    assert_eq!(group(&err.context()[0]).message.as_deref(), Some("using counted"));
+}
+
+#[test]
+fn methods_can_reference_self_and_parameters() {
+    let err = Repo.find("k1").unwrap_err();
+    let ctx = group(&err.context()[0]);
+    assert_eq!(ctx.message.as_deref(), Some("finding k1 in repo"));
+    assert_eq!(ctx.metadata.as_slice(), &[("fn".into(), "find".into())]);
+}
+
+#[cfg(feature = "async")]
+mod async_fns {
+    use super::*;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    static CALLS: AtomicU32 = AtomicU32::new(0);
+
+    struct Tracked(u32);
+
+    impl fmt::Display for Tracked {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            CALLS.fetch_add(1, Ordering::SeqCst);
+            write!(f, "#{}", self.0)
+        }
+    }
+
+    #[rail_context("fetching {id}")]
+    async fn fetch(id: Tracked, fail: bool) -> Result<u32, &'static str> {
+        tokio::task::yield_now().await;
+        if fail {
+            return Err("timeout");
+        }
+        Ok(id.0)
+    }
+
+    #[rail_context("retrying {attempt}")]
+    async fn retry(attempt: u32) -> BoxedResult<(), &'static str> {
+        Err("gave up").ctx("attempt failed")
+    }
+
+    #[tokio::test]
+    async fn async_fn_formats_context_only_on_error() {
+        CALLS.store(0, Ordering::SeqCst);
+        assert_eq!(fetch(Tracked(3), false).await.unwrap(), 3);
+        assert_eq!(CALLS.load(Ordering::SeqCst), 0);
+
+        let err = fetch(Tracked(4), true).await.unwrap_err();
+        assert_eq!(CALLS.load(Ordering::SeqCst), 1);
+        assert_eq!(*err.core_error(), "timeout");
+        let ctx = group(&err.context()[0]);
+        assert_eq!(ctx.message.as_deref(), Some("fetching #4"));
+        assert_eq!(ctx.metadata.as_slice(), &[("fn".into(), "fetch".into())]);
+    }
+
+    #[tokio::test]
+    async fn async_fn_pushes_onto_boxed_result() {
+        let err = retry(2).await.unwrap_err();
+        let contexts = err.context();
+        assert_eq!(contexts.len(), 2);
+        assert_eq!(group(&contexts[0]).message.as_deref(), Some("retrying 2"));
+        assert_eq!(contexts[1].message(), "attempt failed");
+    }
+}
@@ -0,0 +1,132 @@
+use error_rail::{ErrorPipeline, TransientError};
+use std::time::Duration;
+
+#[allow(dead_code)]
+#[derive(Debug, TransientError)]
+enum ApiError {
+    #[transient]
+    Timeout,
+    #[retry_after(ms = 250)]
+    #[max_retries(3)]
+    RateLimited {
+        limit: u32,
+    },
+    #[transient]
+    #[retry_after(secs = 2)]
+    Unavailable(&'static str),
+    #[permanent]
+    BadRequest,
+    NotFound,
+    #[transient(delegate)]
+    Upstream(UpstreamError),
+    #[transient(delegate)]
+    Io {
+        source: std::io::Error,
+    },
+}
+
+#[derive(Debug, TransientError)]
+#[transient]
+enum UpstreamError {
+    Reset,
+    #[permanent]
+    Rejected,
+    #[max_retries(1)]
+    Overloaded,
+}
+
+#[derive(Debug, TransientError)]
+#[transient]
+#[retry_after(ms = 10)]
+struct Flaky;
+
+#[derive(Debug, TransientError)]
+#[transient(delegate)]
+struct Wrapper(ApiError);
+
+#[test]
+fn variants_are_classified_by_attributes() {
+    assert!(ApiError::Timeout.is_transient());
+    assert!(ApiError::RateLimited { limit: 10 }.is_transient());
+    assert!(ApiError::Unavailable("db").is_transient());
+    assert!(ApiError::BadRequest.is_permanent());
+    assert!(ApiError::NotFound.is_permanent());
+}
+
+#[test]
+fn hints_are_generated() {
+    assert_eq!(
+        ApiError::RateLimited { limit: 10 }.retry_after_hint(),
+        Some(Duration::from_millis(250))
+    );
+    assert_eq!(ApiError::RateLimited { limit: 10 }.max_retries_hint(), Some(3));
+    assert_eq!(ApiError::Unavailable("db").retry_after_hint(), Some(Duration::from_secs(2)));
+    assert_eq!(ApiError::Timeout.retry_after_hint(), None);
+    assert_eq!(ApiError::NotFound.max_retries_hint(), None);
+}
+
+#[test]
+fn delegated_variants_forward_to_inner_error() {
+    assert!(ApiError::Upstream(UpstreamError::Reset).is_transient());
+    assert!(ApiError::Upstream(UpstreamError::Rejected).is_permanent());
+    assert_eq!(ApiError::Upstream(UpstreamError::Overloaded).max_retries_hint(), Some(1));
+
+    let timed_out = ApiError::Io { source: std::io::ErrorKind::TimedOut.into() };
+    assert!(timed_out.is_transient());
+    let denied = ApiError::Io { source: std::io::ErrorKind::PermissionDenied.into() };
+    assert!(denied.is_permanent());
+}
+
+#[test]
+fn structs_use_container_attributes() {
+    assert!(Flaky.is_transient());
+    assert_eq!(Flaky.retry_after_hint(), Some(Duration::from_millis(10)));
+    assert!(Wrapper(ApiError::Timeout).is_transient());
+    assert!(Wrapper(ApiError::NotFound).is_permanent());
+}
+
+#[test]
+fn derived_errors_drive_pipeline_retries() {
+    let transient = ErrorPipeline::<(), ApiError>::new(Err(ApiError::Timeout));
+    assert!(transient.should_retry().is_some());
+
+    let permanent = ErrorPipeline::<(), ApiError>::new(Err(ApiError::BadRequest));
+    assert!(permanent.should_retry().is_none());
+}
+
+#[cfg(feature = "tokio")]
+#[tokio::test]
+async fn derived_errors_drive_async_retries() {
+    use error_rail::async_ext::{retry_transient, retry_with_policy, FixedDelay};
+    use std::cell::Cell;
+
+    let calls = Cell::new(0);
+    let result = retry_with_policy(
+        || {
+            calls.set(calls.get() + 1);
+            async {
+                if calls.get() < 3 {
+                    Err(ApiError::Timeout)
+                } else {
+                    Ok(calls.get())
+                }
+            }
+        },
+        FixedDelay::new(Duration::ZERO, 5),
+        |_| async {},
+    )
+    .await;
+    assert_eq!(result.unwrap(), 3);
+
+    let calls = Cell::new(0);
+    let result = retry_transient(
+        || {
+            calls.set(calls.get() + 1);
+            async { Err::<(), _>(ApiError::NotFound) }
+        },
+        FixedDelay::new(Duration::ZERO, 5),
+    )
+    .await;
+    assert!(result.is_err());
+    assert_eq!(calls.get(), 1);
+}
@@ -1,3 +1,5 @@
+#![cfg(feature = "alloc")]
+
 pub mod context;
 pub mod convert;
 pub mod macros;
@@ -7,3 +9,6 @@ pub mod validation;
 
 #[cfg(feature = "async")]
 pub mod async_ext;
+
+#[cfg(feature = "derive")]
+pub mod derive;
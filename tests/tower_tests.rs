@@ -290,3 +290,25 @@ fn test_error_rail_service_context_with_error_context() {
     let ctx = service.context();
     assert_eq!(ctx.message(), "[api]");
 }
+
+#[tokio::test]
+async fn layer_context_limit_travels_with_error() {
+    use error_rail::types::ContextLimit;
+
+    let layer = ErrorRailLayer::new("api-gateway").with_context_limit(ContextLimit::keep_last(2));
+    assert_eq!(layer.context_limit(), Some(ContextLimit::keep_last(2)));
+    let mut service = layer.layer(MockService::failing());
+
+    let err = service
+        .call("test".to_string())
+        .await
+        .unwrap_err()
+        .with_context("router")
+        .with_context("handler");
+
+    assert_eq!(err.dropped_contexts(), 1);
+    assert_eq!(
+        err.error_chain(),
+        "handler -> router -> ... 1 more context elided ... -> service error"
+    );
+}
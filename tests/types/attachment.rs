@@ -0,0 +1,110 @@
+use error_rail::{BoxedResultExt, ComposableError, ErrorPipeline, RailError, ResultExt};
+use std::time::Duration;
+
+#[derive(Debug, PartialEq)]
+struct Request {
+    id: u32,
+}
+
+#[test]
+fn attach_and_request_by_type() {
+    let err = ComposableError::new("timeout")
+        .attach(Request { id: 7 })
+        .attach(Duration::from_millis(10))
+        .attach(Duration::from_millis(20));
+
+    assert_eq!(err.request_ref::<Request>(), Some(&Request { id: 7 }));
+    assert_eq!(err.request_ref::<Duration>(), Some(&Duration::from_millis(20)));
+    assert_eq!(err.attachments::<Duration>().count(), 2);
+    assert!(err.request_ref::<String>().is_none());
+}
+
+#[test]
+fn attachments_are_excluded_from_display_by_default() {
+    let err = ComposableError::new("timeout")
+        .with_context("fetch")
+        .attach(Request { id: 1 })
+        .attach_display(Duration::from_secs(1).as_secs())
+        .set_code(504);
+
+    assert_eq!(err.to_string(), "fetch -> timeout (code: 504)");
+}
+
+#[test]
+fn printable_attachments_render_when_enabled() {
+    let err = ComposableError::new("timeout")
+        .attach(Request { id: 1 })
+        .attach_display("GET /users")
+        .attach_debug(Request { id: 2 })
+        .set_code(504);
+
+    assert_eq!(
+        err.fmt().show_attachments(true).to_string(),
+        "timeout (code: 504) -> attachment: GET /users -> attachment: Request { id: 2 }"
+    );
+    assert_eq!(
+        err.fmt().cascaded().show_attachments(true).to_string(),
+        "timeout (code: 504)\n  attachment: GET /users\n  attachment: Request { id: 2 }"
+    );
+}
+
+#[test]
+fn non_printable_attachments_leave_output_unchanged() {
+    let err = ComposableError::new("timeout")
+        .attach(Request { id: 1 })
+        .set_code(1);
+    assert_eq!(err.fmt().show_attachments(true).to_string(), "timeout (code: 1)");
+}
+
+#[test]
+fn attachments_are_preserved_through_map_core_and_boxed_ext() {
+    let err = ComposableError::new("timeout")
+        .attach(Request { id: 3 })
+        .map_core(|e| e.len());
+    assert_eq!(err.request_ref::<Request>(), Some(&Request { id: 3 }));
+
+    let result: Result<(), _> = Err(Box::new(err));
+    let err = result.ctx_boxed("outer").unwrap_err();
+    assert_eq!(err.request_ref::<Request>(), Some(&Request { id: 3 }));
+}
+
+#[test]
+fn pipeline_attachments_apply_on_error_only() {
+    let err = ErrorPipeline::<(), &str>::new(Err("failed"))
+        .attach(Request { id: 4 })
+        .with_context("step")
+        .finish()
+        .unwrap_err();
+    assert_eq!(err.request_ref::<Request>(), Some(&Request { id: 4 }));
+
+    let ok = ErrorPipeline::<u8, &str>::new(Ok(1))
+        .attach(Request { id: 5 })
+        .finish();
+    assert_eq!(ok.unwrap(), 1);
+
+    let recovered = ErrorPipeline::<u8, &str>::new(Err("failed"))
+        .attach(Request { id: 6 })
+        .fallback(2)
+        .map_error(|e| e.len())
+        .finish();
+    assert_eq!(recovered.unwrap(), 2);
+}
+
+#[test]
+fn attachments_survive_rail_error_conversion() {
+    let err: RailError = Err::<(), _>(std::io::Error::other("io"))
+        .ctx("reading")
+        .map_err(|e| e.attach(Request { id: 8 }))
+        .unwrap_err()
+        .into();
+    assert_eq!(err.request_ref::<Request>(), Some(&Request { id: 8 }));
+}
+
+#[test]
+fn attachments_are_ignored_by_equality_and_kept_by_clone() {
+    let plain = ComposableError::new("e");
+    let attached = ComposableError::new("e").attach(Request { id: 9 });
+
+    assert_eq!(plain, attached);
+    assert_eq!(attached.clone().request_ref::<Request>(), Some(&Request { id: 9 }));
+}
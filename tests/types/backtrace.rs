@@ -0,0 +1,141 @@
+use error_rail::types::{BacktraceFilter, BacktraceFrame, ErrorBacktrace};
+use error_rail::ComposableError;
+
+const TRACE: &str = "   0: app::db::connect
+             at ./src/db.rs:12:9
+   1: <app::db::Pool as core::ops::Drop>::drop
+             at ./src/db.rs:40:5
+   2: error_rail::types::error_pipeline::ErrorPipeline<T,E>::finish
+             at ./src/types/error_pipeline.rs:10:5
+   3: tokio::runtime::task::harness::poll_future
+             at /cargo/tokio/src/runtime/task/harness.rs:473:19
+   4: core::ops::function::FnOnce::call_once
+             at /rustc/library/core/src/ops/function.rs:250:5
+   5: app::main
+             at C:\\work\\app\\src\\main.rs:4:5
+   6: main
+   7: __libc_start_main
+   8: _start
+";
+
+fn functions(trace: &ErrorBacktrace) -> Vec<&str> {
+    trace
+        .frames()
+        .iter()
+        .map(|frame| frame.function.as_str())
+        .collect()
+}
+
+#[test]
+fn parse_reads_functions_and_locations() {
+    let trace = ErrorBacktrace::parse(TRACE, &BacktraceFilter::all());
+
+    assert_eq!(trace.frames().len(), 9);
+    let first = &trace.frames()[0];
+    assert_eq!(first.function, "app::db::connect");
+    assert_eq!(first.file.as_deref(), Some("./src/db.rs"));
+    assert_eq!((first.line, first.column), (Some(12), Some(9)));
+
+    let windows = &trace.frames()[5];
+    assert_eq!(windows.file.as_deref(), Some("C:\\work\\app\\src\\main.rs"));
+    assert_eq!(windows.line, Some(4));
+
+    assert_eq!(trace.frames()[6].file, None);
+}
+
+#[test]
+fn default_filter_hides_std_runtime_and_error_rail_frames() {
+    let trace = ErrorBacktrace::parse(TRACE, &BacktraceFilter::new());
+
+    assert_eq!(
+        functions(&trace),
+        ["app::db::connect", "<app::db::Pool as core::ops::Drop>::drop", "app::main"]
+    );
+}
+
+#[test]
+fn filter_toggles_and_frame_limit() {
+    let keep_runtime = BacktraceFilter::new().hide_runtime(false);
+    let trace = ErrorBacktrace::parse(TRACE, &keep_runtime);
+    assert!(functions(&trace).contains(&"tokio::runtime::task::harness::poll_future"));
+
+    let limited = ErrorBacktrace::parse(TRACE, &BacktraceFilter::new().max_frames(2));
+    assert_eq!(
+        functions(&limited),
+        ["app::db::connect", "<app::db::Pool as core::ops::Drop>::drop"]
+    );
+}
+
+#[test]
+fn frames_report_their_crate() {
+    assert_eq!(BacktraceFrame::new("app::db::connect").crate_name(), Some("app"));
+    assert_eq!(BacktraceFrame::new("<&dyn core::ops::Fn<()>>::call").crate_name(), Some("core"));
+    assert_eq!(
+        BacktraceFrame::new("std[e28293b1aa0f68bd]::rt::lang_start").crate_name(),
+        Some("std")
+    );
+    assert!(BacktraceFrame::new("_start").is_std());
+    assert!(BacktraceFrame::new("tokio::spawn").is_runtime());
+    assert!(BacktraceFrame::new("error_rail::ComposableError::new").is_error_rail());
+}
+
+#[test]
+fn backtrace_is_only_rendered_in_verbose_mode() {
+    let trace = ErrorBacktrace::parse(TRACE, &BacktraceFilter::new().max_frames(2));
+    let err = ComposableError::new("connect failed")
+        .with_context("loading user")
+        .with_backtrace(trace);
+
+    assert_eq!(err.to_string(), "loading user -> connect failed");
+    assert_eq!(err.error_chain(), "loading user -> connect failed");
+    assert_eq!(
+        err.fmt().show_backtrace(true).to_string(),
+        "loading user -> connect failed -> backtrace: app::db::connect at ./src/db.rs:12:9 \
+         -> <app::db::Pool as core::ops::Drop>::drop at ./src/db.rs:40:5"
+    );
+    assert!(err
+        .fmt()
+        .verbose()
+        .to_string()
+        .contains("\n├─ backtrace: app::db::connect at ./src/db.rs:12:9\n"));
+}
+
+#[test]
+fn empty_backtraces_are_not_attached() {
+    let err = ComposableError::new("boom").with_backtrace(ErrorBacktrace::default());
+
+    assert!(err.backtrace().is_none());
+}
+
+#[test]
+fn cause_layers_keep_their_backtrace() {
+    let trace = ErrorBacktrace::parse(TRACE, &BacktraceFilter::new().max_frames(1));
+    let err = ComposableError::new("loading user failed")
+        .with_cause(ComposableError::new("connect failed").with_backtrace(trace.clone()));
+
+    assert_eq!(err.cause().unwrap().backtrace(), Some(&trace));
+    assert_eq!(
+        err.fmt().show_backtrace(true).to_string(),
+        "loading user failed -> caused by: connect failed -> backtrace: app::db::connect at ./src/db.rs:12:9"
+    );
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn backtrace_serializes_as_array_of_frames() {
+    let trace = ErrorBacktrace::parse(TRACE, &BacktraceFilter::new());
+    let err = ComposableError::new("boom".to_string()).with_backtrace(trace);
+
+    let json = serde_json::to_value(&err).unwrap();
+    assert_eq!(
+        json["backtrace"][0],
+        serde_json::json!({ "function": "app::db::connect", "file": "./src/db.rs", "line": 12, "column": 9 })
+    );
+    assert_eq!(json["backtrace"].as_array().unwrap().len(), 3);
+
+    let back: ComposableError<String> = serde_json::from_value(json).unwrap();
+    assert_eq!(back.backtrace(), err.backtrace());
+
+    let plain = serde_json::to_value(ComposableError::new("p".to_string())).unwrap();
+    assert!(plain.get("backtrace").is_none());
+}
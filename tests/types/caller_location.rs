@@ -0,0 +1,300 @@
+use error_rail::types::{caller_location, Location};
+use error_rail::{ComposableError, ResultExt};
+#[cfg(feature = "caller-location")]
+use std::sync::{Mutex, PoisonError};
+
+/// Serializes tests that depend on the process-wide capture switch.
+#[cfg(feature = "caller-location")]
+static CAPTURE: Mutex<()> = Mutex::new(());
+
+#[cfg(feature = "caller-location")]
+fn site<E>(err: &ComposableError<E>) -> (&str, u32) {
+    let location = err.location().expect("location captured");
+    (location.file.as_ref(), location.line)
+}
+
+#[test]
+fn location_display_includes_column_and_module() {
+    let location = Location::new("src/lib.rs", 7)
+        .with_column(3)
+        .with_module_path("app::db");
+
+    assert_eq!(location.to_string(), "src/lib.rs:7:3 in app::db");
+    assert_eq!(Location::new("src/lib.rs", 7).to_string(), "src/lib.rs:7");
+}
+
+#[test]
+fn explicit_location_is_rendered_when_enabled() {
+    let err = ComposableError::new("timeout")
+        .with_context("fetching user")
+        .with_location(Location::new("src/api.rs", 42).with_column(9));
+
+    assert_eq!(err.location().unwrap().column, Some(9));
+    assert!(!err.fmt().to_string().contains("src/api.rs"));
+    assert_eq!(
+        err.fmt().show_locations(true).to_string(),
+        "fetching user -> timeout (at src/api.rs:42:9)"
+    );
+}
+
+#[test]
+fn cause_layers_keep_their_location() {
+    let db = ComposableError::new("connection refused").with_location(Location::new("db.rs", 3));
+    let err = ComposableError::new("loading user failed")
+        .with_location(Location::new("api.rs", 10))
+        .with_cause(db);
+
+    assert_eq!(err.cause().unwrap().location(), Some(&Location::new("db.rs", 3)));
+    assert_eq!(
+        err.fmt().show_locations(true).to_string(),
+        "loading user failed (at api.rs:10) -> caused by: connection refused (at db.rs:3)"
+    );
+}
+
+#[test]
+fn location_is_ignored_by_equality() {
+    let a = ComposableError::new("boom").with_location(Location::new("a.rs", 1));
+    let b = ComposableError::new("boom").with_location(Location::new("b.rs", 2));
+
+    assert_eq!(a, b);
+}
+
+#[cfg(feature = "caller-location")]
+#[test]
+fn entry_points_capture_their_caller() {
+    use error_rail::ErrorPipeline;
+
+    let _guard = CAPTURE.lock().unwrap_or_else(PoisonError::into_inner);
+
+    let err = ComposableError::new("boom");
+    assert_eq!(site(&err), (file!(), line!() - 1));
+
+    let err = Err::<(), _>("boom").ctx("loading").unwrap_err();
+    assert_eq!(site(&err), (file!(), line!() - 1));
+
+    let err = ErrorPipeline::<(), _>::new(Err("boom"))
+        .with_context("loading")
+        .finish()
+        .unwrap_err();
+    assert_eq!(site(&err), (file!(), line!() - 4));
+
+    let err: ComposableError<&str> = "boom".into();
+    assert_eq!(site(&err), (file!(), line!() - 1));
+    assert!(err.location().unwrap().column.is_some());
+}
+
+#[cfg(feature = "caller-location")]
+#[test]
+fn question_mark_captures_conversion_site() {
+    let _guard = CAPTURE.lock().unwrap_or_else(PoisonError::into_inner);
+
+    fn fails() -> Result<(), ComposableError<&'static str>> {
+        Err("boom")?;
+        Ok(())
+    }
+
+    let err = fails().unwrap_err();
+    assert_eq!(site(&err), (file!(), line!() - 5));
+}
+
+#[cfg(feature = "caller-location")]
+#[test]
+fn first_capture_site_is_kept() {
+    use error_rail::BoxedResultExt;
+
+    let _guard = CAPTURE.lock().unwrap_or_else(PoisonError::into_inner);
+
+    let inner = Err::<(), _>("boom").ctx("inner").unwrap_err();
+    let line = inner.location().unwrap().line;
+    let err = Err::<(), _>(inner).ctx_boxed("outer").unwrap_err();
+
+    assert_eq!(err.location().unwrap().line, line);
+    assert_eq!(err.context().len(), 2);
+}
+
+#[cfg(feature = "caller-location")]
+#[test]
+fn capture_can_be_disabled_at_runtime() {
+    let _guard = CAPTURE.lock().unwrap_or_else(PoisonError::into_inner);
+
+    caller_location::set_enabled(false);
+    let err = Err::<(), _>("boom").ctx("loading").unwrap_err();
+    caller_location::set_enabled(true);
+
+    assert!(err.location().is_none());
+    assert!(caller_location::is_enabled());
+    assert!(ComposableError::new("boom").location().is_some());
+}
+
+#[cfg(not(feature = "caller-location"))]
+#[test]
+fn nothing_is_captured_without_the_feature() {
+    assert!(!caller_location::is_enabled());
+    assert!(ComposableError::new("boom").location().is_none());
+    assert!(Err::<(), _>("boom")
+        .ctx("loading")
+        .unwrap_err()
+        .location()
+        .is_none());
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn location_round_trips_through_serde() {
+    let err = ComposableError::new("boom".to_string())
+        .with_context(error_rail::ErrorContext::tag("db"))
+        .with_location(Location::new("src/db.rs", 5).with_column(2));
+
+    let json = serde_json::to_value(&err).unwrap();
+    assert_eq!(json["location"]["line"], 5);
+
+    let back: ComposableError<String> = serde_json::from_value(json).unwrap();
+    assert_eq!(back.location(), err.location());
+}
+
+#[cfg(feature = "caller-location")]
+#[test]
+fn pipeline_records_first_site_that_sees_the_error() {
+    use error_rail::ErrorPipeline;
+
+    let _guard = CAPTURE.lock().unwrap_or_else(PoisonError::into_inner);
+
+    let err = ErrorPipeline::<u32, &str>::new(Ok(1))
+        .and_then(|_| Err::<u32, _>("boom"))
+        .with_context("loading")
+        .context("parsing")
+        .finish()
+        .unwrap_err();
+    assert_eq!(site(&err), (file!(), line!() - 4));
+
+    let err = ErrorPipeline::<u32, &str>::new(Ok(1))
+        .and_then(|_| Err::<u32, _>("boom"))
+        .finish_boxed()
+        .unwrap_err();
+    assert_eq!(site(&err), (file!(), line!() - 2));
+}
+
+#[cfg(feature = "caller-location")]
+fn lines<'a>(sites: impl Iterator<Item = Option<&'a Location>>) -> Vec<Option<u32>> {
+    sites.map(|site| site.map(|site| site.line)).collect()
+}
+
+#[cfg(feature = "caller-location")]
+#[test]
+fn each_context_keeps_its_own_site() {
+    use error_rail::BoxedResultExt;
+
+    let _guard = CAPTURE.lock().unwrap_or_else(PoisonError::into_inner);
+
+    let (res, a) = (Err::<(), _>("timeout").ctx("fetching user"), line!());
+    let (res, b) = (res.ctx_boxed("loading profile"), line!());
+    let (res, c) = (res.ctx_boxed_with(|| "rendering".into()), line!());
+    let err = res.unwrap_err();
+
+    assert_eq!(site(&err), (file!(), a));
+    assert_eq!(lines(err.context_sites()), [Some(c), Some(b), Some(a)]);
+
+    let sites = err.context_sites().flatten().collect::<Vec<_>>();
+    assert_eq!(
+        err.fmt().show_locations(true).to_string(),
+        format!(
+            "rendering (at {}) -> loading profile (at {}) -> fetching user (at {}) -> timeout (at {})",
+            sites[0],
+            sites[1],
+            sites[2],
+            err.location().unwrap()
+        )
+    );
+    assert_eq!(err.to_string(), "rendering -> loading profile -> fetching user -> timeout");
+}
+
+#[cfg(feature = "caller-location")]
+#[test]
+fn pipeline_contexts_keep_their_own_sites() {
+    use error_rail::types::{ContextCompaction, ContextLimit};
+    use error_rail::ErrorPipeline;
+
+    let _guard = CAPTURE.lock().unwrap_or_else(PoisonError::into_inner);
+
+    let (pipeline, start) = (ErrorPipeline::<(), _>::new(Err("boom")), line!());
+    let pipeline = pipeline.with_context("step 1");
+    let (pipeline, b) = (pipeline.with_context("step 2"), line!());
+    let pipeline = pipeline.with_context_limit(ContextLimit::keep_last(2));
+    let (pipeline, c) = (pipeline.with_context("step 3"), line!());
+    let err = pipeline.finish().unwrap_err();
+
+    assert_eq!(site(&err), (file!(), start));
+    assert_eq!(lines(err.context_sites()), [Some(c), Some(b)]);
+
+    let pipeline = ErrorPipeline::<(), _>::new(Err("boom"))
+        .with_compaction(ContextCompaction::new().counter_key("retry_attempt"));
+    let (pipeline, _) = (pipeline.with_retry_context(1), line!());
+    let (pipeline, d) = (pipeline.with_retry_context(2), line!());
+    let err = pipeline.finish().unwrap_err();
+
+    assert_eq!(lines(err.context_sites()), [Some(d)]);
+}
+
+#[cfg(feature = "caller-location")]
+#[test]
+fn contexts_added_without_capture_have_no_site() {
+    let _guard = CAPTURE.lock().unwrap_or_else(PoisonError::into_inner);
+
+    let (err, line) = (Err::<(), _>("boom").ctx("loading").unwrap_err(), line!());
+    let err = err.with_context("parsing");
+
+    assert_eq!(lines(err.context_sites()), [None, Some(line)]);
+}
+
+#[cfg(feature = "caller-location")]
+#[test]
+fn cause_layers_keep_their_context_sites() {
+    let _guard = CAPTURE.lock().unwrap_or_else(PoisonError::into_inner);
+
+    let (db, line) = (Err::<(), _>("refused").ctx("querying").unwrap_err(), line!());
+    let err = ComposableError::new("loading user failed").with_cause(db);
+    let cause = err.cause().unwrap();
+
+    assert_eq!(lines(cause.context_sites()), [Some(line)]);
+    assert!(err
+        .fmt()
+        .show_locations(true)
+        .to_string()
+        .contains(&format!("querying (at {})", cause.context_sites().next().flatten().unwrap())));
+}
+
+#[cfg(feature = "caller-location")]
+#[test]
+fn rail_error_keeps_context_sites_when_merging() {
+    use error_rail::types::{RailError, RailResultExt};
+
+    let _guard = CAPTURE.lock().unwrap_or_else(PoisonError::into_inner);
+
+    let io = std::io::Error::other("disk full");
+    let (res, a) = (Err::<(), _>(io).ctx("loading config"), line!());
+    let rail = RailError::from(res.unwrap_err());
+    let (res, b) = (Err::<(), _>(rail).ctx("reading"), line!());
+    let rail = RailError::from(res.unwrap_err());
+    let (res, c) = (Err::<(), _>(rail).ctx_rail("starting app"), line!());
+
+    assert_eq!(lines(res.unwrap_err().context_sites()), [Some(c), Some(b), Some(a)]);
+}
+
+#[cfg(all(feature = "caller-location", feature = "serde"))]
+#[test]
+fn context_sites_round_trip_through_serde() {
+    let _guard = CAPTURE.lock().unwrap_or_else(PoisonError::into_inner);
+
+    let (err, line) = (Err::<(), _>("boom".to_string()).ctx("loading"), line!());
+    let err = err.unwrap_err();
+
+    let json = serde_json::to_value(&err).unwrap();
+    assert_eq!(json["context_sites"][0]["line"], line);
+
+    let back: ComposableError<String> = serde_json::from_value(json).unwrap();
+    assert!(back.context_sites().eq(err.context_sites()));
+    assert!(serde_json::to_value(ComposableError::new("boom"))
+        .unwrap()
+        .get("context_sites")
+        .is_none());
+}
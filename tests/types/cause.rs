@@ -0,0 +1,323 @@
+use core::error::Error;
+use error_rail::types::ErrorCause;
+use error_rail::{ComposableError, ErrorContext, ErrorFormatConfig};
+use std::io;
+
+fn layered() -> ComposableError<&'static str> {
+    let root = ComposableError::new("timeout")
+        .with_context(ErrorContext::tag("net"))
+        .set_code(7);
+    let db = ComposableError::new("query failed")
+        .with_context("loading rows")
+        .set_code(42)
+        .with_cause(root);
+    ComposableError::new("request failed")
+        .with_context("handling /users")
+        .set_code(500)
+        .with_cause(db)
+}
+
+#[test]
+fn with_cause_preserves_contexts_and_code() {
+    let err = layered();
+    let cause = err.cause().unwrap();
+
+    assert_eq!(cause.message(), "query failed");
+    assert_eq!(cause.error_code(), Some(42));
+    assert_eq!(cause.context_iter().next().unwrap().message(), "loading rows");
+    assert_eq!(cause.cause().unwrap().message(), "timeout");
+}
+
+#[test]
+fn causes_iterates_nearest_first() {
+    let err = layered();
+    let messages: Vec<_> = err.causes().map(|c| c.message()).collect();
+    assert_eq!(messages, ["query failed", "timeout"]);
+
+    let no_cause = ComposableError::<&str>::new("alone");
+    assert_eq!(no_cause.causes().count(), 0);
+}
+
+#[test]
+fn error_chain_renders_each_layer() {
+    let err = layered();
+    assert_eq!(
+        err.error_chain(),
+        "handling /users -> request failed (code: 500) -> caused by: loading rows -> \
+         query failed (code: 42) -> caused by: [net] -> timeout (code: 7)"
+    );
+}
+
+#[test]
+fn error_chain_respects_show_code_and_prefix() {
+    let err = layered();
+    let out = err.fmt().show_code(false).cause_prefix("<- ").to_string();
+    assert_eq!(
+        out,
+        "handling /users -> request failed -> <- loading rows -> query failed -> <- [net] -> timeout"
+    );
+}
+
+#[test]
+fn cascaded_output_indents_cause_layers() {
+    let inner = ComposableError::new("inner");
+    let err = ComposableError::new("outer").with_cause(inner);
+
+    assert_eq!(err.fmt().cascaded().to_string(), "outer\n  caused by: inner");
+}
+
+#[test]
+fn error_chain_with_custom_formatter_includes_causes() {
+    let err = layered();
+    let out = err.error_chain_with(ErrorFormatConfig::compact());
+    assert_eq!(
+        out,
+        "handling /users | request failed | caused by: loading rows | query failed | caused by: [net] | timeout"
+    );
+}
+
+#[test]
+fn source_returns_nearest_cause() {
+    let io_err = io::Error::other("disk full");
+    let err = ComposableError::new(io::Error::other("save failed"))
+        .with_cause(ErrorCause::from_error(io_err));
+
+    let first = err.source().unwrap();
+    assert_eq!(first.to_string(), "disk full");
+    assert!(err.cause().unwrap().downcast_ref::<io::Error>().is_some());
+}
+
+#[test]
+fn source_walks_wrapped_composable_layers() {
+    let root = ComposableError::new(io::Error::other("connection reset")).with_context("reading");
+    let db = ComposableError::new(io::Error::other("query failed")).with_cause(root);
+    let api = ComposableError::new(io::Error::other("request failed")).with_cause(db);
+
+    let mut messages = Vec::new();
+    let mut current = api.source();
+    while let Some(layer) = current {
+        messages.push(layer.to_string());
+        current = layer.source();
+    }
+    assert_eq!(messages, ["query failed", "reading -> connection reset"]);
+}
+
+#[test]
+fn source_without_cause_is_core_error() {
+    let err = ComposableError::new(io::Error::other("root"));
+    assert_eq!(err.source().unwrap().to_string(), "root");
+}
+
+#[test]
+fn boxed_composable_error_converts_into_cause() {
+    let boxed = Box::new(ComposableError::new("inner").with_context("ctx"));
+    let err = ComposableError::new("outer").with_cause(boxed);
+    assert_eq!(err.cause().unwrap().context_iter().count(), 1);
+}
+
+#[test]
+fn map_core_keeps_cause() {
+    let err = layered().map_core(|e| e.len());
+    assert_eq!(err.causes().count(), 2);
+}
+
+#[test]
+fn fingerprint_covers_causes() {
+    let with_cause = layered();
+    let mut other = layered();
+    other = other.with_cause(ComposableError::new("different"));
+
+    assert_ne!(with_cause.fingerprint(), other.fingerprint());
+    assert_eq!(
+        with_cause
+            .fingerprint_config()
+            .include_causes(false)
+            .compute(),
+        other.fingerprint_config().include_causes(false).compute()
+    );
+}
+
+#[test]
+fn fingerprint_without_cause_is_unchanged() {
+    let err = ComposableError::new("e").set_code(1);
+    assert_eq!(err.fingerprint(), err.fingerprint_config().include_causes(false).compute());
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn serde_round_trips_cause_chain() {
+    let err = layered().map_core(|e| e.to_string());
+    let json = serde_json::to_string(&err).unwrap();
+    assert!(json.contains("\"causes\""));
+
+    let back: ComposableError<String> = serde_json::from_str(&json).unwrap();
+    assert_eq!(back, err);
+
+    let plain = ComposableError::new("plain".to_string());
+    let json = serde_json::to_string(&plain).unwrap();
+    assert!(!json.contains("\"causes\""));
+}
+
+fn tree() -> ComposableError<&'static str> {
+    let a = ComposableError::new("a").with_context("a-ctx");
+    let b = ComposableError::new("b").with_cause(ComposableError::new("b-root"));
+    ComposableError::aggregate("all backends failed", [a, b])
+}
+
+#[test]
+fn aggregate_keeps_children() {
+    let err = tree();
+    let children = err.children();
+
+    assert_eq!(children.len(), 2);
+    assert_eq!(children[0].context_iter().next().unwrap().message(), "a-ctx");
+    assert_eq!(children[1].children()[0].message(), "b-root");
+    assert_eq!(err.cause().unwrap().message(), "a");
+}
+
+#[test]
+fn with_causes_appends_siblings() {
+    let err = ComposableError::new("outer")
+        .with_cause(ComposableError::new("first"))
+        .with_causes([ComposableError::new("second"), ComposableError::new("third")]);
+    assert_eq!(err.children().len(), 3);
+
+    let empty = ComposableError::new("outer").with_causes(Vec::<ErrorCause>::new());
+    assert!(empty.children().is_empty());
+}
+
+#[test]
+fn causes_iterates_tree_depth_first() {
+    let err = tree();
+    let messages: Vec<_> = err.causes().map(|c| c.message()).collect();
+    assert_eq!(messages, ["a", "b", "b-root"]);
+}
+
+#[test]
+fn pretty_output_indents_tree_branches() {
+    let err = tree();
+    assert_eq!(
+        err.fmt().pretty().to_string(),
+        "┌ all backends failed\n  ├─ caused by: a-ctx\n  ├─ a\n  ├─ caused by: b\n    └─ caused by: b-root"
+    );
+}
+
+#[test]
+fn cascaded_output_indents_siblings_under_parent() {
+    let err = tree();
+    assert_eq!(
+        err.fmt().cascaded().to_string(),
+        "all backends failed\n  caused by: a-ctx\n    a\n  caused by: b\n    caused by: b-root"
+    );
+}
+
+#[test]
+fn fingerprint_depends_on_tree_shape() {
+    let siblings =
+        ComposableError::aggregate("outer", [ComposableError::new("x"), ComposableError::new("y")]);
+    let nested = ComposableError::new("outer")
+        .with_cause(ComposableError::new("x").with_cause(ComposableError::new("y")));
+
+    assert_ne!(siblings.fingerprint(), nested.fingerprint());
+    assert_eq!(siblings.fingerprint(), siblings.clone().fingerprint());
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn serde_round_trips_cause_tree() {
+    let err = tree().map_core(|e| e.to_string());
+    let json = serde_json::to_string(&err).unwrap();
+
+    let back: ComposableError<String> = serde_json::from_str(&json).unwrap();
+    assert_eq!(back, err);
+    assert_eq!(back.children().len(), 2);
+}
+
+#[derive(Debug)]
+struct Wrapped(&'static str, Option<Box<Wrapped>>);
+
+impl std::fmt::Display for Wrapped {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.0)
+    }
+}
+
+impl Error for Wrapped {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        self.1
+            .as_deref()
+            .map(|inner| inner as &(dyn Error + 'static))
+    }
+}
+
+fn wrapped() -> Wrapped {
+    Wrapped(
+        "request failed",
+        Some(Box::new(Wrapped("tls handshake", Some(Box::new(Wrapped("eof", None)))))),
+    )
+}
+
+#[test]
+fn with_source_chain_captures_each_source_as_a_layer() {
+    let err = ComposableError::new(wrapped())
+        .with_context("fetch")
+        .with_source_chain();
+
+    let messages: Vec<_> = err.causes().map(|c| c.message()).collect();
+    assert_eq!(messages, ["tls handshake", "eof"]);
+    assert_eq!(
+        err.error_chain(),
+        "fetch -> request failed -> caused by: tls handshake -> caused by: eof"
+    );
+}
+
+#[test]
+fn with_source_chain_without_source_is_noop() {
+    let err = ComposableError::new(Wrapped("alone", None)).with_source_chain();
+    assert!(err.children().is_empty());
+}
+
+#[test]
+fn source_chain_contributes_to_fingerprint() {
+    let plain = ComposableError::new(wrapped());
+    let captured = ComposableError::new(wrapped()).with_source_chain();
+    assert_ne!(plain.fingerprint(), captured.fingerprint());
+}
+
+#[test]
+fn from_error_chain_keeps_original_and_sources() {
+    let cause = ErrorCause::from_error_chain(wrapped());
+
+    assert_eq!(cause.message(), "request failed");
+    assert!(cause.downcast_ref::<Wrapped>().is_some());
+    assert_eq!(cause.causes().map(|c| c.message()).collect::<Vec<_>>(), ["tls handshake", "eof"]);
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn source_chain_is_serialized() {
+    let err = ComposableError::new(wrapped())
+        .with_source_chain()
+        .map_core(|e| e.to_string());
+    let json = serde_json::to_value(&err).unwrap();
+    assert_eq!(json["causes"][0]["message"], "tls handshake");
+    assert_eq!(json["causes"][0]["causes"][0]["message"], "eof");
+}
+
+#[test]
+fn cause_conversion_keeps_failure_details_only() {
+    use error_rail::types::Severity;
+
+    let inner = ComposableError::new("timeout")
+        .set_severity(Severity::Critical)
+        .with_trace_id("trace-1")
+        .with_public_message("try again later")
+        .attach(7_u32);
+    let err = ComposableError::new("outer").with_cause(inner);
+
+    let cause = err.cause().unwrap();
+    assert_eq!(cause.severity(), Some(Severity::Critical));
+    assert_eq!(cause.trace_ids().and_then(|ids| ids.trace_id.as_deref()), Some("trace-1"));
+    assert!(err.request_ref::<u32>().is_none());
+    assert_eq!(err.public_view().to_string(), "an internal error occurred");
+}
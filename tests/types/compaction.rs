@@ -0,0 +1,221 @@
+use error_rail::types::{ContextCompaction, ContextLimit, Timestamp};
+use error_rail::{ComposableError, ErrorContext, ErrorPipeline};
+
+fn retries() -> ContextCompaction {
+    ContextCompaction::new().counter_key("retry_attempt")
+}
+
+fn attempt(n: u32) -> ErrorContext {
+    ErrorContext::builder()
+        .tag("http")
+        .metadata("retry_attempt", n)
+        .build()
+}
+
+#[test]
+fn identical_contexts_fold_into_a_repeat_count() {
+    let mut err = ComposableError::new("timeout").with_compaction(ContextCompaction::new());
+    for _ in 0..3 {
+        err = err.with_context("polling").with_context("polling");
+    }
+
+    assert_eq!(err.context().len(), 1);
+    let ErrorContext::Group(group) = &err.context()[0] else { panic!("expected a group") };
+    assert_eq!(group.repeat.as_ref().map(|r| r.count), Some(6));
+    assert_eq!(err.error_chain(), "polling (x6) -> timeout");
+}
+
+#[test]
+fn counters_fold_into_a_range() {
+    let err = ComposableError::new("timeout")
+        .with_compaction(retries())
+        .with_contexts((1..=5).map(attempt));
+
+    assert_eq!(err.context().len(), 1);
+    assert_eq!(err.error_chain(), "[http] (retry_attempt=1..5) -> timeout");
+}
+
+#[test]
+fn different_contexts_are_kept_apart() {
+    let err = ComposableError::new("timeout")
+        .with_compaction(retries())
+        .with_context(attempt(1))
+        .with_context(
+            ErrorContext::builder()
+                .tag("db")
+                .metadata("retry_attempt", 2)
+                .build(),
+        )
+        .with_context("a")
+        .with_context("b")
+        .with_context("a");
+
+    assert_eq!(err.context().len(), 5);
+    assert!(!err.error_chain().contains("(x"));
+    assert!(!err.error_chain().contains(".."));
+}
+
+#[test]
+fn counters_are_not_folded_without_a_counter_key() {
+    let err = ComposableError::new("timeout")
+        .with_compaction(ContextCompaction::new())
+        .with_contexts((1..=3).map(attempt))
+        .with_context(attempt(3));
+
+    assert_eq!(err.context().len(), 3);
+    assert_eq!(
+        err.error_chain(),
+        "[http] (retry_attempt=3) (x2) -> [http] (retry_attempt=2) -> [http] (retry_attempt=1) -> timeout"
+    );
+}
+
+#[test]
+fn timestamps_are_ignored_and_the_latest_is_kept() {
+    let err = ComposableError::new("timeout")
+        .with_compaction(ContextCompaction::new())
+        .with_context(ErrorContext::new("polling").with_timestamp(Timestamp::from_unix_millis(1)))
+        .with_context(ErrorContext::new("polling").with_timestamp(Timestamp::from_unix_millis(2)));
+
+    assert_eq!(err.context().len(), 1);
+    assert_eq!(err.context()[0].timestamp(), Some(Timestamp::from_unix_millis(2)));
+}
+
+#[test]
+fn existing_contexts_are_compacted() {
+    let err = ComposableError::new("timeout")
+        .with_contexts((1..=3).map(attempt))
+        .with_compaction(retries());
+
+    assert_eq!(err.context().len(), 1);
+    assert_eq!(err.error_chain(), "[http] (retry_attempt=1..3) -> timeout");
+}
+
+#[test]
+fn render_time_compaction_leaves_contexts_untouched() {
+    let err = ComposableError::new("timeout")
+        .with_context("connecting")
+        .with_contexts((1..=4).map(attempt))
+        .with_context("polling")
+        .with_context("polling");
+
+    let chain = err.fmt().compaction(retries()).to_string();
+    assert_eq!(chain, "polling (x2) -> [http] (retry_attempt=1..4) -> connecting -> timeout");
+    assert_eq!(err.context().len(), 7);
+
+    let reversed = err
+        .fmt()
+        .compaction(retries())
+        .reverse_context(true)
+        .to_string();
+    assert_eq!(reversed, "connecting -> [http] (retry_attempt=1..4) -> polling (x2) -> timeout");
+}
+
+#[test]
+fn compaction_happens_before_the_limit() {
+    let err = ComposableError::new("timeout")
+        .with_context_limit(ContextLimit::keep_last(2))
+        .with_compaction(retries())
+        .with_context("connecting")
+        .with_contexts((1..=10).map(attempt));
+
+    assert_eq!(err.dropped_contexts(), 0);
+    assert_eq!(err.error_chain(), "[http] (retry_attempt=1..10) -> connecting -> timeout");
+}
+
+#[test]
+fn runs_do_not_cross_the_elision_marker() {
+    let mut err = ComposableError::new("timeout").with_context_limit(ContextLimit::keep_first(1));
+    for _ in 0..3 {
+        err = err.with_context("polling");
+    }
+    let err = err
+        .with_compaction(ContextCompaction::new())
+        .with_context("polling");
+
+    assert_eq!(err.dropped_contexts(), 3);
+    assert_eq!(err.error_chain(), "... 3 more contexts elided ... -> polling -> timeout");
+
+    let err = ComposableError::new("timeout")
+        .with_context_limit(ContextLimit::keep_ends(2))
+        .with_contexts(["a", "x", "b", "a"].map(ErrorContext::new));
+    assert_eq!(
+        err.fmt().compaction(ContextCompaction::new()).to_string(),
+        "a -> ... 2 more contexts elided ... -> a -> timeout"
+    );
+}
+
+#[test]
+fn secret_counters_are_redacted() {
+    let ctx = |n: u32| {
+        ErrorContext::builder()
+            .secret_metadata("retry_attempt", n)
+            .build()
+    };
+    let err = ComposableError::new("timeout")
+        .with_compaction(retries())
+        .with_contexts((1..=3).map(ctx));
+
+    assert_eq!(err.error_chain(), "(retry_attempt=[REDACTED]..[REDACTED]) -> timeout");
+    assert_eq!(err.unredacted().to_string(), "(retry_attempt=1..3) -> timeout");
+    assert!(!format!("{:?}", err).contains("U64(1)"));
+}
+
+#[test]
+fn pipeline_compacts_retry_contexts_on_finish() {
+    let mut pipeline = ErrorPipeline::<(), &str>::new(Err("timeout")).with_compaction(retries());
+    for attempt in 1..=5 {
+        pipeline = pipeline.with_retry_context(attempt);
+    }
+    let err = pipeline.finish_boxed().unwrap_err();
+
+    assert_eq!(err.context().len(), 1);
+    assert_eq!(err.compaction(), Some(&retries()));
+    assert_eq!(err.error_chain(), "(retry_attempt=1..5) -> timeout");
+}
+
+#[test]
+fn pipeline_folds_retry_contexts_as_they_are_added() {
+    let mut pipeline = ErrorPipeline::<(), &str>::new(Err("timeout"))
+        .with_retry_context(1)
+        .with_retry_context(2)
+        .with_compaction(retries());
+    assert_eq!(pipeline.metadata_all("retry_attempt").count(), 1);
+
+    for attempt in 3..=5 {
+        pipeline = pipeline.with_retry_context(attempt);
+        let folded = pipeline
+            .find_context(|_| true)
+            .and_then(ErrorContext::as_group)
+            .unwrap();
+
+        assert_eq!(pipeline.metadata_all("retry_attempt").count(), 1);
+        assert_eq!(folded.repeat.as_ref().map(|repeat| repeat.count), Some(attempt as usize));
+    }
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn serde_round_trips_repeats_and_compaction() {
+    let err = ComposableError::new("timeout")
+        .with_compaction(retries())
+        .with_contexts((1..=3).map(attempt))
+        .with_context("polling")
+        .with_context("polling");
+
+    let json = serde_json::to_value(&err).unwrap();
+    assert_eq!(json["compaction"]["counter_keys"][0], "retry_attempt");
+    let group = &json["context"][0]["Group"];
+    assert_eq!(group["repeat"]["count"], 3);
+    assert_eq!(group["repeat"]["range"]["key"], "retry_attempt");
+
+    let back: ComposableError<String> = serde_json::from_value(json).unwrap();
+    assert_eq!(back.error_chain(), err.error_chain());
+    assert_eq!(back.compaction(), Some(&retries()));
+    let back = back.with_context("polling");
+    assert_eq!(back.error_chain(), "polling (x3) -> [http] (retry_attempt=1..3) -> timeout");
+
+    let plain =
+        serde_json::to_value(ComposableError::new("boom").with_context(attempt(1))).unwrap();
+    assert!(plain.get("compaction").is_none());
+    assert!(plain["context"][0]["Group"].get("repeat").is_none());
+}
@@ -0,0 +1,158 @@
+use error_rail::types::{ContextLimit, OverflowPolicy};
+use error_rail::{ComposableError, ErrorContext, ErrorPipeline};
+
+fn with_steps(limit: ContextLimit, steps: usize) -> ComposableError<&'static str> {
+    let mut err = ComposableError::new("timeout").with_context_limit(limit);
+    for step in 1..=steps {
+        err = err.with_context(format!("step {step}"));
+    }
+    err
+}
+
+fn messages(err: &ComposableError<&str>) -> Vec<String> {
+    err.context_iter()
+        .map(|ctx| ctx.message().into_owned())
+        .collect()
+}
+
+#[test]
+fn keep_first_drops_newest_contexts() {
+    let err = with_steps(ContextLimit::keep_first(2), 5);
+
+    assert_eq!(messages(&err), ["step 2", "step 1"]);
+    assert_eq!(err.dropped_contexts(), 3);
+    assert_eq!(err.error_chain(), "... 3 more contexts elided ... -> step 2 -> step 1 -> timeout");
+}
+
+#[test]
+fn keep_last_drops_oldest_contexts() {
+    let err = with_steps(ContextLimit::keep_last(2), 5);
+
+    assert_eq!(messages(&err), ["step 5", "step 4"]);
+    assert_eq!(err.error_chain(), "step 5 -> step 4 -> ... 3 more contexts elided ... -> timeout");
+}
+
+#[test]
+fn keep_ends_drops_the_middle() {
+    let err = with_steps(ContextLimit::new(3, OverflowPolicy::KeepEnds), 6);
+
+    assert_eq!(messages(&err), ["step 6", "step 5", "step 1"]);
+    assert_eq!(err.dropped_contexts(), 3);
+    assert_eq!(
+        err.error_chain(),
+        "step 6 -> step 5 -> ... 3 more contexts elided ... -> step 1 -> timeout"
+    );
+}
+
+#[test]
+fn stacks_within_limit_are_untouched() {
+    let err = with_steps(ContextLimit::keep_last(4), 3);
+
+    assert_eq!(err.dropped_contexts(), 0);
+    assert_eq!(err.error_chain(), "step 3 -> step 2 -> step 1 -> timeout");
+}
+
+#[test]
+fn limit_applies_to_existing_contexts() {
+    let err = ComposableError::new("boom")
+        .with_context("a")
+        .with_context("b")
+        .with_context("c")
+        .with_context_limit(ContextLimit::keep_last(1));
+
+    assert_eq!(err.context().as_slice(), [ErrorContext::new("c")]);
+    assert_eq!(err.dropped_contexts(), 2);
+}
+
+#[test]
+fn single_elided_context_uses_singular() {
+    let err = with_steps(ContextLimit::keep_last(1), 2);
+    assert_eq!(err.error_chain(), "step 2 -> ... 1 more context elided ... -> timeout");
+}
+
+#[test]
+fn marker_follows_context_order_in_other_layouts() {
+    let err = with_steps(ContextLimit::keep_ends(2), 5);
+
+    let reversed = err.fmt().reverse_context(true).to_string();
+    assert_eq!(reversed, "step 1 -> ... 3 more contexts elided ... -> step 5 -> timeout");
+
+    let pretty = err.fmt().pretty().to_string();
+    assert!(pretty.contains("... 3 more contexts elided ..."));
+    assert!(pretty.find("step 5").unwrap() < pretty.find("elided").unwrap());
+    assert!(pretty.find("elided").unwrap() < pretty.find("step 1").unwrap());
+}
+
+#[test]
+fn cause_layers_keep_their_elision() {
+    let cause = with_steps(ContextLimit::keep_last(1), 3);
+    let err = ComposableError::new("request failed").with_cause(cause);
+
+    assert_eq!(err.causes().next().unwrap().dropped_contexts(), 2);
+    assert_eq!(
+        err.error_chain(),
+        "request failed -> caused by: step 3 -> ... 2 more contexts elided ... -> timeout"
+    );
+}
+
+#[test]
+fn pipeline_applies_limit_on_finish() {
+    let mut pipeline = ErrorPipeline::<(), &str>::new(Err("timeout"))
+        .with_context_limit(ContextLimit::keep_last(2));
+    for attempt in 1..=10 {
+        pipeline = pipeline.with_retry_context(attempt);
+    }
+    let err = pipeline.finish_boxed().unwrap_err();
+
+    assert_eq!(err.context().len(), 2);
+    assert_eq!(err.dropped_contexts(), 8);
+    assert_eq!(err.context_limit(), Some(ContextLimit::keep_last(2)));
+}
+
+#[test]
+fn pipeline_bounds_pending_contexts_on_push() {
+    let mut pipeline = ErrorPipeline::<(), &str>::new(Err("timeout"))
+        .with_context_limit(ContextLimit::keep_last(2));
+    for attempt in 1..=10 {
+        pipeline = pipeline.with_retry_context(attempt);
+        assert!(pipeline.metadata_all("retry_attempt").count() <= 2);
+    }
+    assert_eq!(pipeline.metadata("retry_attempt"), Some("10"));
+
+    let err = pipeline.finish_boxed().unwrap_err();
+    assert_eq!(err.dropped_contexts(), 8);
+    assert_eq!(
+        err.error_chain(),
+        "(retry_attempt=10) -> (retry_attempt=9) -> ... 8 more contexts elided ... -> timeout"
+    );
+}
+
+#[test]
+fn pipeline_limit_truncates_contexts_added_before_it() {
+    let pipeline = ErrorPipeline::<(), &str>::new(Err("timeout"))
+        .with_context("step 1")
+        .with_context("step 2")
+        .with_context("step 3")
+        .with_context_limit(ContextLimit::keep_first(1));
+
+    assert_eq!(pipeline.messages().collect::<Vec<_>>(), ["step 1"]);
+    assert_eq!(pipeline.finish_boxed().unwrap_err().dropped_contexts(), 2);
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn serde_round_trips_limit_and_dropped_count() {
+    let err = with_steps(ContextLimit::keep_ends(2), 5);
+
+    let json = serde_json::to_value(&err).unwrap();
+    assert_eq!(json["dropped_contexts"], 3);
+    assert_eq!(json["context_limit"]["policy"], "keep_ends");
+
+    let back: ComposableError<String> = serde_json::from_value(json).unwrap();
+    assert_eq!(back.dropped_contexts(), 3);
+    assert_eq!(back.error_chain(), err.error_chain());
+
+    let plain = serde_json::to_value(ComposableError::new("boom")).unwrap();
+    assert!(plain.get("dropped_contexts").is_none());
+    assert!(plain.get("context_limit").is_none());
+}
@@ -0,0 +1,183 @@
+use error_rail::types::{ContextCompaction, ContextPayload, MetadataValue, Timestamp};
+use error_rail::{assert_err_eq, ComposableError, ErrorContext};
+use std::fmt;
+use std::hash::Hasher;
+
+#[derive(Debug, Clone, PartialEq)]
+struct SqlQuery {
+    table: &'static str,
+    rows: u64,
+}
+
+impl fmt::Display for SqlQuery {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "querying {} ({} rows)", self.table, self.rows)
+    }
+}
+
+impl ContextPayload for SqlQuery {
+    fn kind(&self) -> &str {
+        "sql_query"
+    }
+
+    fn value(&self) -> Option<MetadataValue> {
+        Some(MetadataValue::map([
+            ("table", MetadataValue::from(self.table)),
+            ("rows", MetadataValue::from(self.rows)),
+        ]))
+    }
+
+    fn fingerprint(&self, hasher: &mut dyn Hasher) {
+        hasher.write(self.table.as_bytes());
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct RequestLine(&'static str);
+
+impl fmt::Display for RequestLine {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.0)
+    }
+}
+
+impl ContextPayload for RequestLine {
+    fn kind(&self) -> &str {
+        "request_line"
+    }
+}
+
+#[cfg(feature = "serde")]
+fn query_value() -> Option<MetadataValue> {
+    SqlQuery { table: "orders", rows: 3 }.value()
+}
+
+fn query(table: &'static str, rows: u64) -> ErrorContext {
+    ErrorContext::custom(SqlQuery { table, rows })
+}
+
+#[test]
+fn custom_contexts_render_with_display() {
+    let err = ComposableError::new("timeout")
+        .with_context(query("orders", 3))
+        .with_context(ErrorContext::tag("db"));
+
+    assert_eq!(err.error_chain(), "[db] -> querying orders (3 rows) -> timeout");
+    assert_eq!(query("orders", 3).message(), "querying orders (3 rows)");
+    assert_eq!(query("orders", 3).to_string(), "querying orders (3 rows)");
+    assert_eq!(err.unredacted().to_string(), err.error_chain());
+}
+
+#[test]
+fn payloads_downcast_to_their_type() {
+    let ctx = query("orders", 3);
+    let payload = ctx.as_custom().unwrap();
+
+    assert_eq!(payload.kind(), "sql_query");
+    assert!(payload.is::<SqlQuery>());
+    assert_eq!(payload.downcast_ref::<SqlQuery>().map(|q| q.rows), Some(3));
+    assert!(payload.downcast_ref::<RequestLine>().is_none());
+    assert!(ctx.as_group().is_none());
+    assert!(ErrorContext::tag("db").as_custom().is_none());
+}
+
+#[test]
+fn payloads_clone_and_compare_by_value() {
+    let ctx = query("orders", 3);
+
+    assert_eq!(ctx.clone(), ctx);
+    assert_ne!(ctx, query("orders", 4));
+    assert_ne!(ctx, ErrorContext::custom(RequestLine("querying orders (3 rows)")));
+    assert_ne!(ctx, ErrorContext::new("querying orders (3 rows)"));
+}
+
+#[test]
+fn custom_contexts_are_internal_and_plain() {
+    let ctx = query("orders", 3);
+    assert!(!ctx.is_public());
+    assert_eq!(ctx.severity(), None);
+    assert_eq!(ctx.message_key(), None);
+    assert_eq!(ctx.clone().with_timestamp(Timestamp::from_unix_millis(1)), ctx);
+
+    let err = ComposableError::new("timeout")
+        .with_context(ErrorContext::public("loading your order"))
+        .with_context(query("orders", 3));
+    assert_eq!(err.public_view().to_string(), "loading your order -> an internal error occurred");
+    assert_eq!(err.messages().collect::<Vec<_>>(), ["loading your order"]);
+}
+
+#[test]
+fn custom_contexts_are_not_folded() {
+    let err = ComposableError::new("timeout")
+        .with_compaction(ContextCompaction::new())
+        .with_context(query("orders", 3))
+        .with_context(query("orders", 3));
+
+    assert_eq!(err.context().len(), 2);
+}
+
+#[test]
+fn fingerprint_includes_kind_and_contribution() {
+    let fingerprint = |ctx: ErrorContext| {
+        ComposableError::new("timeout")
+            .with_context(ctx)
+            .fingerprint()
+    };
+    let plain = ComposableError::new("timeout").fingerprint();
+
+    assert_ne!(fingerprint(query("orders", 3)), plain);
+    assert_eq!(fingerprint(query("orders", 3)), fingerprint(query("orders", 4)));
+    assert_ne!(fingerprint(query("orders", 3)), fingerprint(query("users", 3)));
+    assert_eq!(
+        fingerprint(ErrorContext::custom(RequestLine("GET /a"))),
+        fingerprint(ErrorContext::custom(RequestLine("GET /b")))
+    );
+
+    let err = ComposableError::new("timeout").with_context(query("orders", 3));
+    assert_eq!(err.fingerprint_config().include_custom(false).compute(), plain);
+}
+
+#[test]
+fn assert_err_eq_matches_custom_contexts() {
+    let res: Result<(), _> = Err(ComposableError::new("timeout").with_context(query("orders", 3)));
+    assert_err_eq!(res, "querying orders (3 rows)");
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn serde_keeps_kind_message_and_value() {
+    use error_rail::types::SerializedPayload;
+
+    let err = ComposableError::new("timeout")
+        .with_context(query("orders", 3))
+        .with_context(ErrorContext::custom(RequestLine("GET /orders")));
+
+    let json = serde_json::to_value(&err).unwrap();
+    let custom = &json["context"][0]["Custom"];
+    assert_eq!(custom["kind"], "sql_query");
+    assert_eq!(custom["message"], "querying orders (3 rows)");
+    assert_eq!(custom["value"]["table"], "orders");
+    assert_eq!(custom["value"]["rows"], 3);
+    assert!(json["context"][1]["Custom"].get("value").is_none());
+
+    let back: ComposableError<String> = serde_json::from_value(json).unwrap();
+    assert_eq!(back.error_chain(), err.error_chain());
+    let payload = back
+        .context_iter()
+        .find_map(|ctx| ctx.as_custom()?.downcast_ref::<SerializedPayload>())
+        .unwrap();
+    assert_eq!(payload.kind(), "request_line");
+    assert_eq!(payload.message(), "GET /orders");
+    assert_eq!(payload.value(), None);
+    let payload = back
+        .context_iter()
+        .filter_map(|ctx| ctx.as_custom()?.downcast_ref::<SerializedPayload>())
+        .next_back()
+        .unwrap();
+    assert_eq!(payload.kind(), "sql_query");
+    assert_eq!(payload.message(), "querying orders (3 rows)");
+    assert_eq!(
+        serde_json::to_value(payload.value()).unwrap(),
+        serde_json::to_value(query_value()).unwrap()
+    );
+}
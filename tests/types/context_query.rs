@@ -0,0 +1,169 @@
+use error_rail::types::{Location, MetadataValue};
+use error_rail::validation::Validation;
+use error_rail::{ComposableError, ErrorContext, ErrorPipeline};
+
+fn request_error() -> ComposableError<&'static str> {
+    ComposableError::new("forbidden")
+        .with_context(
+            ErrorContext::builder()
+                .tag("http")
+                .metadata("user_id", "u-1")
+                .metadata("status", 403)
+                .location("api.rs", 10)
+                .build(),
+        )
+        .with_context("checking permissions")
+        .with_context(
+            ErrorContext::builder()
+                .tag("auth")
+                .tag("http")
+                .message("loading policy")
+                .metadata("user_id", "u-2")
+                .build(),
+        )
+}
+
+#[test]
+fn tags_and_has_tag() {
+    let err = request_error();
+
+    assert!(err.has_tag("auth"));
+    assert!(!err.has_tag("db"));
+    assert_eq!(err.tags().collect::<Vec<_>>(), ["auth", "http", "http"]);
+}
+
+#[test]
+fn metadata_prefers_the_most_recent_text_value() {
+    let err = request_error();
+
+    assert_eq!(err.metadata("user_id"), Some("u-2"));
+    assert_eq!(
+        err.metadata_all("user_id").collect::<Vec<_>>(),
+        [&MetadataValue::from("u-2"), &MetadataValue::from("u-1")]
+    );
+    assert_eq!(err.metadata("status"), None);
+    assert_eq!(err.metadata("missing"), None);
+}
+
+#[test]
+fn metadata_does_not_skip_a_newer_value_of_another_type() {
+    let err = ComposableError::new("forbidden")
+        .with_context(ErrorContext::metadata("user_id", "u-1"))
+        .with_context(ErrorContext::metadata("user_id", 42));
+
+    assert_eq!(err.metadata("user_id"), None);
+    assert_eq!(
+        err.metadata_all("user_id").collect::<Vec<_>>(),
+        [&MetadataValue::from(42), &MetadataValue::from("u-1")]
+    );
+
+    let pipeline = ErrorPipeline::<(), &str>::new(Err("timeout"))
+        .with_context(ErrorContext::metadata("table", "orders"))
+        .with_context(ErrorContext::metadata("table", 7));
+    assert_eq!(pipeline.metadata("table"), None);
+    assert_eq!(pipeline.metadata_all("table").count(), 2);
+
+    let v = Validation::<_, ()>::invalid_many([
+        ComposableError::new("missing").with_context(ErrorContext::metadata("field", true)),
+        ComposableError::new("too short").with_context(ErrorContext::metadata("field", "name")),
+    ]);
+    assert_eq!(v.metadata("field"), None);
+    assert_eq!(v.metadata_all("field").nth(1), Some(&MetadataValue::from("name")));
+}
+
+#[test]
+fn metadata_any_returns_numeric_values() {
+    let err = request_error();
+
+    assert_eq!(err.metadata("status"), None);
+    assert_eq!(err.metadata_any("status"), Some(&MetadataValue::I64(403)));
+    assert_eq!(err.metadata_any("user_id"), Some(&MetadataValue::from("u-2")));
+    assert_eq!(err.metadata_any("missing"), None);
+
+    let pipeline = ErrorPipeline::<(), &str>::new(Err("timeout"))
+        .with_context(ErrorContext::metadata("attempt", 3u32));
+    assert_eq!(pipeline.metadata_any("attempt"), Some(&MetadataValue::U64(3)));
+
+    let v = Validation::<_, ()>::invalid(
+        ComposableError::new("too long").with_context(ErrorContext::metadata("max_len", 64)),
+    );
+    assert_eq!(v.metadata("max_len"), None);
+    assert_eq!(v.metadata_any("max_len"), Some(&MetadataValue::I64(64)));
+}
+
+#[test]
+fn locations_and_messages() {
+    let err = request_error();
+
+    assert_eq!(err.locations().collect::<Vec<_>>(), [&Location::new("api.rs", 10)]);
+    assert_eq!(err.messages().collect::<Vec<_>>(), ["loading policy", "checking permissions"]);
+}
+
+#[test]
+fn find_context_returns_the_most_recent_match() {
+    let err = request_error();
+
+    let found = err.find_context(|ctx| {
+        ctx.as_group()
+            .is_some_and(|g| g.tags.iter().any(|t| t == "http"))
+    });
+    assert_eq!(found.and_then(|ctx| ctx.as_group()?.message.as_deref()), Some("loading policy"));
+    assert!(err.find_context(|ctx| ctx.severity().is_some()).is_none());
+}
+
+#[test]
+fn queries_ignore_causes() {
+    let err = ComposableError::new("request failed")
+        .with_cause(ComposableError::new("timeout").with_context(ErrorContext::tag("db")));
+
+    assert!(!err.has_tag("db"));
+    assert_eq!(err.tags().count(), 0);
+}
+
+#[test]
+fn pipeline_queries_pending_contexts() {
+    let pipeline = ErrorPipeline::<(), &str>::new(Err("timeout"))
+        .with_context(ErrorContext::tag("db"))
+        .with_context(ErrorContext::metadata("table", "orders"))
+        .with_context("querying orders");
+
+    assert!(pipeline.has_tag("db"));
+    assert_eq!(pipeline.metadata("table"), Some("orders"));
+    assert_eq!(pipeline.messages().collect::<Vec<_>>(), ["querying orders"]);
+    assert!(pipeline
+        .find_context(|ctx| ctx.as_group().is_none())
+        .is_some());
+
+    let ok = ErrorPipeline::<(), &str>::new(Ok(())).with_context(ErrorContext::tag("db"));
+    assert!(!ok.has_tag("db"));
+}
+
+#[test]
+fn validation_queries_every_error() {
+    let v = Validation::<_, ()>::invalid_many([
+        ComposableError::new("missing").with_context(
+            ErrorContext::builder()
+                .tag("form")
+                .metadata("field", "name")
+                .build(),
+        ),
+        ComposableError::new("too short").with_context(
+            ErrorContext::builder()
+                .tag("password")
+                .metadata("field", "password")
+                .build(),
+        ),
+    ]);
+
+    assert!(v.has_tag("password"));
+    assert_eq!(v.tags().collect::<Vec<_>>(), ["form", "password"]);
+    assert_eq!(v.metadata("field"), Some("name"));
+    assert_eq!(
+        v.metadata_all("field").collect::<Vec<_>>(),
+        [&MetadataValue::from("name"), &MetadataValue::from("password")]
+    );
+
+    let valid = Validation::<ComposableError<&str>, u8>::valid(1);
+    assert!(!valid.has_tag("form"));
+    assert_eq!(valid.metadata("field"), None);
+}
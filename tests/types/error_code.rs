@@ -0,0 +1,144 @@
+use error_rail::types::{CodeInfo, ErrorCode, HierarchicalCode};
+use error_rail::{ComposableError, RailError};
+use std::fmt;
+
+#[derive(Debug, PartialEq)]
+enum ApiCode {
+    RateLimited,
+    Unavailable,
+}
+
+impl fmt::Display for ApiCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::RateLimited => f.write_str("API-RATE-LIMITED"),
+            Self::Unavailable => f.write_str("API-UNAVAILABLE"),
+        }
+    }
+}
+
+impl ErrorCode for ApiCode {
+    fn domain(&self) -> Option<&str> {
+        Some("API")
+    }
+
+    fn number(&self) -> Option<u32> {
+        match self {
+            Self::RateLimited => Some(429),
+            Self::Unavailable => Some(503),
+        }
+    }
+}
+
+#[test]
+fn numeric_codes_are_unchanged() {
+    let err = ComposableError::with_code("not found", 404);
+
+    assert_eq!(err.error_code(), Some(404));
+    assert_eq!(err.code_as::<u32>(), Some(&404));
+    assert_eq!(err.code().unwrap().number(), Some(404));
+    assert_eq!(err.to_string(), "not found (code: 404)");
+}
+
+#[test]
+fn string_codes_have_no_number() {
+    let err = ComposableError::new("pool exhausted").set_code("DB-POOL-EXHAUSTED");
+
+    assert_eq!(err.error_code(), None);
+    assert_eq!(err.code().unwrap().to_string(), "DB-POOL-EXHAUSTED");
+    assert_eq!(err.code_as::<&str>(), Some(&"DB-POOL-EXHAUSTED"));
+    assert_eq!(err.fmt().show_code(false).to_string(), "pool exhausted");
+}
+
+#[test]
+fn hierarchical_codes_expose_their_parts() {
+    let err = ComposableError::new("refused")
+        .with_context("connecting")
+        .set_code(HierarchicalCode::new("DB", "CONN", 42));
+
+    let code = err.code().unwrap();
+    assert_eq!(code.domain(), Some("DB"));
+    assert_eq!(code.category(), Some("CONN"));
+    assert_eq!(err.error_code(), Some(42));
+    assert_eq!(err.to_string(), "connecting -> refused (code: DB-CONN-0042)");
+}
+
+#[test]
+fn custom_codes_can_be_read_back_by_type() {
+    let err = ComposableError::new("slow down").set_code(ApiCode::RateLimited);
+
+    assert_eq!(err.code_as::<ApiCode>(), Some(&ApiCode::RateLimited));
+    assert_eq!(err.code_as::<u32>(), None);
+    assert_eq!(err.error_code(), Some(429));
+
+    let reset = err.set_code(7);
+    assert_eq!(reset.code_as::<ApiCode>(), None);
+    assert_eq!(reset.to_string(), "slow down (code: 7)");
+}
+
+#[test]
+fn cause_codes_are_rendered() {
+    let err = ComposableError::new("checkout failed")
+        .with_cause(ComposableError::new("gateway down").set_code(ApiCode::Unavailable));
+
+    assert_eq!(err.cause().unwrap().code().unwrap().to_string(), "API-UNAVAILABLE");
+    assert_eq!(
+        err.to_string(),
+        "checkout failed -> caused by: gateway down (code: API-UNAVAILABLE)"
+    );
+}
+
+#[test]
+fn fingerprint_distinguishes_rich_codes() {
+    let a = ComposableError::new("e").set_code(ApiCode::RateLimited);
+    let b = ComposableError::new("e").set_code(ApiCode::Unavailable);
+    let same_number = ComposableError::new("e").set_code(HierarchicalCode::new("X", "Y", 429));
+
+    assert_ne!(a.fingerprint(), b.fingerprint());
+    assert_ne!(a.fingerprint(), same_number.fingerprint());
+    assert_eq!(
+        ComposableError::new("e").set_code(429).fingerprint(),
+        ComposableError::with_code("e", 429).fingerprint()
+    );
+}
+
+#[test]
+fn equality_compares_rich_codes() {
+    let a = ComposableError::new("e").set_code("A");
+    assert_eq!(a, ComposableError::new("e").set_code("A"));
+    assert_ne!(a, ComposableError::new("e").set_code("B"));
+}
+
+#[test]
+fn rail_error_keeps_rich_codes() {
+    let err: RailError = ComposableError::new(std::io::Error::other("down"))
+        .set_code(ApiCode::Unavailable)
+        .into();
+    assert_eq!(err.code_as::<ApiCode>(), Some(&ApiCode::Unavailable));
+
+    let outer: RailError = ComposableError::new(err).set_code("OUTER").into();
+    assert_eq!(outer.error_code(), None);
+    assert_eq!(outer.code().unwrap().to_string(), "OUTER");
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn rich_codes_round_trip_through_serde() {
+    let err = ComposableError::new("e".to_string())
+        .set_code(HierarchicalCode::new("DB", "CONN", 42))
+        .with_cause(ComposableError::new("c").set_code("INNER"));
+
+    let json = serde_json::to_value(&err).unwrap();
+    assert_eq!(json["error_code"], 42);
+    assert_eq!(json["code"]["code"], "DB-CONN-0042");
+    assert_eq!(json["code"]["domain"], "DB");
+    assert_eq!(json["causes"][0]["code"]["code"], "INNER");
+
+    let back: ComposableError<String> = serde_json::from_value(json).unwrap();
+    assert_eq!(back, err);
+    assert_eq!(back.code_as::<CodeInfo>().unwrap().category.as_deref(), Some("CONN"));
+    assert_eq!(back.to_string(), err.to_string());
+
+    let plain = serde_json::to_string(&ComposableError::with_code("p".to_string(), 1)).unwrap();
+    assert!(!plain.contains("\"code\""));
+}
@@ -0,0 +1,190 @@
+use error_rail::types::i18n::locale_chain;
+use error_rail::types::{CatalogError, MemoryCatalog, MessageCatalog};
+use error_rail::{group, ComposableError, ErrorContext};
+
+fn catalog() -> MemoryCatalog {
+    MemoryCatalog::new()
+        .with_message("en", "order.not_found", "order {id} was not found")
+        .with_message("de", "order.not_found", "Bestellung {id} wurde nicht gefunden")
+        .with_message("de-AT", "order.not_found", "Bestellung {id} ist nicht auffindbar")
+        .with_message("en", "braces", "{{literal}} {missing} {id}")
+        .with_fallback("en")
+}
+
+#[test]
+fn locale_chain_walks_parent_locales() {
+    assert_eq!(locale_chain("zh-Hant-TW").collect::<Vec<_>>(), ["zh-Hant-TW", "zh-Hant", "zh"]);
+    assert_eq!(locale_chain("en").collect::<Vec<_>>(), ["en"]);
+    assert_eq!(locale_chain("").count(), 0);
+}
+
+#[test]
+fn resolve_falls_back_through_parents_and_default_locale() {
+    let catalog = catalog();
+
+    assert_eq!(
+        catalog.resolve("de-AT", "order.not_found").as_deref(),
+        Some("Bestellung {id} ist nicht auffindbar")
+    );
+    assert_eq!(
+        catalog.resolve("de-DE", "order.not_found").as_deref(),
+        Some("Bestellung {id} wurde nicht gefunden")
+    );
+    assert_eq!(
+        catalog.resolve("ja", "order.not_found").as_deref(),
+        Some("order {id} was not found")
+    );
+    assert_eq!(catalog.resolve("de", "unknown"), None);
+}
+
+#[test]
+fn i18n_context_renders_key_and_args_without_catalog() {
+    let ctx = ErrorContext::i18n("order.not_found", [("id", 42)]);
+
+    assert_eq!(ctx.message_key(), Some("order.not_found"));
+    assert_eq!(ctx.message(), "order.not_found (id=42)");
+    assert_eq!(ErrorContext::new("plain").message_key(), None);
+}
+
+#[test]
+fn formatter_localizes_every_context_in_the_chain() {
+    let catalog = catalog();
+    let err = ComposableError::new("lookup failed")
+        .with_context(ErrorContext::i18n("order.not_found", [("id", 7)]))
+        .with_context(ErrorContext::new("checkout"));
+
+    assert_eq!(
+        err.fmt().locale(&catalog, "de-DE").to_string(),
+        "checkout -> Bestellung 7 wurde nicht gefunden -> lookup failed"
+    );
+    assert_eq!(
+        err.fmt().locale(&catalog, "en-US").to_string(),
+        "checkout -> order 7 was not found -> lookup failed"
+    );
+    assert_eq!(err.to_string(), "checkout -> order.not_found (id=7) -> lookup failed");
+}
+
+#[test]
+fn unused_args_stay_as_metadata_and_placeholders_are_escaped() {
+    let ctx = ErrorContext::i18n("braces", [("id", 1), ("attempt", 2)]);
+
+    assert_eq!(ctx.localize(&catalog(), "en"), "{literal} {missing} 1 (attempt=2)");
+}
+
+#[test]
+fn unresolved_keys_use_the_plain_message() {
+    let ctx = ErrorContext::builder()
+        .message_key("cart.empty")
+        .message("cart is empty")
+        .tag("checkout")
+        .build();
+
+    assert_eq!(ctx.localize(&catalog(), "de"), "[checkout] cart is empty");
+
+    let lazy = group!(message_key("order.not_found"), metadata("id", 3));
+    let err = ComposableError::new("failed").with_context(lazy);
+    assert_eq!(
+        err.fmt().locale(&catalog(), "de").to_string(),
+        "Bestellung 3 wurde nicht gefunden -> failed"
+    );
+}
+
+#[test]
+fn localized_args_respect_redaction() {
+    let catalog = MemoryCatalog::new().with_message("en", "card.declined", "card {card} declined");
+    let ctx = ErrorContext::builder()
+        .message_key("card.declined")
+        .secret_metadata("card", "4111")
+        .build();
+
+    assert_eq!(ctx.localize(&catalog, "en"), "card [REDACTED] declined");
+    let err = ComposableError::new("payment failed").with_context(ctx);
+    assert_eq!(
+        err.fmt()
+            .locale(&catalog, "en")
+            .unredacted(true)
+            .to_string(),
+        "card 4111 declined -> payment failed"
+    );
+}
+
+#[test]
+fn properties_source_loads_keys_and_skips_comments() {
+    let mut catalog = MemoryCatalog::new();
+    catalog
+        .load_properties(
+            "fr",
+            "# orders\n! legacy\n\norder.not_found = commande {id} introuvable\n",
+        )
+        .unwrap();
+
+    assert_eq!(
+        catalog.lookup("fr", "order.not_found").as_deref(),
+        Some("commande {id} introuvable")
+    );
+
+    let err = catalog
+        .load_properties("fr", "ok = yes\nmissing separator")
+        .unwrap_err();
+    assert!(matches!(err, CatalogError::Syntax { line: 2, .. }));
+    assert_eq!(err.to_string(), "invalid catalog at line 2: expected `key = value`");
+}
+
+#[test]
+fn json_source_flattens_nested_objects() {
+    let mut catalog = MemoryCatalog::new();
+    catalog
+        .load_json(
+            "es",
+            r#"{
+                "order": { "not_found": "pedido {id} no encontrado", "quote": "\"é\"" },
+                "top": "nivel"
+            }"#,
+        )
+        .unwrap();
+
+    assert_eq!(
+        catalog.lookup("es", "order.not_found").as_deref(),
+        Some("pedido {id} no encontrado")
+    );
+    assert_eq!(catalog.lookup("es", "order.quote").as_deref(), Some("\"é\""));
+    assert_eq!(catalog.lookup("es", "top").as_deref(), Some("nivel"));
+
+    let err = catalog.load_json("es", "{\n  \"count\": 3\n}").unwrap_err();
+    assert!(matches!(err, CatalogError::Syntax { line: 2, .. }));
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn files_are_loaded_by_extension() {
+    let dir = std::env::temp_dir().join(format!("error-rail-i18n-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let json = dir.join("it.json");
+    let properties = dir.join("it.properties");
+    std::fs::write(&json, r#"{"a": "uno"}"#).unwrap();
+    std::fs::write(&properties, "b = due").unwrap();
+
+    let mut catalog = MemoryCatalog::new();
+    catalog.load_file("it", &json).unwrap();
+    catalog.load_file("it", &properties).unwrap();
+    let missing = catalog.load_file("it", dir.join("missing.json"));
+    std::fs::remove_dir_all(&dir).unwrap();
+
+    assert_eq!(catalog.lookup("it", "a").as_deref(), Some("uno"));
+    assert_eq!(catalog.lookup("it", "b").as_deref(), Some("due"));
+    assert!(matches!(missing, Err(CatalogError::Io(_))));
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn message_key_round_trips_through_serde() {
+    let ctx = ErrorContext::i18n("order.not_found", [("id", 42)]);
+
+    let json = serde_json::to_value(&ctx).unwrap();
+    assert_eq!(json["Group"]["message_key"], "order.not_found");
+    let back: ErrorContext = serde_json::from_value(json).unwrap();
+    assert_eq!(back, ctx);
+
+    let plain = serde_json::to_value(ErrorContext::group("x").build()).unwrap();
+    assert!(plain["Group"].get("message_key").is_none());
+}
@@ -0,0 +1,168 @@
+use core::error::Error;
+use error_rail::types::{CodeRule, ContextLimit, MergeOptions, MergeStrategy, Severity};
+use error_rail::{ComposableError, ErrorContext};
+
+fn primary() -> ComposableError<&'static str> {
+    ComposableError::new("write failed")
+        .with_context("saving order")
+        .set_code(500)
+}
+
+fn rollback() -> ComposableError<&'static str> {
+    ComposableError::new("rollback failed")
+        .with_context("undoing insert")
+        .set_code(503)
+}
+
+#[test]
+fn merge_suppresses_the_other_error_by_default() {
+    let err = primary().merge(rollback());
+
+    assert_eq!(err.core_error(), &"write failed");
+    assert_eq!(err.error_code(), Some(500));
+    assert_eq!(err.context().len(), 1);
+    assert!(err.cause().is_none());
+
+    let suppressed: Vec<_> = err.suppressed().collect();
+    assert_eq!(suppressed.len(), 1);
+    assert!(suppressed[0].is_suppressed());
+    assert_eq!(suppressed[0].message(), "rollback failed");
+    assert_eq!(
+        err.error_chain(),
+        "saving order -> write failed (code: 500) -> suppressed: undoing insert -> rollback failed (code: 503)"
+    );
+}
+
+#[test]
+fn suppressed_children_sit_next_to_regular_causes() {
+    let err = ComposableError::new("write failed")
+        .with_cause(ComposableError::new("disk full"))
+        .merge(ComposableError::new("close failed"));
+
+    assert_eq!(err.children().len(), 2);
+    assert_eq!(err.cause().map(|c| c.message()), Some("disk full"));
+    assert_eq!(err.suppressed().map(|c| c.message()).collect::<Vec<_>>(), ["close failed"]);
+    assert_eq!(
+        err.error_chain(),
+        "write failed -> caused by: disk full -> suppressed: close failed"
+    );
+}
+
+#[test]
+fn concat_appends_the_other_stack() {
+    let err = primary()
+        .with_public_message("could not save")
+        .set_severity(Severity::Warning)
+        .merge_with(
+            rollback()
+                .with_public_message("could not roll back")
+                .with_hint("retry later")
+                .set_severity(Severity::Critical)
+                .with_cause(ComposableError::new("lock timeout")),
+            MergeOptions::concat(),
+        );
+
+    assert_eq!(err.core_error(), &"write failed");
+    assert_eq!(err.context().len(), 3);
+    assert_eq!(err.public_message(), Some("could not save"));
+    assert_eq!(err.hint(), Some("retry later"));
+    assert_eq!(err.severity(), Some(Severity::Critical));
+    assert_eq!(err.suppressed().count(), 0);
+    assert_eq!(
+        err.error_chain(),
+        "critical: undoing insert -> rollback failed -> saving order -> write failed (code: 500) -> caused by: lock timeout"
+    );
+}
+
+#[test]
+fn concat_respects_the_context_limit() {
+    let err = primary()
+        .with_context_limit(ContextLimit::keep_last(2))
+        .merge_with(rollback(), MergeOptions::concat());
+
+    assert_eq!(err.dropped_contexts(), 1);
+    assert_eq!(
+        err.error_chain(),
+        "undoing insert -> rollback failed -> ... 1 more context elided ... -> write failed (code: 500)"
+    );
+}
+
+#[test]
+fn code_rules_pick_the_merged_code() {
+    let merged = |a: ComposableError<&str>, b: ComposableError<&str>, rule| {
+        a.merge_with(b, MergeOptions::suppress().code_rule(rule))
+            .error_code()
+    };
+    let code = |n: u32| ComposableError::new("e").set_code(n);
+    let plain = || ComposableError::new("e");
+
+    assert_eq!(merged(code(500), code(503), CodeRule::First), Some(500));
+    assert_eq!(merged(plain(), code(503), CodeRule::First), Some(503));
+    assert_eq!(merged(code(500), code(503), CodeRule::Last), Some(503));
+    assert_eq!(merged(code(500), plain(), CodeRule::Last), Some(500));
+    assert_eq!(merged(code(503), code(500), CodeRule::Max), Some(503));
+    assert_eq!(merged(code(500), code(503), CodeRule::Max), Some(503));
+    assert_eq!(merged(plain(), code(404), CodeRule::Max), Some(404));
+    assert_eq!(merged(plain(), plain(), CodeRule::Max), None);
+
+    for strategy in [MergeStrategy::Suppress, MergeStrategy::Concat] {
+        let options = MergeOptions::new()
+            .strategy(strategy)
+            .code_rule(CodeRule::Last);
+        let err = code(500).merge_with(code(503), options);
+        assert_eq!(err.code_as::<u32>(), Some(&503));
+    }
+}
+
+#[test]
+fn suppressed_errors_are_left_out_of_the_fingerprint() {
+    let base = primary();
+    let merged = primary().merge(rollback());
+    let other_rollback = primary().merge(ComposableError::new("close failed"));
+
+    assert_eq!(merged.fingerprint(), base.fingerprint());
+    assert_eq!(merged.fingerprint(), other_rollback.fingerprint());
+
+    let with =
+        |err: &ComposableError<&str>| err.fingerprint_config().include_suppressed(true).compute();
+    assert_ne!(with(&merged), with(&base));
+    assert_ne!(with(&merged), with(&other_rollback));
+
+    let caused =
+        ComposableError::new("write failed").with_cause(ComposableError::new("close failed"));
+    assert_ne!(caused.fingerprint(), ComposableError::new("write failed").fingerprint());
+}
+
+#[test]
+fn concatenated_contexts_change_the_fingerprint() {
+    let rollback = rollback().with_context(ErrorContext::tag("db"));
+    let merged = primary().merge_with(rollback, MergeOptions::concat());
+    assert_ne!(merged.fingerprint(), primary().fingerprint());
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn serde_round_trips_suppressed_children() {
+    let err = primary()
+        .with_cause(ComposableError::new("disk full"))
+        .merge(rollback());
+
+    let json = serde_json::to_value(&err).unwrap();
+    assert!(json["causes"][0].get("suppressed").is_none());
+    assert_eq!(json["causes"][1]["suppressed"], true);
+    assert_eq!(json["causes"][1]["message"], "rollback failed");
+
+    let back: ComposableError<String> = serde_json::from_value(json).unwrap();
+    assert_eq!(back.error_chain(), err.error_chain());
+    assert_eq!(back.suppressed().count(), 1);
+    assert_eq!(back.cause().map(|c| c.message()), Some("disk full"));
+    assert_eq!(back.fingerprint(), err.fingerprint());
+}
+
+#[test]
+fn suppressed_children_are_not_the_source() {
+    let err = ComposableError::new(std::io::Error::other("write failed"))
+        .merge(ComposableError::new("close failed"));
+
+    assert_eq!(err.source().map(|source| source.to_string()), Some("write failed".into()));
+}
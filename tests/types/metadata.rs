@@ -0,0 +1,99 @@
+use error_rail::types::{ContextKey, MetadataValue};
+use error_rail::{group, ComposableError, ErrorContext};
+use std::time::Duration;
+
+const USER_ID: ContextKey<u64> = ContextKey::new("user_id");
+const RETRIES: ContextKey<u32> = ContextKey::new("retries");
+const ELAPSED: ContextKey<Duration> = ContextKey::new("elapsed");
+const CACHED: ContextKey<bool> = ContextKey::new("cached");
+
+fn typed_context() -> ErrorContext {
+    ErrorContext::builder()
+        .set(USER_ID, 42)
+        .set(ELAPSED, Duration::from_millis(1500))
+        .metadata("ratio", 0.5)
+        .metadata("cached", false)
+        .metadata("shards", MetadataValue::list([1, 2]))
+        .metadata("limits", MetadataValue::map([("rps", 10)]))
+        .build()
+}
+
+#[test]
+fn values_render_without_quotes() {
+    assert_eq!(
+        typed_context().to_string(),
+        "(user_id=42, elapsed=1.5s, ratio=0.5, cached=false, shards=[1, 2], limits={rps=10})"
+    );
+}
+
+#[test]
+fn typed_keys_read_values_back() {
+    let err = ComposableError::new("lookup failed").with_context(typed_context());
+
+    assert_eq!(err.metadata_value(USER_ID), Some(42));
+    assert_eq!(err.metadata_value(ELAPSED), Some(Duration::from_millis(1500)));
+    assert_eq!(err.metadata_value(CACHED), Some(false));
+    assert_eq!(err.metadata_value(RETRIES), None);
+    assert_eq!(err.metadata_value(ContextKey::<Vec<u8>>::new("shards")), Some(vec![1, 2]));
+}
+
+#[test]
+fn typed_keys_convert_compatible_values() {
+    let ctx = ErrorContext::builder()
+        .metadata("retries", "3")
+        .metadata("user_id", -1)
+        .metadata("elapsed", 10)
+        .build();
+
+    assert_eq!(ctx.metadata_value(RETRIES), Some(3));
+    assert_eq!(ctx.metadata_value(USER_ID), None);
+    assert_eq!(ctx.metadata_value(ELAPSED), None);
+    assert_eq!(ErrorContext::new("plain").metadata_value(RETRIES), None);
+}
+
+#[test]
+fn most_recent_context_wins() {
+    let err = ComposableError::new("timeout")
+        .with_context(group!(set(RETRIES, 1)))
+        .with_context(group!(tag("net"), set(RETRIES, 2)));
+
+    assert_eq!(err.metadata_value(RETRIES), Some(2));
+
+    let outer = ComposableError::new("request failed").with_cause(err);
+    assert_eq!(outer.metadata_value(RETRIES), None);
+    assert_eq!(outer.causes().next().unwrap().metadata_value(RETRIES), Some(2));
+}
+
+#[test]
+fn signed_and_unsigned_integers_compare_equal() {
+    assert_eq!(MetadataValue::from(3i64), MetadataValue::from(3u64));
+    assert_ne!(MetadataValue::from(-3i64), MetadataValue::from(3u64));
+    assert_ne!(MetadataValue::from(3), MetadataValue::from("3"));
+    assert_eq!(MetadataValue::from("3"), "3");
+}
+
+#[test]
+fn secret_typed_values_are_redacted() {
+    let ctx = ErrorContext::builder().secret_metadata("pin", 1234).build();
+
+    assert_eq!(ctx.to_string(), "(pin=[REDACTED])");
+    assert_eq!(ctx.unredacted().to_string(), "(pin=1234)");
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn values_serialize_natively() {
+    let json = serde_json::to_value(typed_context()).unwrap();
+    let metadata = &json["Group"]["metadata"];
+
+    assert_eq!(metadata[0], serde_json::json!(["user_id", 42]));
+    assert_eq!(metadata[1], serde_json::json!(["elapsed", { "secs": 1, "nanos": 500_000_000 }]));
+    assert_eq!(metadata[2], serde_json::json!(["ratio", 0.5]));
+    assert_eq!(metadata[3], serde_json::json!(["cached", false]));
+    assert_eq!(metadata[4], serde_json::json!(["shards", [1, 2]]));
+    assert_eq!(metadata[5], serde_json::json!(["limits", { "rps": 10 }]));
+
+    let back: ErrorContext = serde_json::from_value(json).unwrap();
+    assert_eq!(back, typed_context());
+    assert_eq!(back.metadata_value(ELAPSED), Some(Duration::from_millis(1500)));
+}
@@ -72,12 +72,31 @@ fn composable_error_map_core_preserves_context() {
 }
 
 pub mod accumulator;
+pub mod attachment;
+pub mod backtrace;
+pub mod caller_location;
+pub mod cause;
+pub mod compaction;
 pub mod composable_error;
+pub mod context_limit;
+pub mod context_payload;
+pub mod context_query;
+pub mod error_code;
 pub mod error_context;
 pub mod error_context_builder;
 pub mod error_context_builder_example;
 pub mod error_formatter;
 pub mod error_pipeline;
 pub mod fingerprint;
+pub mod i18n;
+pub mod merge;
+pub mod metadata;
 pub mod pipeline_ops;
+pub mod rail_error;
+pub mod redaction;
 pub mod retry;
+pub mod severity;
+pub mod thin_error;
+pub mod timestamp;
+pub mod trace;
+pub mod visibility;
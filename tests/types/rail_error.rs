@@ -0,0 +1,131 @@
+use core::error::Error;
+use error_rail::{
+    BoxedResultExt, ComposableError, ErrorContext, RailError, RailResult, RailResultExt, ResultExt,
+};
+use std::io;
+
+#[derive(Debug, PartialEq)]
+struct NotFound(&'static str);
+
+impl std::fmt::Display for NotFound {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} not found", self.0)
+    }
+}
+
+impl Error for NotFound {}
+
+fn find_user() -> RailResult<()> {
+    Err(NotFound("user")).ctx("loading user")?
+}
+
+#[test]
+fn conversion_keeps_contexts_and_code() {
+    let err: RailError = ComposableError::new(NotFound("user"))
+        .with_context(ErrorContext::tag("db"))
+        .set_code(404)
+        .into();
+
+    assert_eq!(err.error_code(), Some(404));
+    assert_eq!(err.context_iter().count(), 1);
+    assert_eq!(err.to_string(), "[db] -> user not found (code: 404)");
+}
+
+#[test]
+fn downcast_ref_recovers_core_error() {
+    let err = find_user().unwrap_err();
+
+    assert!(err.is::<NotFound>());
+    assert_eq!(err.downcast_ref::<NotFound>(), Some(&NotFound("user")));
+    assert!(err.downcast_ref::<io::Error>().is_none());
+}
+
+#[test]
+fn downcast_returns_typed_composable_error() {
+    let err = find_user().unwrap_err().set_code(7);
+
+    let err = err.downcast::<io::Error>().unwrap_err();
+    let typed = err.downcast::<NotFound>().unwrap();
+    assert_eq!(typed.core_error(), &NotFound("user"));
+    assert_eq!(typed.error_code(), Some(7));
+    assert_eq!(typed.context_iter().next().unwrap().message(), "loading user");
+}
+
+#[test]
+fn ctx_on_rail_result_merges_instead_of_nesting() {
+    fn outer() -> RailResult<()> {
+        find_user()
+            .ctx("handling request")
+            .map_err(|e| e.set_code(500))?;
+        Ok(())
+    }
+
+    let err = outer().unwrap_err();
+    assert!(err.is::<NotFound>());
+    assert_eq!(err.error_code(), Some(500));
+    let messages: Vec<_> = err
+        .context_iter()
+        .map(|c| c.message().into_owned())
+        .collect();
+    assert_eq!(messages, ["handling request", "loading user"]);
+}
+
+#[test]
+fn ctx_boxed_adds_context_in_place() {
+    let err = find_user()
+        .ctx_boxed("first")
+        .ctx_boxed_with(|| "second".to_string())
+        .unwrap_err();
+    assert_eq!(err.context_iter().count(), 3);
+    assert_eq!(err.context_iter().next().unwrap().message(), "second");
+}
+
+#[test]
+fn keeps_causes_and_source() {
+    let err = RailError::new(io::Error::other("write failed"))
+        .with_context("saving")
+        .with_cause(ComposableError::new("disk full"));
+
+    assert_eq!(err.source().unwrap().to_string(), "disk full");
+    assert!(err.error_chain().ends_with("caused by: disk full"));
+}
+
+#[test]
+fn is_pointer_sized() {
+    assert_eq!(std::mem::size_of::<RailError>(), std::mem::size_of::<usize>());
+}
+
+#[test]
+fn ctx_rail_keeps_the_rail_result_type() {
+    let res: RailResult<()> = find_user().ctx_rail("handling request");
+    let res: RailResult<()> = res.ctx_rail_with(|| format!("request {}", 7));
+
+    let err = res.unwrap_err();
+    assert!(err.is::<NotFound>());
+    let messages: Vec<_> = err
+        .context_iter()
+        .map(|c| c.message().into_owned())
+        .collect();
+    assert_eq!(messages, ["request 7", "handling request", "loading user"]);
+}
+
+#[test]
+fn source_matches_composable_error() {
+    let layered = || {
+        ComposableError::new(io::Error::other("request failed"))
+            .with_cause(ComposableError::new(io::Error::other("timeout")))
+    };
+    let chain = |mut current: Option<&dyn Error>| {
+        let mut messages = Vec::new();
+        while let Some(layer) = current {
+            messages.push(layer.to_string());
+            current = layer.source();
+        }
+        messages
+    };
+
+    let plain = layered();
+    let rail = RailError::from(layered());
+    assert_eq!(chain(plain.source()), chain(rail.source()));
+    assert_eq!(chain(rail.source()), ["timeout"]);
+}
@@ -0,0 +1,171 @@
+use error_rail::types::redaction::REDACTED;
+use error_rail::{group, ComposableError, ErrorContext};
+
+fn login_error() -> ComposableError<&'static str> {
+    ComposableError::new("login failed")
+        .with_context(
+            ErrorContext::builder()
+                .tag("auth")
+                .metadata("user_id", "42")
+                .secret_metadata("email", "alice@example.com")
+                .build(),
+        )
+        .with_context(ErrorContext::secret("token abc123 rejected"))
+}
+
+#[test]
+fn secret_parts_are_redacted_in_display_and_chain() {
+    let err = login_error();
+
+    assert_eq!(
+        err.error_chain(),
+        "[REDACTED] -> [auth] (user_id=42, email=[REDACTED]) -> login failed"
+    );
+    assert_eq!(err.to_string(), err.error_chain());
+    assert!(!err.fmt().pretty().to_string().contains("alice"));
+    assert!(!format!("{err:#}").contains("abc123"));
+    assert!(!err
+        .error_chain_with(error_rail::ErrorFormatConfig::compact())
+        .contains("alice"));
+}
+
+#[test]
+fn debug_output_is_redacted() {
+    let debug = format!("{:?}", login_error());
+    assert!(!debug.contains("alice@example.com"));
+    assert!(!debug.contains("abc123"));
+    assert!(debug.contains(REDACTED));
+    assert!(debug.contains("user_id"));
+}
+
+#[test]
+fn unredacted_view_reveals_secrets() {
+    let err = login_error();
+
+    assert_eq!(
+        err.unredacted().to_string(),
+        "token abc123 rejected -> [auth] (user_id=42, email=alice@example.com) -> login failed"
+    );
+    assert_eq!(err.fmt().unredacted(true).to_string(), err.unredacted().to_string());
+    assert!(err
+        .fmt()
+        .unredacted(true)
+        .pretty()
+        .to_string()
+        .contains("alice@example.com"));
+
+    let ctx = ErrorContext::secret("card 4111");
+    assert_eq!(ctx.to_string(), REDACTED);
+    assert_eq!(ctx.unredacted().to_string(), "card 4111");
+}
+
+#[test]
+fn secrets_in_causes_are_redacted() {
+    let db = ComposableError::new("connection refused").with_context(
+        ErrorContext::builder()
+            .secret_metadata("dsn", "postgres://u:pw@db")
+            .build(),
+    );
+    let err = ComposableError::new("query failed").with_cause(db);
+
+    assert!(!err.error_chain().contains("pw@db"));
+    assert!(err.unredacted().to_string().contains("postgres://u:pw@db"));
+}
+
+#[test]
+fn group_macro_accepts_secret_fields() {
+    let account = "DE89 3704";
+    let err = ComposableError::new("transfer failed").with_context(group!(
+        tag("billing"),
+        secret("iban", account),
+        secret_message("moving funds from {}", account)
+    ));
+
+    assert_eq!(err.error_chain(), "[billing] [REDACTED] (iban=[REDACTED]) -> transfer failed");
+    assert_eq!(
+        err.unredacted().to_string(),
+        "[billing] moving funds from DE89 3704 (iban=DE89 3704) -> transfer failed"
+    );
+}
+
+#[test]
+fn fingerprint_hashes_placeholder_instead_of_secret() {
+    let with = |email: &'static str| {
+        ComposableError::new("login failed").with_context(
+            ErrorContext::builder()
+                .secret_metadata("email", email)
+                .build(),
+        )
+    };
+    let a = with("alice@example.com");
+    let b = with("bob@example.com");
+
+    let fp =
+        |err: &ComposableError<&str>| err.fingerprint_config().include_metadata(true).compute();
+    assert_eq!(fp(&a), fp(&b));
+
+    let plain = ComposableError::new("login failed")
+        .with_context(ErrorContext::builder().metadata("email", REDACTED).build());
+    assert_eq!(fp(&a), fp(&plain));
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn global_denylist_redacts_keys_case_insensitively() {
+    use error_rail::types::redaction;
+
+    let ctx = ErrorContext::builder()
+        .metadata("Session_Cookie", "c00k1e")
+        .metadata("path", "/")
+        .build();
+    assert_eq!(ctx.to_string(), "(Session_Cookie=c00k1e, path=/)");
+
+    redaction::set_redacted_keys(&["session_cookie"]);
+    assert_eq!(redaction::redacted_keys(), &["session_cookie"]);
+    assert_eq!(ctx.to_string(), "(Session_Cookie=[REDACTED], path=/)");
+    assert_eq!(ctx.unredacted().to_string(), "(Session_Cookie=c00k1e, path=/)");
+
+    redaction::set_redacted_keys(&[]);
+    assert_eq!(ctx.to_string(), "(Session_Cookie=c00k1e, path=/)");
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn serde_output_is_redacted_unless_unredacted() {
+    let err = login_error();
+
+    let redacted = serde_json::to_string(&err).unwrap();
+    assert!(!redacted.contains("alice@example.com"));
+    assert!(!redacted.contains("abc123"));
+    assert!(redacted.contains(REDACTED));
+
+    let revealed = serde_json::to_string(&err.unredacted()).unwrap();
+    assert!(revealed.contains("alice@example.com"));
+    assert!(revealed.contains("abc123"));
+
+    let back: ComposableError<String> = serde_json::from_str(&revealed).unwrap();
+    assert_eq!(back.error_chain(), err.error_chain());
+    assert_eq!(back.unredacted().to_string(), err.unredacted().to_string());
+
+    let ctx = ErrorContext::secret("card 4111");
+    assert!(!serde_json::to_string(&ctx).unwrap().contains("4111"));
+    assert!(serde_json::to_string(&ctx.unredacted())
+        .unwrap()
+        .contains("4111"));
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn serde_redacts_secrets_in_causes() {
+    let db = ComposableError::new("connection refused").with_context(
+        ErrorContext::builder()
+            .secret_metadata("dsn", "postgres://u:pw@db")
+            .build(),
+    );
+    let err = ComposableError::new("query failed").with_cause(db);
+
+    assert!(!serde_json::to_string(&err).unwrap().contains("pw@db"));
+    assert!(serde_json::to_string(&err.unredacted())
+        .unwrap()
+        .contains("pw@db"));
+}
@@ -0,0 +1,120 @@
+use error_rail::types::Severity;
+use error_rail::{group, ComposableError, ErrorContext};
+
+#[test]
+fn severity_levels_are_ordered() {
+    assert!(Severity::Debug < Severity::Info);
+    assert!(Severity::Warning < Severity::Error);
+    assert!(Severity::Critical < Severity::Fatal);
+    assert_eq!(Severity::Critical.to_string(), "critical");
+}
+
+#[test]
+fn max_severity_aggregates_error_contexts_and_causes() {
+    let cause = ComposableError::new("replica lag").set_severity(Severity::Critical);
+    let err = ComposableError::new("read failed")
+        .with_context(
+            ErrorContext::builder()
+                .tag("db")
+                .severity(Severity::Warning)
+                .build(),
+        )
+        .set_severity(Severity::Error)
+        .with_cause(cause);
+
+    assert_eq!(err.severity(), Some(Severity::Error));
+    assert_eq!(err.cause().unwrap().severity(), Some(Severity::Critical));
+    assert_eq!(err.max_severity(), Some(Severity::Critical));
+
+    assert_eq!(ComposableError::new("plain").max_severity(), None);
+}
+
+#[test]
+fn group_macro_accepts_severity() {
+    let err = ComposableError::new("auth failed")
+        .with_context(group!(tag("auth"), severity(Severity::Fatal)));
+
+    assert_eq!(err.context()[0].severity(), Some(Severity::Fatal));
+    assert_eq!(err.max_severity(), Some(Severity::Fatal));
+}
+
+#[test]
+fn severity_is_rendered_as_prefix() {
+    let err = ComposableError::new("disk full")
+        .with_context("saving")
+        .set_severity(Severity::Critical)
+        .set_code(507);
+
+    assert_eq!(err.to_string(), "critical: saving -> disk full (code: 507)");
+    assert_eq!(err.fmt().show_severity(false).to_string(), "saving -> disk full (code: 507)");
+    assert_eq!(err.fmt().pretty().to_string(), "┌ critical: saving\n└─ disk full (code: 507)");
+}
+
+#[test]
+fn cause_severity_follows_cause_prefix() {
+    let err = ComposableError::new("outer")
+        .with_cause(ComposableError::new("inner").set_severity(Severity::Warning));
+    assert_eq!(err.to_string(), "outer -> caused by: warning: inner");
+}
+
+#[test]
+fn fingerprint_includes_severity_only_when_enabled() {
+    let warn = ComposableError::new("e").set_severity(Severity::Warning);
+    let fatal = ComposableError::new("e").set_severity(Severity::Fatal);
+
+    assert_eq!(warn.fingerprint(), fatal.fingerprint());
+    assert_ne!(
+        warn.fingerprint_config().include_severity(true).compute(),
+        fatal.fingerprint_config().include_severity(true).compute()
+    );
+}
+
+#[test]
+fn severity_is_part_of_equality() {
+    let a = ComposableError::new("e").set_severity(Severity::Info);
+    assert_ne!(a, ComposableError::new("e"));
+    assert_eq!(a.clone(), a);
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn severity_round_trips_through_serde() {
+    let err = ComposableError::new("e".to_string())
+        .with_context(group!(tag("db"), severity(Severity::Debug)))
+        .set_severity(Severity::Warning)
+        .with_cause(ComposableError::new("c").set_severity(Severity::Fatal));
+
+    let json = serde_json::to_value(&err).unwrap();
+    assert_eq!(json["severity"], "warning");
+    assert_eq!(json["causes"][0]["severity"], "fatal");
+
+    let back: ComposableError<String> = serde_json::from_value(json).unwrap();
+    assert_eq!(back, err);
+
+    let plain = serde_json::to_string(&ComposableError::new("p".to_string())).unwrap();
+    assert!(!plain.contains("\"severity\""));
+}
+
+#[test]
+fn rail_error_keeps_the_higher_severity_when_merging() {
+    use error_rail::{RailError, ResultExt};
+    use std::io;
+
+    let inner: RailError = ComposableError::new(io::Error::other("disk full"))
+        .set_severity(Severity::Critical)
+        .into();
+    let err: RailError = Err::<(), _>(inner)
+        .ctx("saving")
+        .map_err(|e| e.set_severity(Severity::Warning))
+        .unwrap_err()
+        .into();
+    assert_eq!(err.severity(), Some(Severity::Critical));
+
+    let inner: RailError = ComposableError::new(io::Error::other("disk full"))
+        .set_severity(Severity::Warning)
+        .into();
+    let err: RailError = ComposableError::new(inner)
+        .set_severity(Severity::Fatal)
+        .into();
+    assert_eq!(err.severity(), Some(Severity::Fatal));
+}
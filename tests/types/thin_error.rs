@@ -0,0 +1,123 @@
+use core::mem::size_of;
+use error_rail::types::{SharedComposableError, ThinComposableError};
+use error_rail::{BoxedResultExt, ComposableError, ErrorContext, ResultExt};
+use std::io;
+use std::sync::Arc;
+use std::thread;
+
+/// Core error that is neither `Clone` nor `PartialEq`.
+#[derive(Debug)]
+struct Unclonable(&'static str);
+
+impl core::fmt::Display for Unclonable {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(self.0)
+    }
+}
+
+#[test]
+fn thin_and_shared_errors_are_pointer_sized() {
+    assert_eq!(size_of::<ThinComposableError<io::Error>>(), size_of::<usize>());
+    assert_eq!(size_of::<SharedComposableError<io::Error>>(), size_of::<usize>());
+    assert_eq!(size_of::<Result<(), ThinComposableError<io::Error>>>(), size_of::<usize>());
+    assert!(size_of::<ComposableError<io::Error>>() > size_of::<ThinComposableError<io::Error>>());
+}
+
+#[test]
+fn thin_error_converts_with_question_mark() {
+    fn plain() -> Result<(), ThinComposableError<&'static str>> {
+        Err("denied")?
+    }
+
+    fn with_context() -> Result<(), ThinComposableError<&'static str>> {
+        Err("denied").ctx("checking access")?;
+        Ok(())
+    }
+
+    assert_eq!(plain().unwrap_err().to_string(), "denied");
+    let err = with_context().ctx_boxed("handling request").unwrap_err();
+    assert_eq!(err.error_chain(), "handling request -> checking access -> denied");
+}
+
+#[test]
+fn thin_error_builds_like_composable_error() {
+    let thin = ThinComposableError::new("boom")
+        .with_context(ErrorContext::tag("db"))
+        .set_code(500);
+    let full = ComposableError::new("boom")
+        .with_context(ErrorContext::tag("db"))
+        .set_code(500);
+
+    assert_eq!(thin.clone().into_inner(), full);
+    assert_eq!(thin, full.into_thin());
+    assert_eq!(thin.to_string(), "[db] -> boom (code: 500)");
+}
+
+#[test]
+fn shared_error_clones_without_cloning_the_core_error() {
+    let shared = ComposableError::new(Unclonable("connection lost"))
+        .with_context("refreshing cache")
+        .into_shared();
+    let copies: Vec<_> = (0..4).map(|_| shared.clone()).collect();
+
+    assert_eq!(shared.share_count(), 5);
+    assert!(copies.iter().all(|copy| copy.ptr_eq(&shared)));
+    assert_eq!(copies[0].to_string(), "refreshing cache -> connection lost");
+
+    drop(copies);
+    let inner = shared.try_into_inner().unwrap();
+    assert_eq!(inner.core_error().0, "connection lost");
+}
+
+#[test]
+fn shared_error_broadcasts_to_threads() {
+    let shared: SharedComposableError<io::Error> =
+        ThinComposableError::new(io::Error::other("upstream closed"))
+            .with_context("fetching feed")
+            .into_shared();
+
+    let handles: Vec<_> = (0..3)
+        .map(|_| {
+            let err = shared.clone();
+            thread::spawn(move || err.error_chain())
+        })
+        .collect();
+
+    for handle in handles {
+        assert_eq!(handle.join().unwrap(), "fetching feed -> upstream closed");
+    }
+}
+
+#[test]
+fn shared_error_can_be_unwrapped_or_cloned_out() {
+    let shared = SharedComposableError::from(ComposableError::new("boom"));
+    let other = shared.clone();
+
+    let shared = shared.try_into_inner().unwrap_err();
+    assert_eq!(shared.clone().into_inner(), ComposableError::new("boom"));
+    drop(other);
+    assert_eq!(Arc::strong_count(&shared.into_arc()), 1);
+}
+
+#[test]
+fn shared_error_exposes_the_source_chain() {
+    let shared = ComposableError::new(io::Error::other("disk full")).into_shared();
+    let source = core::error::Error::source(&shared).unwrap();
+
+    assert_eq!(source.to_string(), "disk full");
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn thin_and_shared_errors_serialize_like_composable_error() {
+    let full = ComposableError::new("boom".to_string()).with_context("loading");
+    let json = serde_json::to_value(&full).unwrap();
+
+    assert_eq!(serde_json::to_value(full.clone().into_thin()).unwrap(), json);
+    assert_eq!(serde_json::to_value(full.clone().into_shared()).unwrap(), json);
+
+    let thin: ThinComposableError<String> = serde_json::from_value(json.clone()).unwrap();
+    let shared: SharedComposableError<String> = serde_json::from_value(json).unwrap();
+    assert_eq!(thin.into_inner(), full);
+    assert_eq!(shared.into_inner(), full);
+}
@@ -0,0 +1,134 @@
+use error_rail::types::{Clock, ManualClock, Timestamp};
+use error_rail::{group, ComposableError, ErrorContext, RailError};
+use std::time::Duration;
+
+const START: u64 = 1_700_000_000_000;
+
+#[test]
+fn timestamp_renders_as_rfc3339() {
+    assert_eq!(Timestamp::UNIX_EPOCH.to_string(), "1970-01-01T00:00:00.000Z");
+    assert_eq!(Timestamp::from_unix_millis(START + 250).to_string(), "2023-11-14T22:13:20.250Z");
+    assert_eq!(
+        Timestamp::from_unix_millis(951_782_400_000).to_string(),
+        "2000-02-29T00:00:00.000Z"
+    );
+}
+
+#[test]
+fn manual_clock_only_moves_when_told() {
+    let clock = ManualClock::new(Timestamp::from_unix_millis(START));
+    assert_eq!(clock.now(), clock.now());
+
+    clock.advance(Duration::from_secs(2));
+    assert_eq!(clock.now().unix_millis(), START + 2000);
+
+    clock.set(Timestamp::UNIX_EPOCH);
+    assert_eq!(clock.now(), Timestamp::UNIX_EPOCH);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn system_clock_matches_system_time() {
+    use error_rail::types::SystemClock;
+
+    let before = Timestamp::from(std::time::SystemTime::now());
+    let now = SystemClock.now();
+    assert!(now >= before);
+    assert!(ComposableError::new("e").stamp().timestamp().is_some());
+}
+
+#[test]
+fn contexts_record_when_they_were_attached() {
+    let clock = ManualClock::new(Timestamp::from_unix_millis(START));
+    let mut err = ComposableError::new("upstream timeout").stamp_with(&clock);
+    for attempt in ["retry 1", "retry 2"] {
+        clock.advance(Duration::from_millis(100));
+        err = err.with_context_at(attempt, &clock);
+    }
+
+    let times: Vec<_> = err
+        .context_iter()
+        .map(|ctx| {
+            ctx.timestamp()
+                .unwrap()
+                .duration_since(err.timestamp().unwrap())
+        })
+        .collect();
+    assert_eq!(times, [Some(Duration::from_millis(200)), Some(Duration::from_millis(100))]);
+    assert_eq!(err.to_string(), "retry 2 -> retry 1 -> upstream timeout");
+}
+
+#[test]
+fn builder_and_group_macro_accept_timestamps() {
+    let ts = Timestamp::from_unix_millis(START);
+    let built = ErrorContext::builder().tag("db").timestamp(ts).build();
+    let err = ComposableError::new("e").with_context(group!(tag("db"), timestamp(ts)));
+
+    assert_eq!(built.timestamp(), Some(ts));
+    assert_eq!(err.context()[0].timestamp(), Some(ts));
+    assert_eq!(ErrorContext::new("plain").timestamp(), None);
+}
+
+#[test]
+fn timestamps_are_rendered_on_request() {
+    let clock = ManualClock::new(Timestamp::from_unix_millis(START));
+    let cause = ComposableError::new("refused").stamp_with(&clock);
+    let err = ComposableError::new("connect failed")
+        .with_context_at("dialing", &clock)
+        .with_cause(cause)
+        .set_code(7);
+
+    assert_eq!(err.to_string(), "dialing -> connect failed (code: 7) -> caused by: refused");
+    assert_eq!(
+        err.fmt().show_timestamps(true).to_string(),
+        "2023-11-14T22:13:20.000Z dialing -> connect failed (code: 7) -> caused by: \
+         2023-11-14T22:13:20.000Z refused"
+    );
+}
+
+#[test]
+fn timestamps_are_not_in_default_fingerprint() {
+    let a = ComposableError::new("e").with_timestamp(Timestamp::from_unix_millis(1));
+    let b = ComposableError::new("e").with_timestamp(Timestamp::from_unix_millis(2));
+
+    assert_eq!(a.fingerprint(), b.fingerprint());
+    assert_eq!(a.fingerprint(), ComposableError::new("e").fingerprint());
+    assert_ne!(
+        a.fingerprint_config().include_timestamps(true).compute(),
+        b.fingerprint_config().include_timestamps(true).compute()
+    );
+    assert_ne!(a, b);
+}
+
+#[test]
+fn rail_error_keeps_the_earliest_timestamp() {
+    let inner: RailError = ComposableError::new(std::io::Error::other("io"))
+        .with_timestamp(Timestamp::from_unix_millis(1))
+        .into();
+    let outer: RailError = ComposableError::new(inner)
+        .with_timestamp(Timestamp::from_unix_millis(2))
+        .into();
+
+    assert_eq!(outer.timestamp(), Some(Timestamp::from_unix_millis(1)));
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn timestamps_round_trip_through_serde() {
+    let clock = ManualClock::new(Timestamp::from_unix_millis(START));
+    let err = ComposableError::new("e".to_string())
+        .stamp_with(&clock)
+        .with_context_at("ctx", &clock)
+        .with_cause(ComposableError::new("c").stamp_with(&clock));
+
+    let json = serde_json::to_value(&err).unwrap();
+    assert_eq!(json["timestamp"]["secs"], START / 1000);
+    assert!(json["causes"][0]["timestamp"].is_object());
+
+    let back: ComposableError<String> = serde_json::from_value(json).unwrap();
+    assert_eq!(back, err);
+    assert_eq!(back.context()[0].timestamp(), err.context()[0].timestamp());
+
+    let plain = serde_json::to_string(&ComposableError::new("p".to_string())).unwrap();
+    assert!(!plain.contains("\"timestamp\""));
+}
@@ -0,0 +1,116 @@
+use error_rail::types::TraceIds;
+use error_rail::{ComposableError, RailError};
+
+const TRACEPARENT: &str = "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01";
+
+#[test]
+fn traceparent_is_parsed() {
+    let ids = TraceIds::from_traceparent(TRACEPARENT).unwrap();
+    assert_eq!(ids.trace_id.as_deref(), Some("4bf92f3577b34da6a3ce929d0e0e4736"));
+    assert_eq!(ids.span_id.as_deref(), Some("00f067aa0ba902b7"));
+    assert_eq!(ids.correlation_id, None);
+
+    // Future versions may append fields.
+    assert!(TraceIds::from_traceparent(
+        "cc-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01-extra"
+    )
+    .is_some());
+}
+
+#[test]
+fn invalid_traceparents_are_rejected() {
+    for header in [
+        "",
+        "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7",
+        "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01-extra",
+        "ff-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01",
+        "00-00000000000000000000000000000000-00f067aa0ba902b7-01",
+        "00-4bf92f3577b34da6a3ce929d0e0e4736-0000000000000000-01",
+        "00-4BF92F3577B34DA6A3CE929D0E0E4736-00f067aa0ba902b7-01",
+        "00-4bf92f3577b34da6a3ce929d0e0e473-00f067aa0ba902b7-01",
+    ] {
+        assert!(TraceIds::from_traceparent(header).is_none(), "{header}");
+    }
+}
+
+#[test]
+fn ids_are_set_individually() {
+    let err = ComposableError::new("e")
+        .with_correlation_id("req-7")
+        .with_traceparent(TRACEPARENT)
+        .with_span_id("override");
+
+    assert_eq!(err.trace_id(), Some("4bf92f3577b34da6a3ce929d0e0e4736"));
+    assert_eq!(err.span_id(), Some("override"));
+    assert_eq!(err.correlation_id(), Some("req-7"));
+
+    let cleared = err.with_trace_ids(TraceIds::default());
+    assert_eq!(cleared.trace_ids(), None);
+}
+
+#[test]
+fn ids_are_rendered_after_the_core_error() {
+    let cause = ComposableError::new("refused").with_correlation_id("req-1");
+    let err = ComposableError::new("connect failed")
+        .with_context("dialing")
+        .with_trace_id("t1")
+        .set_code(7)
+        .with_cause(cause);
+
+    assert_eq!(
+        err.to_string(),
+        "dialing -> connect failed (code: 7) (trace_id=t1) -> caused by: refused (correlation_id=req-1)"
+    );
+    assert_eq!(
+        err.fmt().show_trace_ids(false).to_string(),
+        "dialing -> connect failed (code: 7) -> caused by: refused"
+    );
+
+    let single = ComposableError::new("e").with_trace_id("t1").set_code(1);
+    assert_eq!(single.to_string(), "e (code: 1) (trace_id=t1)");
+    assert_eq!(single.error_chain_with(error_rail::ErrorFormatConfig::default()), "e");
+}
+
+#[test]
+fn ids_are_ignored_by_default_fingerprint() {
+    let a = ComposableError::new("e").with_trace_id("a");
+    let b = ComposableError::new("e").with_trace_id("b");
+
+    assert_eq!(a.fingerprint(), b.fingerprint());
+    assert_ne!(
+        a.fingerprint_config().include_trace_ids(true).compute(),
+        b.fingerprint_config().include_trace_ids(true).compute()
+    );
+}
+
+#[test]
+fn rail_error_fills_missing_ids_from_outer_layers() {
+    let inner: RailError = ComposableError::new(std::io::Error::other("io"))
+        .with_span_id("inner-span")
+        .into();
+    let outer: RailError = ComposableError::new(inner)
+        .with_traceparent(TRACEPARENT)
+        .into();
+
+    assert_eq!(outer.span_id(), Some("inner-span"));
+    assert_eq!(outer.trace_id(), Some("4bf92f3577b34da6a3ce929d0e0e4736"));
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn ids_round_trip_through_serde() {
+    let err = ComposableError::new("e".to_string())
+        .with_traceparent(TRACEPARENT)
+        .with_cause(ComposableError::new("c").with_correlation_id("req-1"));
+
+    let json = serde_json::to_value(&err).unwrap();
+    assert_eq!(json["trace_ids"]["span_id"], "00f067aa0ba902b7");
+    assert!(json["trace_ids"].get("correlation_id").is_none());
+    assert_eq!(json["causes"][0]["trace_ids"]["correlation_id"], "req-1");
+
+    let back: ComposableError<String> = serde_json::from_value(json).unwrap();
+    assert_eq!(back, err);
+
+    let plain = serde_json::to_string(&ComposableError::new("p".to_string())).unwrap();
+    assert!(!plain.contains("trace_ids"));
+}
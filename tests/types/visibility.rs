@@ -0,0 +1,134 @@
+use error_rail::types::{MemoryCatalog, PublicView, Visibility};
+use error_rail::{group, ComposableError, ErrorContext, RailError};
+
+fn order_error() -> ComposableError<&'static str> {
+    ComposableError::new("connection refused: db-7.internal:5432")
+        .with_context("SELECT * FROM orders WHERE id = 42")
+        .with_context(ErrorContext::public("loading your order"))
+        .with_context(
+            ErrorContext::builder()
+                .tag("http")
+                .location("/srv/app/api.rs", 12)
+                .build(),
+        )
+}
+
+#[test]
+fn contexts_are_internal_by_default() {
+    assert_eq!(ErrorContext::new("plain").visibility(), Visibility::Internal);
+    assert_eq!(ErrorContext::tag("db").visibility(), Visibility::Internal);
+    assert!(ErrorContext::public("checking out").is_public());
+    assert!(ErrorContext::builder()
+        .visibility(Visibility::Public)
+        .build()
+        .is_public());
+}
+
+#[test]
+fn public_view_shows_only_public_parts() {
+    let err = order_error()
+        .with_public_message("the order could not be loaded")
+        .with_hint("try again later")
+        .set_code(503);
+
+    assert_eq!(
+        err.public_view().to_string(),
+        "loading your order -> the order could not be loaded (code: 503) (hint: try again later)"
+    );
+    let chain = err.error_chain();
+    assert!(chain.contains("SELECT * FROM orders"));
+    assert!(chain.contains("db-7.internal"));
+    assert!(!chain.contains("the order could not be loaded"));
+}
+
+#[test]
+fn public_view_falls_back_to_a_generic_message() {
+    let err = order_error();
+
+    assert_eq!(
+        err.public_view().to_string(),
+        format!("loading your order -> {}", PublicView::<&str>::DEFAULT_MESSAGE)
+    );
+    assert_eq!(
+        err.public_view()
+            .fallback_message("something went wrong")
+            .message(),
+        "something went wrong"
+    );
+    assert_eq!(
+        ComposableError::new("boom")
+            .public_view()
+            .contexts()
+            .count(),
+        0
+    );
+}
+
+#[test]
+fn public_view_exposes_its_parts() {
+    let err = order_error().with_hint("retry").set_code("ORD-503");
+    let view = err.public_view();
+
+    assert_eq!(view.contexts().map(|c| c.message()).collect::<Vec<_>>(), ["loading your order"]);
+    assert_eq!(view.hint(), Some("retry"));
+    assert_eq!(view.code().unwrap().to_string(), "ORD-503");
+}
+
+#[test]
+fn public_contexts_keep_redaction_and_localization() {
+    let catalog =
+        MemoryCatalog::new().with_message("de", "order.load", "Bestellung {id} wird geladen");
+    let err = ComposableError::new("timeout")
+        .with_context(group!(message_key("order.load"), metadata("id", 7), public()))
+        .with_context(
+            ErrorContext::builder()
+                .message("verifying card")
+                .secret_metadata("card", "4111")
+                .public()
+                .build(),
+        )
+        .with_public_message("Zahlung fehlgeschlagen");
+
+    assert_eq!(
+        err.public_view().locale(&catalog, "de-DE").to_string(),
+        "verifying card (card=[REDACTED]) -> Bestellung 7 wird geladen -> Zahlung fehlgeschlagen"
+    );
+}
+
+#[test]
+fn rail_error_keeps_the_outer_public_message() {
+    let inner: RailError = ComposableError::new(std::io::Error::other("disk full"))
+        .with_public_message("upload failed")
+        .with_hint("free some space")
+        .into();
+    let merged: RailError = ComposableError::new(inner)
+        .with_public_message("the file could not be saved")
+        .into();
+
+    assert_eq!(merged.public_message(), Some("the file could not be saved"));
+    assert_eq!(merged.hint(), Some("free some space"));
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn public_parts_round_trip_through_serde() {
+    let err = ComposableError::new("boom".to_string())
+        .with_context(ErrorContext::public("saving"))
+        .with_public_message("could not save")
+        .with_hint("retry");
+
+    let json = serde_json::to_value(&err).unwrap();
+    assert_eq!(json["public_message"], "could not save");
+    assert_eq!(json["hint"], "retry");
+    assert_eq!(json["context"][0]["Group"]["visibility"], "public");
+
+    let back: ComposableError<String> = serde_json::from_value(json).unwrap();
+    assert_eq!(back.public_view().to_string(), err.public_view().to_string());
+
+    let plain = serde_json::to_value(
+        ComposableError::new("p".to_string()).with_context(ErrorContext::tag("x")),
+    )
+    .unwrap();
+    assert!(plain.get("public_message").is_none());
+    assert!(plain["context"][0]["Group"].get("visibility").is_none());
+}